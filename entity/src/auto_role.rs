@@ -6,8 +6,11 @@ use sea_orm::entity::prelude::*;
 #[sea_orm(table_name = "auto_role")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
-    pub id:    String,
+    pub id: String,
     pub group: Option<String>,
+    pub min_level: Option<i32>,
+    pub requires_character: bool,
+    pub approval: bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -20,6 +23,12 @@ pub enum Relation {
         on_delete = "Cascade"
     )]
     AutoRoleGroup,
+    #[sea_orm(has_many = "super::auto_role_prerequisite::Entity")]
+    AutoRolePrerequisite,
+    #[sea_orm(has_many = "super::auto_role_request::Entity")]
+    AutoRoleRequest,
+    #[sea_orm(has_many = "super::reaction_role::Entity")]
+    ReactionRole,
 }
 
 impl Related<super::auto_role_group::Entity> for Entity {
@@ -28,4 +37,22 @@ impl Related<super::auto_role_group::Entity> for Entity {
     }
 }
 
+impl Related<super::auto_role_prerequisite::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AutoRolePrerequisite.def()
+    }
+}
+
+impl Related<super::auto_role_request::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::AutoRoleRequest.def()
+    }
+}
+
+impl Related<super::reaction_role::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::ReactionRole.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
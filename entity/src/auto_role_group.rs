@@ -6,13 +6,17 @@ use sea_orm::entity::prelude::*;
 #[sea_orm(table_name = "auto_role_group")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
-    pub name: String,
+    pub name:      String,
+    pub max_roles: Option<i32>,
+    pub required:  bool,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::auto_role::Entity")]
     AutoRole,
+    #[sea_orm(has_many = "super::role_panel::Entity")]
+    RolePanel,
 }
 
 impl Related<super::auto_role::Entity> for Entity {
@@ -21,4 +25,10 @@ impl Related<super::auto_role::Entity> for Entity {
     }
 }
 
+impl Related<super::role_panel::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::RolePanel.def()
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
@@ -1,7 +1,40 @@
 pub mod prelude;
 
+pub mod audit_log;
 pub mod auto_role;
 pub mod auto_role_group;
+pub mod auto_role_prerequisite;
+pub mod auto_role_request;
+pub mod automod_exemption;
+pub mod automod_rule;
+pub mod calendar;
+pub mod calendar_month;
+pub mod character;
+pub mod character_stat;
+pub mod combatant;
+pub mod difficulty;
+pub mod encounter;
+pub mod escalation;
+pub mod event;
+pub mod event_rsvp;
+pub mod faction;
+pub mod faction_member;
+pub mod faction_rank;
+pub mod faction_request;
+pub mod greeting;
+pub mod inventory_item;
 pub mod member;
+pub mod mod_case;
+pub mod planet;
+pub mod reaction_role;
+pub mod role_panel;
+pub mod route;
+pub mod ship;
+pub mod ship_cargo;
+pub mod ship_class;
+pub mod ship_crew;
+pub mod temp_role;
+pub mod timeline_event;
+pub mod verification;
 pub mod xp_channel;
 pub mod xp_role;
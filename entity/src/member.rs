@@ -6,14 +6,25 @@ use sea_orm::entity::prelude::*;
 #[sea_orm(table_name = "member")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
-    pub id:          String,
-    pub balance:     i64,
-    pub xp:          i32,
-    pub level:       i32,
-    pub permissions: i32,
+    pub id:               String,
+    pub balance:          i64,
+    pub xp:               i32,
+    pub level:            i32,
+    pub permissions:      i32,
+    pub active_character: Option<i32>,
+    pub departed_at:      Option<DateTimeUtc>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
-pub enum Relation {}
+pub enum Relation {
+    #[sea_orm(has_many = "super::character::Entity")]
+    Character,
+}
+
+impl Related<super::character::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Character.def()
+    }
+}
 
 impl ActiveModelBehavior for ActiveModel {}
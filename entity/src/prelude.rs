@@ -1,9 +1,42 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.12.15
 
 pub use super::{
+    audit_log::Entity as AuditLog,
     auto_role::Entity as AutoRole,
     auto_role_group::Entity as AutoRoleGroup,
+    auto_role_prerequisite::Entity as AutoRolePrerequisite,
+    auto_role_request::Entity as AutoRoleRequest,
+    automod_exemption::Entity as AutomodExemption,
+    automod_rule::Entity as AutomodRule,
+    calendar::Entity as Calendar,
+    calendar_month::Entity as CalendarMonth,
+    character::Entity as Character,
+    character_stat::Entity as CharacterStat,
+    combatant::Entity as Combatant,
+    difficulty::Entity as Difficulty,
+    encounter::Entity as Encounter,
+    escalation::Entity as Escalation,
+    event::Entity as Event,
+    event_rsvp::Entity as EventRsvp,
+    faction::Entity as Faction,
+    faction_member::Entity as FactionMember,
+    faction_rank::Entity as FactionRank,
+    faction_request::Entity as FactionRequest,
+    greeting::Entity as Greeting,
+    inventory_item::Entity as InventoryItem,
     member::Entity as Member,
+    mod_case::Entity as ModCase,
+    planet::Entity as Planet,
+    reaction_role::Entity as ReactionRole,
+    role_panel::Entity as RolePanel,
+    route::Entity as Route,
+    ship::Entity as Ship,
+    ship_cargo::Entity as ShipCargo,
+    ship_class::Entity as ShipClass,
+    ship_crew::Entity as ShipCrew,
+    temp_role::Entity as TempRole,
+    timeline_event::Entity as TimelineEvent,
+    verification::Entity as Verification,
     xp_channel::Entity as XpChannel,
     xp_role::Entity as XpRole,
 };
@@ -1,12 +1,60 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20220101_000001_create_table;
+mod m20261019_000001_create_audit_log;
+mod m20261019_000002_create_character;
+mod m20261019_000003_character_wallets;
+mod m20261019_000004_character_review;
+mod m20261019_000005_character_proxy;
+mod m20261019_000006_create_faction;
+mod m20261019_000007_auto_role_requirements;
+mod m20261019_000008_create_role_panel;
+mod m20261019_000009_create_reaction_role;
+mod m20261019_000010_auto_role_group_modes;
+mod m20261019_000011_create_temp_role;
+mod m20261019_000012_create_verification;
+mod m20261019_000013_create_greeting;
+mod m20261019_000014_member_departed;
+mod m20261019_000015_create_mod_case;
+mod m20261019_000016_create_escalation;
+mod m20261019_000017_create_automod;
+mod m20261019_000018_create_character_stat;
+mod m20261019_000019_create_encounter;
+mod m20261019_000020_create_galaxy;
+mod m20261019_000021_create_ship;
+mod m20261019_000022_create_calendar;
+mod m20261019_000023_create_event;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20220101_000001_create_table::Migration)]
+        vec![
+            Box::new(m20220101_000001_create_table::Migration),
+            Box::new(m20261019_000001_create_audit_log::Migration),
+            Box::new(m20261019_000002_create_character::Migration),
+            Box::new(m20261019_000003_character_wallets::Migration),
+            Box::new(m20261019_000004_character_review::Migration),
+            Box::new(m20261019_000005_character_proxy::Migration),
+            Box::new(m20261019_000006_create_faction::Migration),
+            Box::new(m20261019_000007_auto_role_requirements::Migration),
+            Box::new(m20261019_000008_create_role_panel::Migration),
+            Box::new(m20261019_000009_create_reaction_role::Migration),
+            Box::new(m20261019_000010_auto_role_group_modes::Migration),
+            Box::new(m20261019_000011_create_temp_role::Migration),
+            Box::new(m20261019_000012_create_verification::Migration),
+            Box::new(m20261019_000013_create_greeting::Migration),
+            Box::new(m20261019_000014_member_departed::Migration),
+            Box::new(m20261019_000015_create_mod_case::Migration),
+            Box::new(m20261019_000016_create_escalation::Migration),
+            Box::new(m20261019_000017_create_automod::Migration),
+            Box::new(m20261019_000018_create_character_stat::Migration),
+            Box::new(m20261019_000019_create_encounter::Migration),
+            Box::new(m20261019_000020_create_galaxy::Migration),
+            Box::new(m20261019_000021_create_ship::Migration),
+            Box::new(m20261019_000022_create_calendar::Migration),
+            Box::new(m20261019_000023_create_event::Migration),
+        ]
     }
 }
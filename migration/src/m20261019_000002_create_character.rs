@@ -0,0 +1,78 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Character::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Character::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Character::Owner).text().not_null())
+                    .col(ColumnDef::new(Character::Name).text().not_null())
+                    .col(ColumnDef::new(Character::Species).text().not_null())
+                    .col(ColumnDef::new(Character::Faction).text())
+                    .col(ColumnDef::new(Character::Affiliation).text())
+                    .col(ColumnDef::new(Character::Age).integer())
+                    .col(ColumnDef::new(Character::ThreadId).text())
+                    .col(
+                        ColumnDef::new(Character::Status)
+                            .text()
+                            .not_null()
+                            .default("submitted"),
+                    )
+                    .col(
+                        ColumnDef::new(Character::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_character_owner")
+                            .from(Character::Table, Character::Owner)
+                            .to(Member::Table, Member::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Character::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    Id,
+    Owner,
+    Name,
+    Species,
+    Faction,
+    Affiliation,
+    Age,
+    ThreadId,
+    Status,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Member {
+    Table,
+    Id,
+}
@@ -0,0 +1,138 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Member::Table)
+                    .add_column(ColumnDef::new(Member::ActiveCharacter).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .add_column(
+                        ColumnDef::new(Character::Balance)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .add_column(
+                        ColumnDef::new(Character::Xp)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(InventoryItem::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(InventoryItem::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(InventoryItem::Character)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(InventoryItem::Name).text().not_null())
+                    .col(
+                        ColumnDef::new(InventoryItem::Quantity)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_inventory_item_character")
+                            .from(InventoryItem::Table, InventoryItem::Character)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(InventoryItem::Table).to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .drop_column(Character::Xp)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .drop_column(Character::Balance)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Member::Table)
+                    .drop_column(Member::ActiveCharacter)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Member {
+    Table,
+    ActiveCharacter,
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    Id,
+    Balance,
+    Xp,
+}
+
+#[derive(DeriveIden)]
+enum InventoryItem {
+    Table,
+    Id,
+    Character,
+    Name,
+    Quantity,
+}
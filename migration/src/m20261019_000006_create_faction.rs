@@ -0,0 +1,201 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Faction::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Faction::Name)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Faction::Description).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(FactionRank::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FactionRank::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FactionRank::Faction).text().not_null())
+                    .col(ColumnDef::new(FactionRank::Name).text().not_null())
+                    .col(ColumnDef::new(FactionRank::Position).integer().not_null())
+                    .col(ColumnDef::new(FactionRank::Role).text().not_null())
+                    .col(
+                        ColumnDef::new(FactionRank::Officer)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_faction_rank_faction")
+                            .from(FactionRank::Table, FactionRank::Faction)
+                            .to(Faction::Table, Faction::Name)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(FactionMember::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FactionMember::Character)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FactionMember::Faction).text().not_null())
+                    .col(ColumnDef::new(FactionMember::Rank).integer().not_null())
+                    .col(
+                        ColumnDef::new(FactionMember::JoinedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_faction_member_character")
+                            .from(FactionMember::Table, FactionMember::Character)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_faction_member_faction")
+                            .from(FactionMember::Table, FactionMember::Faction)
+                            .to(Faction::Table, Faction::Name)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_faction_member_rank")
+                            .from(FactionMember::Table, FactionMember::Rank)
+                            .to(FactionRank::Table, FactionRank::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(FactionRequest::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(FactionRequest::Character)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(FactionRequest::Faction).text().not_null())
+                    .col(
+                        ColumnDef::new(FactionRequest::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_faction_request_character")
+                            .from(FactionRequest::Table, FactionRequest::Character)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_faction_request_faction")
+                            .from(FactionRequest::Table, FactionRequest::Faction)
+                            .to(Faction::Table, Faction::Name)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(FactionRequest::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(FactionMember::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(FactionRank::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Faction::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Faction {
+    Table,
+    Name,
+    Description,
+}
+
+#[derive(DeriveIden)]
+enum FactionRank {
+    Table,
+    Id,
+    Faction,
+    Name,
+    Position,
+    Role,
+    Officer,
+}
+
+#[derive(DeriveIden)]
+enum FactionMember {
+    Table,
+    Character,
+    Faction,
+    Rank,
+    JoinedAt,
+}
+
+#[derive(DeriveIden)]
+enum FactionRequest {
+    Table,
+    Character,
+    Faction,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    Id,
+}
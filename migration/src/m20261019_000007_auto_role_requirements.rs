@@ -0,0 +1,165 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AutoRole::Table)
+                    .add_column(ColumnDef::new(AutoRole::MinLevel).integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AutoRole::Table)
+                    .add_column(
+                        ColumnDef::new(AutoRole::RequiresCharacter)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AutoRole::Table)
+                    .add_column(
+                        ColumnDef::new(AutoRole::Approval)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutoRolePrerequisite::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AutoRolePrerequisite::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(AutoRolePrerequisite::AutoRole)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(AutoRolePrerequisite::Role).text().not_null())
+                    .col(
+                        ColumnDef::new(AutoRolePrerequisite::Held)
+                            .boolean()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_auto_role_prerequisite_auto_role")
+                            .from(AutoRolePrerequisite::Table, AutoRolePrerequisite::AutoRole)
+                            .to(AutoRole::Table, AutoRole::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutoRoleRequest::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AutoRoleRequest::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AutoRoleRequest::AutoRole).text().not_null())
+                    .col(ColumnDef::new(AutoRoleRequest::Member).text().not_null())
+                    .col(
+                        ColumnDef::new(AutoRoleRequest::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_auto_role_request_auto_role")
+                            .from(AutoRoleRequest::Table, AutoRoleRequest::AutoRole)
+                            .to(AutoRole::Table, AutoRole::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AutoRoleRequest::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AutoRolePrerequisite::Table).to_owned())
+            .await?;
+
+        for column in [
+            AutoRole::Approval,
+            AutoRole::RequiresCharacter,
+            AutoRole::MinLevel,
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(AutoRole::Table)
+                        .drop_column(column)
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(DeriveIden)]
+enum AutoRole {
+    Table,
+    Id,
+    MinLevel,
+    RequiresCharacter,
+    Approval,
+}
+
+#[derive(DeriveIden)]
+enum AutoRolePrerequisite {
+    Table,
+    Id,
+    AutoRole,
+    Role,
+    Held,
+}
+
+#[derive(DeriveIden)]
+enum AutoRoleRequest {
+    Table,
+    Id,
+    AutoRole,
+    Member,
+    CreatedAt,
+}
@@ -0,0 +1,56 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RolePanel::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RolePanel::Id)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(RolePanel::Channel).text().not_null())
+                    .col(ColumnDef::new(RolePanel::Group).text().not_null())
+                    .col(ColumnDef::new(RolePanel::Style).text().not_null())
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_role_panel_group")
+                            .from(RolePanel::Table, RolePanel::Group)
+                            .to(AutoRoleGroup::Table, AutoRoleGroup::Name)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RolePanel::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum RolePanel {
+    Table,
+    Id,
+    Channel,
+    Group,
+    Style,
+}
+
+#[derive(DeriveIden)]
+enum AutoRoleGroup {
+    Table,
+    Name,
+}
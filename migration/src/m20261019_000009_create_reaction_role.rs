@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ReactionRole::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ReactionRole::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ReactionRole::Channel).text().not_null())
+                    .col(ColumnDef::new(ReactionRole::Message).text().not_null())
+                    .col(ColumnDef::new(ReactionRole::Emoji).text().not_null())
+                    .col(ColumnDef::new(ReactionRole::Role).text().not_null())
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_reaction_role_role")
+                            .from(ReactionRole::Table, ReactionRole::Role)
+                            .to(AutoRole::Table, AutoRole::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_reaction_role_message_emoji")
+                    .table(ReactionRole::Table)
+                    .col(ReactionRole::Message)
+                    .col(ReactionRole::Emoji)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ReactionRole::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ReactionRole {
+    Table,
+    Id,
+    Channel,
+    Message,
+    Emoji,
+    Role,
+}
+
+#[derive(DeriveIden)]
+enum AutoRole {
+    Table,
+    Id,
+}
@@ -0,0 +1,60 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // existing groups were single choice, so they keep a limit of one
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AutoRoleGroup::Table)
+                    .add_column(ColumnDef::new(AutoRoleGroup::MaxRoles).integer().default(1))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AutoRoleGroup::Table)
+                    .add_column(
+                        ColumnDef::new(AutoRoleGroup::Required)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AutoRoleGroup::Table)
+                    .drop_column(AutoRoleGroup::Required)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(AutoRoleGroup::Table)
+                    .drop_column(AutoRoleGroup::MaxRoles)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AutoRoleGroup {
+    Table,
+    MaxRoles,
+    Required,
+}
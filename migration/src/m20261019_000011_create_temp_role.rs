@@ -0,0 +1,57 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TempRole::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TempRole::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TempRole::Member).text().not_null())
+                    .col(ColumnDef::new(TempRole::Role).text().not_null())
+                    .col(ColumnDef::new(TempRole::GrantedBy).text().not_null())
+                    .col(ColumnDef::new(TempRole::Reason).text())
+                    .col(
+                        ColumnDef::new(TempRole::ExpiresAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TempRole::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TempRole::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TempRole {
+    Table,
+    Id,
+    Member,
+    Role,
+    GrantedBy,
+    Reason,
+    ExpiresAt,
+    CreatedAt,
+}
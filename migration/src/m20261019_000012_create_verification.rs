@@ -0,0 +1,46 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Verification::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Verification::Member)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Verification::JoinedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Verification::Answers).text())
+                    .col(ColumnDef::new(Verification::SubmittedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Verification::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Verification {
+    Table,
+    Member,
+    JoinedAt,
+    Answers,
+    SubmittedAt,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Greeting::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Greeting::Kind)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Greeting::Channel).text())
+                    .col(ColumnDef::new(Greeting::Title).text())
+                    .col(ColumnDef::new(Greeting::Message).text().not_null())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Greeting::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Greeting {
+    Table,
+    Kind,
+    Channel,
+    Title,
+    Message,
+}
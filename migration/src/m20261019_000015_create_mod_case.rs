@@ -0,0 +1,65 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModCase::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModCase::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ModCase::Member).text().not_null())
+                    .col(ColumnDef::new(ModCase::Moderator).text().not_null())
+                    .col(ColumnDef::new(ModCase::Action).text().not_null())
+                    .col(ColumnDef::new(ModCase::Reason).text().not_null())
+                    .col(ColumnDef::new(ModCase::Duration).big_integer())
+                    .col(ColumnDef::new(ModCase::Evidence).text())
+                    .col(
+                        ColumnDef::new(ModCase::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_mod_case_member")
+                    .table(ModCase::Table)
+                    .col(ModCase::Member)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ModCase::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ModCase {
+    Table,
+    Id,
+    Member,
+    Moderator,
+    Action,
+    Reason,
+    Duration,
+    Evidence,
+    CreatedAt,
+}
@@ -0,0 +1,40 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Escalation::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Escalation::Warnings)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Escalation::Action).text().not_null())
+                    .col(ColumnDef::new(Escalation::Duration).big_integer())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Escalation::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Escalation {
+    Table,
+    Warnings,
+    Action,
+    Duration,
+}
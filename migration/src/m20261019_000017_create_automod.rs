@@ -0,0 +1,86 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutomodRule::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AutomodRule::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AutomodRule::Kind).text().not_null())
+                    .col(ColumnDef::new(AutomodRule::Pattern).text())
+                    .col(ColumnDef::new(AutomodRule::Threshold).integer())
+                    .col(ColumnDef::new(AutomodRule::Action).text().not_null())
+                    .col(ColumnDef::new(AutomodRule::Duration).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        // an exemption without a rule covers every rule
+        manager
+            .create_table(
+                Table::create()
+                    .table(AutomodExemption::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AutomodExemption::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AutomodExemption::Channel).text().not_null())
+                    .col(ColumnDef::new(AutomodExemption::Rule).integer())
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_automod_exemption_rule")
+                            .from(AutomodExemption::Table, AutomodExemption::Rule)
+                            .to(AutomodRule::Table, AutomodRule::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AutomodExemption::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(AutomodRule::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum AutomodRule {
+    Table,
+    Id,
+    Kind,
+    Pattern,
+    Threshold,
+    Action,
+    Duration,
+}
+
+#[derive(DeriveIden)]
+enum AutomodExemption {
+    Table,
+    Id,
+    Channel,
+    Rule,
+}
@@ -0,0 +1,153 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CharacterStat::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CharacterStat::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CharacterStat::Character)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CharacterStat::Kind).text().not_null())
+                    .col(ColumnDef::new(CharacterStat::Name).text().not_null())
+                    .col(ColumnDef::new(CharacterStat::Value).integer().not_null())
+                    .col(ColumnDef::new(CharacterStat::Attribute).text())
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_character_stat_character")
+                            .from(CharacterStat::Table, CharacterStat::Character)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_character_stat_character_name")
+                    .table(CharacterStat::Table)
+                    .col(CharacterStat::Character)
+                    .col(CharacterStat::Name)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(Difficulty::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Difficulty::Name)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Difficulty::Target).integer().not_null())
+                    .col(
+                        ColumnDef::new(Difficulty::Xp)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(Difficulty::Credits)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // start with the usual d20 tiers so checks work out of the box
+        let mut tiers = Query::insert()
+            .into_table(Difficulty::Table)
+            .columns([Difficulty::Name, Difficulty::Target])
+            .to_owned();
+        for (name, target) in [
+            ("Easy", 10),
+            ("Medium", 15),
+            ("Hard", 20),
+            ("Very Hard", 25),
+        ] {
+            tiers.values_panic([name.into(), target.into()]);
+        }
+        manager.exec_stmt(tiers).await?;
+
+        // when a check last paid the character, so rewards can cool down
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .add_column(ColumnDef::new(Character::RewardedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .drop_column(Character::RewardedAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Difficulty::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CharacterStat::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum CharacterStat {
+    Table,
+    Id,
+    Character,
+    Kind,
+    Name,
+    Value,
+    Attribute,
+}
+
+#[derive(DeriveIden)]
+enum Difficulty {
+    Table,
+    Name,
+    Target,
+    Xp,
+    Credits,
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    Id,
+    RewardedAt,
+}
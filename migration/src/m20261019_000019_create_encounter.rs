@@ -0,0 +1,151 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Encounter::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Encounter::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Encounter::Channel).text().not_null())
+                    .col(ColumnDef::new(Encounter::Message).text())
+                    .col(ColumnDef::new(Encounter::Gm).text().not_null())
+                    .col(
+                        ColumnDef::new(Encounter::Round)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .col(ColumnDef::new(Encounter::Current).integer())
+                    .col(
+                        ColumnDef::new(Encounter::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Encounter::EndedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_encounter_channel")
+                    .table(Encounter::Table)
+                    .col(Encounter::Channel)
+                    .to_owned(),
+            )
+            .await?;
+
+        // combatants without a character are npcs run by the gm
+        manager
+            .create_table(
+                Table::create()
+                    .table(Combatant::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Combatant::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Combatant::Encounter).integer().not_null())
+                    .col(ColumnDef::new(Combatant::Name).text().not_null())
+                    .col(ColumnDef::new(Combatant::Character).integer())
+                    .col(ColumnDef::new(Combatant::Owner).text())
+                    .col(ColumnDef::new(Combatant::Initiative).integer().not_null())
+                    .col(ColumnDef::new(Combatant::Hp).integer().not_null())
+                    .col(ColumnDef::new(Combatant::MaxHp).integer().not_null())
+                    .col(ColumnDef::new(Combatant::Defense).integer().not_null())
+                    .col(
+                        ColumnDef::new(Combatant::Conditions)
+                            .text()
+                            .not_null()
+                            .default(""),
+                    )
+                    // set once a combatant attacks and cleared when their next
+                    // turn starts
+                    .col(
+                        ColumnDef::new(Combatant::Acted)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_combatant_encounter")
+                            .from(Combatant::Table, Combatant::Encounter)
+                            .to(Encounter::Table, Encounter::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_combatant_character")
+                            .from(Combatant::Table, Combatant::Character)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Combatant::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Encounter::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Encounter {
+    Table,
+    Id,
+    Channel,
+    Message,
+    Gm,
+    Round,
+    Current,
+    CreatedAt,
+    EndedAt,
+}
+
+#[derive(DeriveIden)]
+enum Combatant {
+    Table,
+    Id,
+    Encounter,
+    Name,
+    Character,
+    Owner,
+    Initiative,
+    Hp,
+    MaxHp,
+    Defense,
+    Conditions,
+    Acted,
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    Id,
+}
@@ -0,0 +1,143 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Planet::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(Planet::Name).text().not_null().primary_key())
+                    .col(ColumnDef::new(Planet::Description).text())
+                    .col(ColumnDef::new(Planet::Role).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        // routes go both ways, so there's only ever one between two planets
+        manager
+            .create_table(
+                Table::create()
+                    .table(Route::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Route::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Route::From).text().not_null())
+                    .col(ColumnDef::new(Route::To).text().not_null())
+                    .col(ColumnDef::new(Route::Duration).big_integer().not_null())
+                    .col(ColumnDef::new(Route::Cost).big_integer().not_null())
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_route_from")
+                            .from(Route::Table, Route::From)
+                            .to(Planet::Table, Planet::Name)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_route_to")
+                            .from(Route::Table, Route::To)
+                            .to(Planet::Table, Planet::Name)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_route_from_to")
+                    .table(Route::Table)
+                    .col(Route::From)
+                    .col(Route::To)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // while arrives_at is in the future the character is on their way to
+        // location
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .add_column(ColumnDef::new(Character::Location).text())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .add_column(ColumnDef::new(Character::ArrivesAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .drop_column(Character::ArrivesAt)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Character::Table)
+                    .drop_column(Character::Location)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Route::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Planet::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Planet {
+    Table,
+    Name,
+    Description,
+    Role,
+}
+
+#[derive(DeriveIden)]
+enum Route {
+    Table,
+    Id,
+    From,
+    To,
+    Duration,
+    Cost,
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    Location,
+    ArrivesAt,
+}
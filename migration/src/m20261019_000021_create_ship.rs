@@ -0,0 +1,239 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShipClass::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ShipClass::Name)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ShipClass::Price).big_integer().not_null())
+                    .col(
+                        ColumnDef::new(ShipClass::CargoCapacity)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(ShipClass::CrewCapacity).integer().not_null())
+                    .col(ColumnDef::new(ShipClass::Hull).integer().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        // ships copy their class's stats when bought, so editing or removing a
+        // class doesn't change ships already flying
+        manager
+            .create_table(
+                Table::create()
+                    .table(Ship::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Ship::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Ship::Name).text().not_null().unique_key())
+                    .col(ColumnDef::new(Ship::Class).text().not_null())
+                    .col(ColumnDef::new(Ship::OwnerCharacter).integer())
+                    .col(ColumnDef::new(Ship::OwnerFaction).text())
+                    .col(ColumnDef::new(Ship::CargoCapacity).integer().not_null())
+                    .col(ColumnDef::new(Ship::CrewCapacity).integer().not_null())
+                    .col(ColumnDef::new(Ship::Hull).integer().not_null())
+                    .col(ColumnDef::new(Ship::MaxHull).integer().not_null())
+                    .col(ColumnDef::new(Ship::Location).text())
+                    .col(ColumnDef::new(Ship::ArrivesAt).timestamp_with_time_zone())
+                    .col(
+                        ColumnDef::new(Ship::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    // ships outlive their owners, the bot hands them on before
+                    // an owner is deleted and anything it misses is left
+                    // ownerless for staff to sort
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_ship_owner_character")
+                            .from(Ship::Table, Ship::OwnerCharacter)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .to_owned(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_ship_owner_faction")
+                            .from(Ship::Table, Ship::OwnerFaction)
+                            .to(Faction::Table, Faction::Name)
+                            .on_delete(ForeignKeyAction::SetNull)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // crew who haven't joined yet have only been invited
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShipCrew::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ShipCrew::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ShipCrew::Ship).integer().not_null())
+                    .col(ColumnDef::new(ShipCrew::Character).integer().not_null())
+                    .col(ColumnDef::new(ShipCrew::JoinedAt).timestamp_with_time_zone())
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_ship_crew_ship")
+                            .from(ShipCrew::Table, ShipCrew::Ship)
+                            .to(Ship::Table, Ship::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_ship_crew_character")
+                            .from(ShipCrew::Table, ShipCrew::Character)
+                            .to(Character::Table, Character::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_ship_crew_ship_character")
+                    .table(ShipCrew::Table)
+                    .col(ShipCrew::Ship)
+                    .col(ShipCrew::Character)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(ShipCargo::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ShipCargo::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ShipCargo::Ship).integer().not_null())
+                    .col(ColumnDef::new(ShipCargo::Name).text().not_null())
+                    .col(
+                        ColumnDef::new(ShipCargo::Quantity)
+                            .integer()
+                            .not_null()
+                            .default(1),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_ship_cargo_ship")
+                            .from(ShipCargo::Table, ShipCargo::Ship)
+                            .to(Ship::Table, Ship::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ShipCargo::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ShipCrew::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Ship::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(ShipClass::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ShipClass {
+    Table,
+    Name,
+    Price,
+    CargoCapacity,
+    CrewCapacity,
+    Hull,
+}
+
+#[derive(DeriveIden)]
+enum Ship {
+    Table,
+    Id,
+    Name,
+    Class,
+    OwnerCharacter,
+    OwnerFaction,
+    CargoCapacity,
+    CrewCapacity,
+    Hull,
+    MaxHull,
+    Location,
+    ArrivesAt,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ShipCrew {
+    Table,
+    Id,
+    Ship,
+    Character,
+    JoinedAt,
+}
+
+#[derive(DeriveIden)]
+enum ShipCargo {
+    Table,
+    Id,
+    Ship,
+    Name,
+    Quantity,
+}
+
+#[derive(DeriveIden)]
+enum Character {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Faction {
+    Table,
+    Name,
+}
@@ -0,0 +1,139 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // there's only one calendar, kept in the row with id 1. at epoch it
+        // was day `day` of month `month` in year `year`. dates point at their
+        // month, so changing the months doesn't move dates already recorded
+        manager
+            .create_table(
+                Table::create()
+                    .table(Calendar::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Calendar::Id)
+                            .integer()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(Calendar::Epoch)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Calendar::Year).integer().not_null())
+                    .col(ColumnDef::new(Calendar::Month).integer().not_null())
+                    .col(ColumnDef::new(Calendar::Day).big_integer().not_null())
+                    .col(ColumnDef::new(Calendar::Ratio).double().not_null())
+                    .col(ColumnDef::new(Calendar::Era).text())
+                    .col(ColumnDef::new(Calendar::Channel).text())
+                    .col(ColumnDef::new(Calendar::Announced).big_integer())
+                    .to_owned(),
+            )
+            .await?;
+
+        // months come in the order they were added
+        manager
+            .create_table(
+                Table::create()
+                    .table(CalendarMonth::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CalendarMonth::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CalendarMonth::Name)
+                            .text()
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(ColumnDef::new(CalendarMonth::Days).integer().not_null())
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TimelineEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TimelineEvent::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TimelineEvent::Title).text().not_null())
+                    .col(ColumnDef::new(TimelineEvent::Description).text())
+                    .col(ColumnDef::new(TimelineEvent::Year).integer().not_null())
+                    .col(ColumnDef::new(TimelineEvent::Month).integer().not_null())
+                    .col(ColumnDef::new(TimelineEvent::Day).big_integer().not_null())
+                    .col(ColumnDef::new(TimelineEvent::Author).text().not_null())
+                    .col(
+                        ColumnDef::new(TimelineEvent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TimelineEvent::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(CalendarMonth::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Calendar::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Calendar {
+    Table,
+    Id,
+    Epoch,
+    Year,
+    Month,
+    Day,
+    Ratio,
+    Era,
+    Channel,
+    Announced,
+}
+
+#[derive(DeriveIden)]
+enum CalendarMonth {
+    Table,
+    Id,
+    Name,
+    Days,
+}
+
+#[derive(DeriveIden)]
+enum TimelineEvent {
+    Table,
+    Id,
+    Title,
+    Description,
+    Year,
+    Month,
+    Day,
+    Author,
+    CreatedAt,
+}
@@ -0,0 +1,131 @@
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // remind_at is cleared once reminders are sent, ended_at is set once
+        // rewards are handed out
+        manager
+            .create_table(
+                Table::create()
+                    .table(Event::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Event::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Event::Title).text().not_null())
+                    .col(ColumnDef::new(Event::Description).text())
+                    .col(ColumnDef::new(Event::Channel).text().not_null())
+                    .col(ColumnDef::new(Event::Message).text())
+                    .col(ColumnDef::new(Event::Host).text().not_null())
+                    .col(
+                        ColumnDef::new(Event::StartsAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(Event::RemindAt).timestamp_with_time_zone())
+                    .col(ColumnDef::new(Event::Capacity).integer())
+                    .col(ColumnDef::new(Event::Xp).integer().not_null().default(0))
+                    .col(
+                        ColumnDef::new(Event::Credits)
+                            .big_integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(Event::EndedAt).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await?;
+
+        // attended stays null until the event ends or staff record it
+        manager
+            .create_table(
+                Table::create()
+                    .table(EventRsvp::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(EventRsvp::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(EventRsvp::Event).integer().not_null())
+                    .col(ColumnDef::new(EventRsvp::Member).text().not_null())
+                    .col(ColumnDef::new(EventRsvp::Status).text().not_null())
+                    .col(ColumnDef::new(EventRsvp::Attended).boolean())
+                    .col(
+                        ColumnDef::new(EventRsvp::RespondedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        &mut ForeignKey::create()
+                            .name("fk_event_rsvp_event")
+                            .from(EventRsvp::Table, EventRsvp::Event)
+                            .to(Event::Table, Event::Id)
+                            .on_delete(ForeignKeyAction::Cascade)
+                            .to_owned(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_event_rsvp_event_member")
+                    .table(EventRsvp::Table)
+                    .col(EventRsvp::Event)
+                    .col(EventRsvp::Member)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(EventRsvp::Table).to_owned())
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table(Event::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum Event {
+    Table,
+    Id,
+    Title,
+    Description,
+    Channel,
+    Message,
+    Host,
+    StartsAt,
+    RemindAt,
+    Capacity,
+    Xp,
+    Credits,
+    EndedAt,
+}
+
+#[derive(DeriveIden)]
+enum EventRsvp {
+    Table,
+    Id,
+    Event,
+    Member,
+    Status,
+    Attended,
+    RespondedAt,
+}
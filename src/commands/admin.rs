@@ -3,7 +3,8 @@ use sea_orm::{prelude::*, ActiveValue, IntoActiveModel, QueryOrder};
 
 use crate::{
     utils::{
-        db::get_member,
+        audit::Audit,
+        db::{find_member, get_member},
         embed::{not_admin, not_owner, Embed},
         perms::{admin, is_owner},
     },
@@ -32,6 +33,12 @@ pub async fn add_admin(ctx: Ctx<'_>, user: User) -> Result<(), crate::Error> {
     }
     mem.permissions = ActiveValue::Set(1);
     mem.save(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before("not admin")
+        .after("admin")
+        .log()
+        .await?;
     Embed::success(&ctx)
         .description(format!("Added {} as an admin.", user))
         .send(&ctx)
@@ -59,6 +66,12 @@ pub async fn remove_admin(ctx: Ctx<'_>, user: User) -> Result<(), crate::Error>
     }
     mem.permissions = ActiveValue::Set(0);
     mem.save(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before("admin")
+        .after("not admin")
+        .log()
+        .await?;
     Embed::success(&ctx)
         .description(format!("Removed {} as an admin.", user))
         .send(&ctx)
@@ -75,9 +88,9 @@ pub async fn check_admin(ctx: Ctx<'_>, user: User) -> Result<(), crate::Error> {
         return not_admin(&ctx).await;
     }
     let user_id = user.id.to_string();
-    let mem = get_member(&ctx.data().db, &user_id).await?;
+    let mem = find_member(&ctx.data().db, &user_id).await?;
     Embed::info(&ctx)
-        .description(if mem.permissions == 1 {
+        .description(if mem.is_some_and(|mem| mem.permissions == 1) {
             format!("{} is an admin.", user)
         } else {
             format!("{} is not an admin.", user)
@@ -0,0 +1,109 @@
+use chrono::{NaiveDate, NaiveTime};
+use poise::serenity_prelude::{Mention, User, UserId};
+use sea_orm::{prelude::*, Condition, QueryOrder, QuerySelect};
+
+use crate::{
+    utils::{
+        embed::{not_admin, Embed},
+        num::Ths,
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// Midnight at the start of a date, or of the day after it for the end of a
+/// range, so the last day asked for is included.
+fn parse_date(date: &str, end: bool) -> Option<DateTimeUtc> {
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    let date = if end { date.succ_opt()? } else { date };
+    Some(date.and_time(NaiveTime::MIN).and_utc())
+}
+
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn audit(
+    ctx: Ctx<'_>,
+    user: Option<User>,
+    command: Option<String>,
+    #[description = "First day to include, as YYYY-MM-DD"] from: Option<String>,
+    #[description = "Last day to include, as YYYY-MM-DD"] to: Option<String>,
+    #[min = 1] page: Option<u64>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let mut condition = Condition::all();
+    if let Some(ref user) = user {
+        condition = condition.add(
+            Condition::any()
+                .add(entity::audit_log::Column::Actor.eq(user.id.to_string()))
+                .add(entity::audit_log::Column::Target.eq(Mention::from(user.id).to_string())),
+        );
+    }
+    if let Some(command) = command {
+        condition = condition.add(
+            entity::audit_log::Column::Command.eq(command.trim_start_matches('/').to_string()),
+        );
+    }
+    for (date, after) in [(from, true), (to, false)] {
+        if let Some(date) = date {
+            let Some(date) = parse_date(&date, !after) else {
+                return Embed::error(&ctx)
+                    .description(format!("`{}` is not a valid date, use YYYY-MM-DD.", date))
+                    .send(&ctx)
+                    .await;
+            };
+            condition = condition.add(if after {
+                entity::audit_log::Column::CreatedAt.gte(date)
+            } else {
+                entity::audit_log::Column::CreatedAt.lt(date)
+            });
+        }
+    }
+    let total = entity::audit_log::Entity::find()
+        .filter(condition.clone())
+        .count(&ctx.data().db)
+        .await?;
+    let entries = entity::audit_log::Entity::find()
+        .filter(condition)
+        .order_by_desc(entity::audit_log::Column::Id)
+        .limit(10)
+        .offset((page.unwrap_or(1) - 1) * 10)
+        .all(&ctx.data().db)
+        .await?;
+    if entries.is_empty() {
+        return Embed::error(&ctx)
+            .description("No audit log entries found on this page.")
+            .send(&ctx)
+            .await;
+    }
+    let log = entries
+        .iter()
+        .map(|entry| {
+            let mut s = format!(
+                "**#{}** <t:{}:f> {} `{}`",
+                entry.id,
+                entry.created_at.timestamp(),
+                Mention::from(entry.actor.parse::<UserId>().unwrap()),
+                entry.arguments,
+            );
+            match (&entry.before, &entry.after) {
+                (Some(before), Some(after)) => s.push_str(&format!("\n{} → {}", before, after)),
+                (Some(before), None) => s.push_str(&format!("\nRemoved: {}", before)),
+                (None, Some(after)) => s.push_str(&format!("\nAdded: {}", after)),
+                (None, None) => {},
+            }
+            s
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Embed::info(&ctx)
+        .title(format!("Audit Log ({} entries)", total.ths()))
+        .description(log)
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![audit()]
+}
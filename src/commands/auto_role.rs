@@ -1,15 +1,187 @@
-use poise::serenity_prelude::{Mention, Role, RoleId};
-use sea_orm::{prelude::*, Set};
+use poise::serenity_prelude::{
+    ButtonStyle,
+    ComponentInteraction,
+    Context,
+    CreateActionRow,
+    CreateButton,
+    CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+    CreateMessage,
+    Mention,
+    Role,
+    RoleId,
+    User,
+    UserId,
+};
+use sea_orm::{prelude::*, IntoActiveModel, Set};
 
 use crate::{
+    commands::{
+        auto_role_group::describe_rule,
+        character::APPROVED_CHARACTER_ROLE,
+        faction::is_rank_role,
+        role_panel::refresh_panels,
+    },
     utils::{
-        embed::{not_admin, Embed},
-        perms::admin,
+        audit::Audit,
+        db::{find_member, get_member},
+        embed::{not_admin, Embed, EmbedStyle},
+        perms::{admin, is_admin},
     },
     Ctx,
     Data,
 };
 
+/// Custom id prefix of the approve and deny buttons on role requests.
+pub const REQUEST_PREFIX: &str = "role-request";
+
+/// Describes the requirements of an auto role, e.g. `level 5, not @Role`.
+fn describe_requirements(
+    auto_role: &entity::auto_role::Model,
+    prerequisites: &[entity::auto_role_prerequisite::Model],
+) -> Vec<String> {
+    let mut requirements = vec![];
+    if let Some(level) = auto_role.min_level {
+        requirements.push(format!("level {}", level));
+    }
+    if auto_role.requires_character {
+        requirements.push("an approved character".to_string());
+    }
+    for prerequisite in prerequisites {
+        let role = Mention::from(prerequisite.role.parse::<RoleId>().unwrap());
+        if prerequisite.held {
+            requirements.push(role.to_string());
+        } else {
+            requirements.push(format!("not {}", role));
+        }
+    }
+    if auto_role.approval {
+        requirements.push("staff approval".to_string());
+    }
+    requirements
+}
+
+/// The requirements of an auto role that a user doesn't meet, excluding staff
+/// approval, which is handled separately.
+async fn missing_requirements(
+    ctx: &Context,
+    data: &Data,
+    user: UserId,
+    auto_role: &entity::auto_role::Model,
+) -> Result<Vec<String>, crate::Error> {
+    let roles = data.primary_guild_id.member(ctx, user).await?.roles;
+    let mut missing = vec![];
+    if let Some(level) = auto_role.min_level {
+        let current = find_member(&data.db, user)
+            .await?
+            .map_or(0, |member| member.level);
+        if current < level {
+            missing.push(format!(
+                "Reach level {} (you are level {}).",
+                level, current
+            ));
+        }
+    }
+    if auto_role.requires_character && !roles.contains(&APPROVED_CHARACTER_ROLE) {
+        missing.push("Have an approved character.".to_string());
+    }
+    let prerequisites = entity::auto_role_prerequisite::Entity::find()
+        .filter(entity::auto_role_prerequisite::Column::AutoRole.eq(&auto_role.id))
+        .all(&data.db)
+        .await?;
+    for prerequisite in prerequisites {
+        let role = prerequisite.role.parse::<RoleId>().unwrap();
+        if roles.contains(&role) != prerequisite.held {
+            missing.push(if prerequisite.held {
+                format!("Have the {} role.", Mention::from(role))
+            } else {
+                format!("Not have the {} role.", Mention::from(role))
+            });
+        }
+    }
+    Ok(missing)
+}
+
+/// The group of an auto role along with the other roles from it that a user
+/// holds.
+async fn held_group_roles(
+    ctx: &Context,
+    data: &Data,
+    user: UserId,
+    auto_role: &entity::auto_role::Model,
+) -> Result<Option<(entity::auto_role_group::Model, Vec<RoleId>)>, crate::Error> {
+    let Some(group) = auto_role
+        .find_related(entity::auto_role_group::Entity)
+        .one(&data.db)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let roles = data.primary_guild_id.member(ctx, user).await?.roles;
+    let held = entity::auto_role::Entity::find()
+        .filter(entity::auto_role::Column::Group.eq(&group.name))
+        .filter(entity::auto_role::Column::Id.ne(&auto_role.id))
+        .all(&data.db)
+        .await?
+        .into_iter()
+        .map(|r| r.id.parse::<RoleId>().unwrap())
+        .filter(|r| roles.contains(r))
+        .collect();
+    Ok(Some((group, held)))
+}
+
+/// The group of an auto role and its maximum, if the user already holds as
+/// many of its roles as allowed. The `replacing` roles don't count, and single
+/// choice groups are never full since taking a role swaps the old one out.
+async fn full_group(
+    ctx: &Context,
+    data: &Data,
+    user: UserId,
+    auto_role: &entity::auto_role::Model,
+    replacing: &[RoleId],
+) -> Result<Option<(entity::auto_role_group::Model, i32)>, crate::Error> {
+    let Some((group, held)) = held_group_roles(ctx, data, user, auto_role).await? else {
+        return Ok(None);
+    };
+    Ok(group
+        .max_roles
+        .filter(|max| *max > 1)
+        .filter(|max| held.iter().filter(|r| !replacing.contains(r)).count() >= *max as usize)
+        .map(|max| (group, max)))
+}
+
+/// Gives a user an auto role, removing the other roles in its group if it's
+/// single choice.
+async fn grant_auto_role(
+    ctx: &Context,
+    data: &Data,
+    user: UserId,
+    auto_role: &entity::auto_role::Model,
+) -> Result<(), crate::Error> {
+    let http = &ctx.http;
+    if let Some((group, held)) = held_group_roles(ctx, data, user, auto_role).await? {
+        if group.max_roles == Some(1) {
+            for role in held {
+                http.remove_member_role(
+                    data.primary_guild_id,
+                    user,
+                    role,
+                    Some("Role removed due to auto role group change."),
+                )
+                .await?;
+            }
+        }
+    }
+    http.add_member_role(
+        data.primary_guild_id,
+        user,
+        auto_role.id.parse().unwrap(),
+        Some("Auto role."),
+    )
+    .await?;
+    Ok(())
+}
+
 #[poise::command(
     slash_command,
     rename = "add-auto-role",
@@ -31,6 +203,11 @@ pub async fn add_auto_role(
             .description(format!("{} is already an auto role.", role))
             .send(&ctx)
             .await
+    } else if is_rank_role(&ctx.data().db, role.id).await? {
+        Embed::error(&ctx)
+            .description(format!("{} is a faction rank role.", role))
+            .send(&ctx)
+            .await
     } else {
         if let Some(ref group) = group {
             let auto_role_group = entity::auto_role_group::Entity::find_by_id(group.clone())
@@ -44,12 +221,24 @@ pub async fn add_auto_role(
             }
         }
         let auto_role = entity::auto_role::ActiveModel {
-            id:    Set(role.id.to_string()),
+            id: Set(role.id.to_string()),
             group: Set(group.clone()),
+            ..Default::default()
         };
         entity::auto_role::Entity::insert(auto_role)
             .exec(&ctx.data().db)
             .await?;
+        if let Some(ref group) = group {
+            refresh_panels(ctx.serenity_context(), ctx.data(), group).await?;
+        }
+        Audit::new(&ctx)
+            .target(role.id)
+            .after(match group {
+                Some(ref group) => format!("Auto role in group {}", group),
+                None => "Auto role".to_string(),
+            })
+            .log()
+            .await?;
         if let Some(group) = group {
             Embed::success(&ctx)
                 .description(format!(
@@ -80,7 +269,20 @@ pub async fn remove_auto_role(ctx: Ctx<'_>, role: Role) -> Result<(), crate::Err
         .one(&ctx.data().db)
         .await?;
     if let Some(auto_role) = auto_role {
+        let before = match auto_role.group {
+            Some(ref group) => format!("Auto role in group {}", group),
+            None => "Auto role".to_string(),
+        };
+        let group = auto_role.group.clone();
         auto_role.delete(&ctx.data().db).await?;
+        if let Some(ref group) = group {
+            refresh_panels(ctx.serenity_context(), ctx.data(), group).await?;
+        }
+        Audit::new(&ctx)
+            .target(role.id)
+            .before(before)
+            .log()
+            .await?;
         Embed::success(&ctx)
             .description(format!("Removed {} as an auto role.", role))
             .send(&ctx)
@@ -96,6 +298,7 @@ pub async fn remove_auto_role(ctx: Ctx<'_>, role: Role) -> Result<(), crate::Err
 #[poise::command(slash_command, rename = "roles")]
 pub async fn auto_roles(ctx: Ctx<'_>) -> Result<(), crate::Error> {
     let auto_roles = entity::auto_role::Entity::find()
+        .find_with_related(entity::auto_role_prerequisite::Entity)
         .all(&ctx.data().db)
         .await?;
     if auto_roles.is_empty() {
@@ -104,16 +307,33 @@ pub async fn auto_roles(ctx: Ctx<'_>) -> Result<(), crate::Error> {
             .send(&ctx)
             .await;
     }
+    let rules = entity::auto_role_group::Entity::find()
+        .all(&ctx.data().db)
+        .await?
+        .into_iter()
+        .map(|group| (group.name.clone(), describe_rule(&group)))
+        .collect::<std::collections::HashMap<_, _>>();
     let mut groups = std::collections::HashMap::new();
-    for auto_role in auto_roles {
-        let group = auto_role
-            .group
-            .clone()
-            .unwrap_or_else(|| "None".to_string());
+    for (auto_role, prerequisites) in auto_roles {
+        let group = match auto_role.group {
+            Some(ref group) => {
+                match rules.get(group) {
+                    Some(rule) => format!("{} ({})", group, rule),
+                    None => group.clone(),
+                }
+            },
+            None => "None".to_string(),
+        };
+        let mention = Mention::from(auto_role.id.parse::<RoleId>().unwrap());
+        let requirements = describe_requirements(&auto_role, &prerequisites);
         groups
             .entry(group)
             .or_insert_with(Vec::new)
-            .push(Mention::from(auto_role.id.parse::<RoleId>().unwrap()));
+            .push(if requirements.is_empty() {
+                mention.to_string()
+            } else {
+                format!("{} (requires {})", mention, requirements.join(", "))
+            });
     }
     Embed::info(&ctx)
         .description(groups.iter().fold(String::new(), |mut s, (group, roles)| {
@@ -133,42 +353,463 @@ pub async fn auto_role(ctx: Ctx<'_>, role: Role) -> Result<(), crate::Error> {
     let auto_role = entity::auto_role::Entity::find_by_id(role.id.to_string())
         .one(&ctx.data().db)
         .await?;
-    if let Some(auto_role) = auto_role {
-        ctx.defer_ephemeral().await?;
-        if let Some(group) = auto_role.group {
-            let group_roles = entity::auto_role::Entity::find()
-                .filter(entity::auto_role::Column::Group.eq(group))
-                .all(&ctx.data().db)
-                .await?;
-            for role in group_roles {
-                ctx.http()
-                    .remove_member_role(
-                        ctx.data().primary_guild_id,
-                        ctx.author().id,
-                        role.id.parse().unwrap(),
-                        Some("Role removed due to auto role group change."),
-                    )
-                    .await?;
-            }
+    let Some(auto_role) = auto_role else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not an auto role.", role))
+            .send(&ctx)
+            .await;
+    };
+    ctx.defer_ephemeral().await?;
+    let (Ok(embed) | Err(embed)) =
+        take_auto_role(ctx.serenity_context(), ctx.data(), ctx.author(), &auto_role).await?;
+    embed.send(&ctx).await
+}
+
+/// Gives a user an auto role if they meet its requirements, or files a
+/// request for staff approval if it needs one, returning the reply to show.
+/// The reply is an error if the role was refused.
+pub async fn take_auto_role(
+    ctx: &Context,
+    data: &Data,
+    user: &User,
+    auto_role: &entity::auto_role::Model,
+) -> Result<Result<Embed, Embed>, crate::Error> {
+    take_auto_role_replacing(ctx, data, user, auto_role, &[]).await
+}
+
+/// Like [`take_auto_role`], but for swapping roles: the `replacing` roles are
+/// about to be dropped, so they don't count towards the group's maximum.
+pub async fn take_auto_role_replacing(
+    ctx: &Context,
+    data: &Data,
+    user: &User,
+    auto_role: &entity::auto_role::Model,
+    replacing: &[RoleId],
+) -> Result<Result<Embed, Embed>, crate::Error> {
+    let role = Mention::from(auto_role.id.parse::<RoleId>().unwrap());
+    let missing = missing_requirements(ctx, data, user.id, auto_role).await?;
+    if !missing.is_empty() {
+        return Ok(Err(Embed::plain(EmbedStyle::Error)
+            .author(user)
+            .title("Requirements not met")
+            .description(format!(
+                "To get {} you need to:\n{}",
+                role,
+                missing
+                    .iter()
+                    .map(|m| format!("- {}", m))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ))));
+    }
+    if let Some((group, max)) = full_group(ctx, data, user.id, auto_role, replacing).await? {
+        return Ok(Err(Embed::plain(EmbedStyle::Error)
+            .author(user)
+            .description(format!(
+                "You can only hold {} roles from {}, remove one before taking {}.",
+                max, group.name, role
+            ))));
+    }
+    if !auto_role.approval {
+        grant_auto_role(ctx, data, user.id, auto_role).await?;
+        return Ok(Ok(Embed::plain(EmbedStyle::Success)
+            .author(user)
+            .description(format!("Added {}!", role))));
+    }
+    let Some(channel) = data.role_request_channel else {
+        return Ok(Err(Embed::plain(EmbedStyle::Error)
+            .author(user)
+            .description(format!(
+                "{} requires staff approval, but no request channel is configured.",
+                role
+            ))));
+    };
+    let pending = entity::auto_role_request::Entity::find()
+        .filter(entity::auto_role_request::Column::AutoRole.eq(&auto_role.id))
+        .filter(entity::auto_role_request::Column::Member.eq(user.id.to_string()))
+        .one(&data.db)
+        .await?;
+    if pending.is_some() {
+        return Ok(Err(Embed::plain(EmbedStyle::Error)
+            .author(user)
+            .description(format!(
+                "You already have a pending request for {}.",
+                role
+            ))));
+    }
+    let request = entity::auto_role_request::ActiveModel {
+        auto_role: Set(auto_role.id.clone()),
+        member: Set(user.id.to_string()),
+        created_at: Set(chrono::Utc::now()),
+        ..Default::default()
+    }
+    .insert(&data.db)
+    .await?;
+    channel
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(
+                    Embed::plain(EmbedStyle::Info)
+                        .author(user)
+                        .title("Role Request")
+                        .description(format!("{} has requested {}.", user, role))
+                        .build(),
+                )
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new(format!("{}:approve:{}", REQUEST_PREFIX, request.id))
+                        .label("Approve")
+                        .style(ButtonStyle::Success),
+                    CreateButton::new(format!("{}:deny:{}", REQUEST_PREFIX, request.id))
+                        .label("Deny")
+                        .style(ButtonStyle::Danger),
+                ])]),
+        )
+        .await?;
+    Ok(Ok(Embed::plain(EmbedStyle::Success)
+        .author(user)
+        .description(format!(
+            "{} requires staff approval, your request has been sent.",
+            role
+        ))))
+}
+
+/// Takes an auto role away from a user, returning the reply to show. The
+/// reply is an error if the role is the last one held from a required group.
+pub async fn drop_auto_role(
+    ctx: &Context,
+    data: &Data,
+    user: &User,
+    auto_role: &entity::auto_role::Model,
+) -> Result<Result<Embed, Embed>, crate::Error> {
+    let role = auto_role.id.parse::<RoleId>().unwrap();
+    if let Some((group, held)) = held_group_roles(ctx, data, user.id, auto_role).await? {
+        if group.required && held.is_empty() {
+            return Ok(Err(Embed::plain(EmbedStyle::Error)
+                .author(user)
+                .description(format!(
+                    "You must keep at least one role from {}, take another before removing {}.",
+                    group.name,
+                    Mention::from(role)
+                ))));
         }
-        ctx.http()
-            .add_member_role(
-                ctx.data().primary_guild_id,
-                ctx.author().id,
-                role.id,
-                Some("Auto role."),
+    }
+    ctx.http
+        .remove_member_role(
+            data.primary_guild_id,
+            user.id,
+            role,
+            Some("Role removed due to auto role."),
+        )
+        .await?;
+    Ok(Ok(Embed::plain(EmbedStyle::Success)
+        .author(user)
+        .description(format!("Removed {}!", Mention::from(role)))))
+}
+
+/// Handles the approve and deny buttons on a role request, `args` being the
+/// custom id after the prefix.
+pub async fn request_button(
+    ctx: &Context,
+    data: &Data,
+    interaction: &ComponentInteraction,
+    args: &str,
+) -> Result<(), crate::Error> {
+    let Some((action, id)) = args.split_once(':') else {
+        return Ok(());
+    };
+    let reply = |embed: Embed| {
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .embed(embed.build())
+                .ephemeral(true),
+        )
+    };
+    if !is_admin(&get_member(&data.db, interaction.user.id).await?) {
+        return Ok(interaction
+            .create_response(
+                ctx,
+                reply(Embed::plain(EmbedStyle::Error).description("You are not an admin.")),
             )
-            .await?;
-        Embed::success(&ctx)
-            .description(format!("Added {}!", role))
+            .await?);
+    }
+    let request = entity::auto_role_request::Entity::find_by_id(id.parse::<i32>()?)
+        .find_also_related(entity::auto_role::Entity)
+        .one(&data.db)
+        .await?;
+    let Some((request, Some(auto_role))) = request else {
+        return Ok(interaction
+            .create_response(
+                ctx,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(
+                            Embed::plain(EmbedStyle::Warning)
+                                .title("Role Request")
+                                .description("This request has already been handled.")
+                                .build(),
+                        )
+                        .components(vec![]),
+                ),
+            )
+            .await?);
+    };
+    let user = request.member.parse::<UserId>().unwrap();
+    let role = Mention::from(auto_role.id.parse::<RoleId>().unwrap());
+    let approved = action == "approve";
+    if approved {
+        // things may have changed since the request was made
+        let refused = if data.primary_guild_id.member(ctx, user).await.is_err() {
+            Some(format!(
+                "{} is no longer in the server.",
+                Mention::from(user)
+            ))
+        } else if let Some((group, max)) = full_group(ctx, data, user, &auto_role, &[]).await? {
+            Some(format!(
+                "{} already holds {} roles from {}.",
+                Mention::from(user),
+                max,
+                group.name
+            ))
+        } else {
+            let missing = missing_requirements(ctx, data, user, &auto_role).await?;
+            (!missing.is_empty()).then(|| {
+                format!(
+                    "{} no longer meets the requirements for {}:\n{}",
+                    Mention::from(user),
+                    role,
+                    missing
+                        .iter()
+                        .map(|m| format!("- {}", m))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                )
+            })
+        };
+        if let Some(refused) = refused {
+            return Ok(interaction
+                .create_response(
+                    ctx,
+                    reply(Embed::plain(EmbedStyle::Error).description(refused)),
+                )
+                .await?);
+        }
+        grant_auto_role(ctx, data, user, &auto_role).await?;
+    }
+    request.into_active_model().delete(&data.db).await?;
+    let outcome = if approved { "approved" } else { "denied" };
+    Audit::component(
+        &ctx.http,
+        data,
+        interaction,
+        format!("role request {}", action),
+    )
+    .target(user)
+    .after(format!("{} {}", role, outcome))
+    .log()
+    .await?;
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(
+                        Embed::plain(if approved {
+                            EmbedStyle::Success
+                        } else {
+                            EmbedStyle::Error
+                        })
+                        .author(&interaction.user)
+                        .title("Role Request")
+                        .description(format!(
+                            "{}'s request for {} was {} by {}.",
+                            Mention::from(user),
+                            role,
+                            outcome,
+                            interaction.user
+                        ))
+                        .build(),
+                    )
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+    // the member may have DMs closed, the outcome is already recorded
+    if let Ok(channel) = user.create_dm_channel(ctx).await {
+        let _ = channel
+            .send_message(
+                ctx,
+                CreateMessage::new().content(format!(
+                    "Your request for the {} role was {}.",
+                    role, outcome
+                )),
+            )
+            .await;
+    }
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "set-role-requirements",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn set_role_requirements(
+    ctx: Ctx<'_>,
+    role: Role,
+    #[description = "Minimum XP level, 0 for none"]
+    #[min = 0]
+    min_level: Option<i32>,
+    #[description = "Require an approved character"] approved_character: Option<bool>,
+    #[description = "Require staff approval"] approval: Option<bool>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(auto_role) = entity::auto_role::Entity::find_by_id(role.id.to_string())
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not an auto role.", role))
             .send(&ctx)
-            .await
-    } else {
-        Embed::error(&ctx)
+            .await;
+    };
+    let prerequisites = entity::auto_role_prerequisite::Entity::find()
+        .filter(entity::auto_role_prerequisite::Column::AutoRole.eq(&auto_role.id))
+        .all(&ctx.data().db)
+        .await?;
+    let before = describe_requirements(&auto_role, &prerequisites).join(", ");
+    let mut auto_role = auto_role.into_active_model();
+    if let Some(min_level) = min_level {
+        auto_role.min_level = Set(Some(min_level).filter(|l| *l > 0));
+    }
+    if let Some(approved_character) = approved_character {
+        auto_role.requires_character = Set(approved_character);
+    }
+    if let Some(approval) = approval {
+        auto_role.approval = Set(approval);
+    }
+    let auto_role = auto_role.update(&ctx.data().db).await?;
+    let after = describe_requirements(&auto_role, &prerequisites).join(", ");
+    Audit::new(&ctx)
+        .target(role.id)
+        .before(&before)
+        .after(&after)
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(if after.is_empty() {
+            format!("{} has no requirements.", role)
+        } else {
+            format!("{} now requires {}.", role, after)
+        })
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "add-role-prerequisite",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn add_role_prerequisite(
+    ctx: Ctx<'_>,
+    role: Role,
+    prerequisite: Role,
+    #[description = "Whether the role must be held (default) or must not be held"] held: Option<
+        bool,
+    >,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    if entity::auto_role::Entity::find_by_id(role.id.to_string())
+        .one(&ctx.data().db)
+        .await?
+        .is_none()
+    {
+        return Embed::error(&ctx)
             .description(format!("{} is not an auto role.", role))
             .send(&ctx)
-            .await
+            .await;
     }
+    let existing = entity::auto_role_prerequisite::Entity::find()
+        .filter(entity::auto_role_prerequisite::Column::AutoRole.eq(role.id.to_string()))
+        .filter(entity::auto_role_prerequisite::Column::Role.eq(prerequisite.id.to_string()))
+        .one(&ctx.data().db)
+        .await?;
+    if existing.is_some() {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is already a prerequisite of {}.",
+                prerequisite, role
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let held = held.unwrap_or(true);
+    entity::auto_role_prerequisite::ActiveModel {
+        auto_role: Set(role.id.to_string()),
+        role: Set(prerequisite.id.to_string()),
+        held: Set(held),
+        ..Default::default()
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    let rule = if held {
+        format!("Requires {}", prerequisite)
+    } else {
+        format!("Requires not having {}", prerequisite)
+    };
+    Audit::new(&ctx).target(role.id).after(&rule).log().await?;
+    Embed::success(&ctx)
+        .description(format!("{} on {}.", rule, role))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "remove-role-prerequisite",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn remove_role_prerequisite(
+    ctx: Ctx<'_>,
+    role: Role,
+    prerequisite: Role,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let existing = entity::auto_role_prerequisite::Entity::find()
+        .filter(entity::auto_role_prerequisite::Column::AutoRole.eq(role.id.to_string()))
+        .filter(entity::auto_role_prerequisite::Column::Role.eq(prerequisite.id.to_string()))
+        .one(&ctx.data().db)
+        .await?;
+    let Some(existing) = existing else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is not a prerequisite of {}.",
+                prerequisite, role
+            ))
+            .send(&ctx)
+            .await;
+    };
+    let rule = if existing.held {
+        format!("Requires {}", prerequisite)
+    } else {
+        format!("Requires not having {}", prerequisite)
+    };
+    existing.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).target(role.id).before(&rule).log().await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} is no longer a prerequisite of {}.",
+            prerequisite, role
+        ))
+        .send(&ctx)
+        .await
 }
 
 #[poise::command(slash_command, rename = "remove-role")]
@@ -176,26 +817,16 @@ pub async fn remove_role(ctx: Ctx<'_>, role: Role) -> Result<(), crate::Error> {
     let auto_role = entity::auto_role::Entity::find_by_id(role.id.to_string())
         .one(&ctx.data().db)
         .await?;
-    if auto_role.is_some() {
-        ctx.defer_ephemeral().await?;
-        ctx.http()
-            .remove_member_role(
-                ctx.data().primary_guild_id,
-                ctx.author().id,
-                role.id,
-                Some("Role removed due to auto role."),
-            )
-            .await?;
-        Embed::success(&ctx)
-            .description(format!("Removed {}!", role))
-            .send(&ctx)
-            .await
-    } else {
-        Embed::error(&ctx)
+    let Some(auto_role) = auto_role else {
+        return Embed::error(&ctx)
             .description(format!("{} is not an auto role.", role))
             .send(&ctx)
-            .await
-    }
+            .await;
+    };
+    ctx.defer_ephemeral().await?;
+    let (Ok(embed) | Err(embed)) =
+        drop_auto_role(ctx.serenity_context(), ctx.data(), ctx.author(), &auto_role).await?;
+    embed.send(&ctx).await
 }
 
 pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
@@ -205,5 +836,8 @@ pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
         auto_roles(),
         auto_role(),
         remove_role(),
+        set_role_requirements(),
+        add_role_prerequisite(),
+        remove_role_prerequisite(),
     ]
 }
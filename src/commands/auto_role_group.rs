@@ -1,7 +1,9 @@
-use sea_orm::{prelude::*, EntityTrait, Set};
+use sea_orm::{prelude::*, EntityTrait, IntoActiveModel, Set};
 
 use crate::{
+    commands::role_panel::refresh_panels,
     utils::{
+        audit::Audit,
         embed::{not_admin, Embed},
         perms::admin,
     },
@@ -9,6 +11,30 @@ use crate::{
     Data,
 };
 
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum GroupMode {
+    Unlimited,
+    #[name = "Single choice"]
+    Single,
+    #[name = "Limited"]
+    Limited,
+}
+
+/// Describes how many roles can be taken from a group, e.g. `pick up to 3,
+/// required`.
+pub fn describe_rule(group: &entity::auto_role_group::Model) -> String {
+    let pick = match group.max_roles {
+        None => "pick any".to_string(),
+        Some(1) => "pick one".to_string(),
+        Some(max) => format!("pick up to {}", max),
+    };
+    if group.required {
+        format!("{}, required", pick)
+    } else {
+        pick
+    }
+}
+
 #[poise::command(
     slash_command,
     rename = "add-auto-role-group",
@@ -29,11 +55,17 @@ pub async fn add_auto_role_group(ctx: Ctx<'_>, name: String) -> Result<(), crate
             .await
     } else {
         let auto_role_group = entity::auto_role_group::ActiveModel {
-            name: Set(name.clone()),
+            name:      Set(name.clone()),
+            max_roles: Set(Some(1)),
+            required:  Set(false),
         };
         entity::auto_role_group::Entity::insert(auto_role_group)
             .exec(&ctx.data().db)
             .await?;
+        Audit::new(&ctx)
+            .after(format!("Auto role group {}", name))
+            .log()
+            .await?;
         Embed::success(&ctx)
             .description(format!("Added auto role group {}.", name))
             .send(&ctx)
@@ -65,7 +97,26 @@ pub async fn remove_auto_role_group(ctx: Ctx<'_>, name: String) -> Result<(), cr
                 .send(&ctx)
                 .await;
         }
+        // panels for the group would be left with no roles, so take them down
+        let panels = entity::role_panel::Entity::find()
+            .filter(entity::role_panel::Column::Group.eq(auto_role_group.name.clone()))
+            .all(&ctx.data().db)
+            .await?;
+        for panel in panels {
+            let _ = ctx
+                .http()
+                .delete_message(
+                    panel.channel.parse().unwrap(),
+                    panel.id.parse().unwrap(),
+                    Some("Auto role group removed."),
+                )
+                .await;
+        }
         auto_role_group.delete(&ctx.data().db).await?;
+        Audit::new(&ctx)
+            .before(format!("Auto role group {}", name))
+            .log()
+            .await?;
         Embed::success(&ctx)
             .description(format!("Removed auto role group {}.", name))
             .send(&ctx)
@@ -78,6 +129,71 @@ pub async fn remove_auto_role_group(ctx: Ctx<'_>, name: String) -> Result<(), cr
     }
 }
 
+#[poise::command(
+    slash_command,
+    rename = "set-auto-role-group-mode",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn set_auto_role_group_mode(
+    ctx: Ctx<'_>,
+    name: String,
+    mode: GroupMode,
+    #[description = "Most roles a member can hold in limited mode"]
+    #[min = 2]
+    limit: Option<i32>,
+    #[description = "Whether members can't remove their last role from the group"] required: Option<
+        bool,
+    >,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(auto_role_group) = entity::auto_role_group::Entity::find_by_id(name.clone())
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("Auto role group {} doesn't exist.", name))
+            .send(&ctx)
+            .await;
+    };
+    let max_roles = match mode {
+        GroupMode::Unlimited => None,
+        GroupMode::Single => Some(1),
+        GroupMode::Limited => {
+            let Some(limit) = limit else {
+                return Embed::error(&ctx)
+                    .description("Limited mode needs a limit.")
+                    .send(&ctx)
+                    .await;
+            };
+            Some(limit)
+        },
+    };
+    let before = describe_rule(&auto_role_group);
+    let mut auto_role_group = auto_role_group.into_active_model();
+    auto_role_group.max_roles = Set(max_roles);
+    if let Some(required) = required {
+        auto_role_group.required = Set(required);
+    }
+    let auto_role_group = auto_role_group.update(&ctx.data().db).await?;
+    let after = describe_rule(&auto_role_group);
+    refresh_panels(ctx.serenity_context(), ctx.data(), &name).await?;
+    Audit::new(&ctx)
+        .before(format!("{}: {}", name, before))
+        .after(format!("{}: {}", name, after))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Auto role group {} is now {}.", name, after))
+        .send(&ctx)
+        .await
+}
+
 pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
-    vec![add_auto_role_group(), remove_auto_role_group()]
+    vec![
+        add_auto_role_group(),
+        remove_auto_role_group(),
+        set_auto_role_group_mode(),
+    ]
 }
@@ -0,0 +1,473 @@
+use std::{collections::HashMap, sync::Mutex};
+
+use entity::{
+    automod_rule::{AutomodAction, AutomodKind},
+    mod_case::ModAction,
+};
+use once_cell::sync::Lazy;
+use poise::serenity_prelude::{ChannelId, Context, CreateMessage, GuildChannel, Mention, Message};
+use regex::Regex;
+use sea_orm::{prelude::*, QueryOrder, Set};
+
+use crate::{
+    commands::moderation::{sanction, Sanction},
+    utils::{
+        audit::Audit,
+        automod::{caps_percent, contains_invite, emoji_count, word_regex},
+        duration::{format_duration, parse_duration},
+        embed::{not_admin, Embed, EmbedStyle},
+        perms::{admin, is_admin, is_owner},
+    },
+    Ctx,
+    Data,
+};
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum RuleKind {
+    #[name = "Banned word"]
+    Word,
+    #[name = "Regex"]
+    Regex,
+    #[name = "Invite links"]
+    Invite,
+    #[name = "Mass mentions"]
+    MassMention,
+    #[name = "Excessive caps"]
+    Caps,
+    #[name = "Excessive emoji"]
+    Emoji,
+}
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum RuleAction {
+    Delete,
+    #[name = "Delete and warn"]
+    Warn,
+    #[name = "Delete and time out"]
+    Timeout,
+}
+
+fn default_threshold(kind: AutomodKind) -> Option<i32> {
+    match kind {
+        AutomodKind::MassMention => Some(5),
+        AutomodKind::Caps => Some(70),
+        AutomodKind::Emoji => Some(10),
+        _ => None,
+    }
+}
+
+/// Describes what a rule catches, e.g. `5 or more mentions`.
+fn describe_condition(rule: &entity::automod_rule::Model) -> String {
+    let pattern = rule.pattern.as_deref().unwrap_or_default();
+    let threshold = rule.threshold.unwrap_or_default();
+    match rule.kind {
+        AutomodKind::Word => format!("banned word `{}`", pattern),
+        AutomodKind::Regex => format!("pattern `{}`", pattern),
+        AutomodKind::Invite => "invite links".to_string(),
+        AutomodKind::MassMention => format!("{} or more mentions", threshold),
+        AutomodKind::Caps => format!("{}% or more caps", threshold),
+        AutomodKind::Emoji => format!("{} or more emoji", threshold),
+    }
+}
+
+fn describe(rule: &entity::automod_rule::Model) -> String {
+    let action = match rule.action {
+        AutomodAction::Delete => "delete".to_string(),
+        AutomodAction::Warn => "delete and warn".to_string(),
+        AutomodAction::Timeout => {
+            format!(
+                "delete and time out for {}",
+                format_duration(chrono::Duration::seconds(rule.duration.unwrap_or_default()))
+            )
+        },
+    };
+    format!("#{} {}: {}", rule.id, describe_condition(rule), action)
+}
+
+/// Compiled patterns of word and regex rules by rule id, so they aren't
+/// compiled again for every message. Entries are replaced as rules are added
+/// and dropped as they're removed.
+static PATTERNS: Lazy<Mutex<HashMap<i32, Option<Regex>>>> = Lazy::new(Default::default);
+
+fn compile(rule: &entity::automod_rule::Model) -> Option<Regex> {
+    let pattern = rule.pattern.as_deref()?;
+    match rule.kind {
+        AutomodKind::Word => word_regex(pattern),
+        AutomodKind::Regex => Regex::new(pattern).ok(),
+        _ => None,
+    }
+}
+
+/// The rule's compiled pattern, compiling it on first use.
+fn pattern(rule: &entity::automod_rule::Model) -> Option<Regex> {
+    PATTERNS
+        .lock()
+        .unwrap()
+        .entry(rule.id)
+        .or_insert_with(|| compile(rule))
+        .clone()
+}
+
+/// Whether a message breaks a rule.
+fn violates(rule: &entity::automod_rule::Model, msg: &Message) -> bool {
+    let content = msg.content.as_str();
+    let threshold = rule.threshold.unwrap_or_default().max(1) as usize;
+    match rule.kind {
+        AutomodKind::Word | AutomodKind::Regex => {
+            pattern(rule).is_some_and(|regex| regex.is_match(content))
+        },
+        AutomodKind::Invite => contains_invite(content),
+        AutomodKind::MassMention => {
+            msg.mentions.len() + msg.mention_roles.len() + msg.mention_everyone as usize
+                >= threshold
+        },
+        AutomodKind::Caps => caps_percent(content).is_some_and(|p| p as usize >= threshold),
+        AutomodKind::Emoji => emoji_count(content) >= threshold,
+    }
+}
+
+/// Runs a message through the automod rules, returning whether it was
+/// flagged. Flagged messages are deleted and the rule's action is taken
+/// against the author. Admins are never flagged, and a channel can be exempt
+/// from a rule or from all of them, which also covers its threads.
+pub async fn automod(ctx: &Context, data: &Data, msg: &Message) -> Result<bool, crate::Error> {
+    if msg.author.bot || msg.guild_id != Some(data.primary_guild_id) {
+        return Ok(false);
+    }
+    let rules = entity::automod_rule::Entity::find()
+        .order_by_asc(entity::automod_rule::Column::Id)
+        .all(&data.db)
+        .await?;
+    if rules.is_empty() || is_owner(&msg.author.id.to_string()) {
+        return Ok(false);
+    }
+    if entity::member::Entity::find_by_id(msg.author.id.to_string())
+        .one(&data.db)
+        .await?
+        .is_some_and(|m| is_admin(&m))
+    {
+        return Ok(false);
+    }
+    let mut channels = vec![msg.channel_id.to_string()];
+    if let Some(parent) = msg.channel(ctx).await?.guild().and_then(|c| c.parent_id) {
+        channels.push(parent.to_string());
+    }
+    let exemptions = entity::automod_exemption::Entity::find()
+        .filter(entity::automod_exemption::Column::Channel.is_in(channels))
+        .all(&data.db)
+        .await?;
+    if exemptions.iter().any(|e| e.rule.is_none()) {
+        return Ok(false);
+    }
+    let Some(rule) = rules
+        .iter()
+        .find(|r| !exemptions.iter().any(|e| e.rule == Some(r.id)) && violates(r, msg))
+    else {
+        return Ok(false);
+    };
+    // the message may already be gone, it's flagged either way
+    let _ = msg.delete(ctx).await;
+    let reason = format!("Automod: {}", describe_condition(rule));
+    // deletions and refused sanctions go to the mod log, cases post themselves
+    let report = match rule.action {
+        AutomodAction::Delete => {
+            Some(
+                Embed::plain(EmbedStyle::Warning)
+                    .author(&msg.author)
+                    .title("Automod")
+                    .description(format!(
+                        "Deleted a message from {} in {}.",
+                        msg.author, msg.channel_id
+                    ))
+                    .field("Rule", describe(rule), false)
+                    .field(
+                        "Message",
+                        msg.content.chars().take(1000).collect::<String>(),
+                        false,
+                    ),
+            )
+        },
+        AutomodAction::Warn | AutomodAction::Timeout => {
+            let action = match rule.action {
+                AutomodAction::Timeout => ModAction::Timeout,
+                _ => ModAction::Warn,
+            };
+            let bot = ctx.cache.current_user().id;
+            sanction(ctx, data, &msg.author, bot, Sanction {
+                action,
+                reason,
+                duration: rule.duration.map(chrono::Duration::seconds),
+                evidence: None,
+            })
+            .await?
+            .err()
+        },
+    };
+    if let (Some(embed), Some(channel)) = (report, data.mod_log_channel) {
+        let _ = channel
+            .send_message(ctx, CreateMessage::new().embed(embed.build()))
+            .await;
+    }
+    Ok(true)
+}
+
+#[poise::command(
+    slash_command,
+    rename = "automod",
+    subcommands("add", "remove", "list", "exempt", "unexempt"),
+    subcommand_required,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn automod_command(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Adds an automod rule. Flagged messages are always deleted and earn no XP.
+#[poise::command(slash_command)]
+pub async fn add(
+    ctx: Ctx<'_>,
+    kind: RuleKind,
+    action: RuleAction,
+    #[description = "The word or regex to catch"] pattern: Option<String>,
+    #[description = "Mentions, percent of caps or emoji before a message is caught"]
+    #[min = 1]
+    threshold: Option<i32>,
+    #[description = "How long to time out for, e.g. 10m or 1h"] duration: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let kind = match kind {
+        RuleKind::Word => AutomodKind::Word,
+        RuleKind::Regex => AutomodKind::Regex,
+        RuleKind::Invite => AutomodKind::Invite,
+        RuleKind::MassMention => AutomodKind::MassMention,
+        RuleKind::Caps => AutomodKind::Caps,
+        RuleKind::Emoji => AutomodKind::Emoji,
+    };
+    let action = match action {
+        RuleAction::Delete => AutomodAction::Delete,
+        RuleAction::Warn => AutomodAction::Warn,
+        RuleAction::Timeout => AutomodAction::Timeout,
+    };
+    let pattern = match (kind, pattern) {
+        (AutomodKind::Word | AutomodKind::Regex, None) => {
+            return Embed::error(&ctx)
+                .description("This rule needs a pattern.")
+                .send(&ctx)
+                .await;
+        },
+        (AutomodKind::Regex, Some(pattern)) => {
+            if let Err(e) = Regex::new(&pattern) {
+                return Embed::error(&ctx)
+                    .description(format!("`{}` is not a valid regex: {}", pattern, e))
+                    .send(&ctx)
+                    .await;
+            }
+            Some(pattern)
+        },
+        (AutomodKind::Word, pattern) => pattern,
+        _ => None,
+    };
+    let duration = match (action, duration) {
+        (AutomodAction::Timeout, Some(duration)) => {
+            match parse_duration(&duration) {
+                Some(length) if length <= chrono::Duration::days(28) => Some(length),
+                _ => {
+                    return Embed::error(&ctx)
+                        .description(format!(
+                            "{} is not a valid timeout, use something like `10m` or `1h`, up to \
+                             28 days.",
+                            duration
+                        ))
+                        .send(&ctx)
+                        .await;
+                },
+            }
+        },
+        (AutomodAction::Timeout, None) => {
+            return Embed::error(&ctx)
+                .description("Timeouts need a duration.")
+                .send(&ctx)
+                .await;
+        },
+        _ => None,
+    };
+    let rule = entity::automod_rule::ActiveModel {
+        kind: Set(kind),
+        pattern: Set(pattern),
+        threshold: Set(default_threshold(kind).map(|d| threshold.unwrap_or(d))),
+        action: Set(action),
+        duration: Set(duration.map(|d| d.num_seconds())),
+        ..Default::default()
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    PATTERNS.lock().unwrap().insert(rule.id, compile(&rule));
+    Audit::new(&ctx).after(describe(&rule)).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Added automod rule {}.", describe(&rule)))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn remove(ctx: Ctx<'_>, rule: i32) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(rule) = entity::automod_rule::Entity::find_by_id(rule)
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("Automod rule #{} does not exist.", rule))
+            .send(&ctx)
+            .await;
+    };
+    let before = describe(&rule);
+    PATTERNS.lock().unwrap().remove(&rule.id);
+    rule.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).before(&before).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Removed automod rule {}.", before))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn list(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let rules = entity::automod_rule::Entity::find()
+        .order_by_asc(entity::automod_rule::Column::Id)
+        .all(&ctx.data().db)
+        .await?;
+    let exemptions = entity::automod_exemption::Entity::find()
+        .order_by_asc(entity::automod_exemption::Column::Channel)
+        .all(&ctx.data().db)
+        .await?;
+    let mut embed = Embed::info(&ctx)
+        .title("Automod")
+        .description(if rules.is_empty() {
+            "No automod rules.".to_string()
+        } else {
+            rules.iter().map(describe).collect::<Vec<_>>().join("\n")
+        });
+    if !exemptions.is_empty() {
+        embed = embed.field(
+            "Exemptions",
+            exemptions
+                .iter()
+                .map(|e| {
+                    format!(
+                        "{} - {}",
+                        Mention::from(e.channel.parse::<ChannelId>().unwrap()),
+                        e.rule
+                            .map(|r| format!("rule #{}", r))
+                            .unwrap_or_else(|| "all rules".to_string())
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            false,
+        );
+    }
+    embed.send(&ctx).await
+}
+
+/// Exempts a channel and its threads from a rule, or from all rules.
+#[poise::command(slash_command)]
+pub async fn exempt(
+    ctx: Ctx<'_>,
+    channel: GuildChannel,
+    rule: Option<i32>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    if let Some(rule) = rule {
+        if entity::automod_rule::Entity::find_by_id(rule)
+            .one(&ctx.data().db)
+            .await?
+            .is_none()
+        {
+            return Embed::error(&ctx)
+                .description(format!("Automod rule #{} does not exist.", rule))
+                .send(&ctx)
+                .await;
+        }
+    }
+    let scope = rule
+        .map(|r| format!("rule #{}", r))
+        .unwrap_or_else(|| "all rules".to_string());
+    let mut existing = entity::automod_exemption::Entity::find()
+        .filter(entity::automod_exemption::Column::Channel.eq(channel.id.to_string()));
+    existing = match rule {
+        Some(rule) => existing.filter(entity::automod_exemption::Column::Rule.eq(rule)),
+        None => existing.filter(entity::automod_exemption::Column::Rule.is_null()),
+    };
+    if existing.one(&ctx.data().db).await?.is_some() {
+        return Embed::error(&ctx)
+            .description(format!("{} is already exempt from {}.", channel, scope))
+            .send(&ctx)
+            .await;
+    }
+    entity::automod_exemption::ActiveModel {
+        channel: Set(channel.id.to_string()),
+        rule: Set(rule),
+        ..Default::default()
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    Audit::new(&ctx)
+        .target(channel.id)
+        .after(format!("Exempt from {}", scope))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("{} is now exempt from {}.", channel, scope))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn unexempt(
+    ctx: Ctx<'_>,
+    channel: GuildChannel,
+    rule: Option<i32>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let scope = rule
+        .map(|r| format!("rule #{}", r))
+        .unwrap_or_else(|| "all rules".to_string());
+    let mut existing = entity::automod_exemption::Entity::find()
+        .filter(entity::automod_exemption::Column::Channel.eq(channel.id.to_string()));
+    existing = match rule {
+        Some(rule) => existing.filter(entity::automod_exemption::Column::Rule.eq(rule)),
+        None => existing.filter(entity::automod_exemption::Column::Rule.is_null()),
+    };
+    let Some(exemption) = existing.one(&ctx.data().db).await? else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not exempt from {}.", channel, scope))
+            .send(&ctx)
+            .await;
+    };
+    exemption.delete(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(channel.id)
+        .before(format!("Exempt from {}", scope))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("{} is no longer exempt from {}.", channel, scope))
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![automod_command()]
+}
@@ -0,0 +1,665 @@
+use poise::serenity_prelude::{ChannelId, Context, CreateMessage, GuildChannel};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, QuerySelect, Set};
+
+use crate::{
+    utils::{
+        audit::Audit,
+        calendar::{date, day_of_year, elapsed_days, Date, Month},
+        embed::{not_admin, Embed, EmbedStyle},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// The calendar lives in this row.
+const CALENDAR: i32 = 1;
+
+/// The calendar and its months, once staff have set one up.
+async fn calendar(
+    db: &DatabaseConnection,
+) -> Result<Option<(entity::calendar::Model, Vec<entity::calendar_month::Model>)>, sea_orm::DbErr> {
+    let Some(calendar) = entity::calendar::Entity::find_by_id(CALENDAR)
+        .one(db)
+        .await?
+    else {
+        return Ok(None);
+    };
+    let months = entity::calendar_month::Entity::find()
+        .order_by_asc(entity::calendar_month::Column::Id)
+        .all(db)
+        .await?;
+    Ok(Some((calendar, months)))
+}
+
+fn months(months: &[entity::calendar_month::Model]) -> Vec<Month<'_>> {
+    months
+        .iter()
+        .map(|m| {
+            Month {
+                name: &m.name,
+                days: m.days,
+            }
+        })
+        .collect()
+}
+
+/// Where a month falls in the year.
+fn month_index(models: &[entity::calendar_month::Model], id: i32) -> Option<usize> {
+    models.iter().position(|m| m.id == id)
+}
+
+/// The in-universe date at a moment in real time. `None` if the calendar has
+/// no months or has run past the years it can count.
+fn date_at(
+    calendar: &entity::calendar::Model,
+    models: &[entity::calendar_month::Model],
+    at: DateTimeUtc,
+) -> Option<Date> {
+    let months = months(models);
+    let start = day_of_year(
+        &months,
+        month_index(models, calendar.month)?,
+        i32::try_from(calendar.day).ok()?,
+    );
+    let day = start.checked_add(elapsed_days(at - calendar.epoch, calendar.ratio)?)?;
+    date(&months, calendar.year, day)
+}
+
+/// Counts months since the start of year 0, so a change means a new month.
+fn month_number(date: Date, months: &[Month<'_>]) -> i64 {
+    date.year as i64 * months.len() as i64 + date.month as i64
+}
+
+fn format_date(calendar: &entity::calendar::Model, months: &[Month<'_>], date: Date) -> String {
+    match calendar.era {
+        Some(ref era) => {
+            format!(
+                "{} {} {} {}",
+                date.day, months[date.month].name, date.year, era
+            )
+        },
+        None => format!("{} {} {}", date.day, months[date.month].name, date.year),
+    }
+}
+
+/// Marks the current month as announced, so changing the calendar doesn't
+/// post about a month that began long ago.
+async fn mark_announced(db: &DatabaseConnection) -> Result<(), sea_orm::DbErr> {
+    let Some((calendar, models)) = calendar(db).await? else {
+        return Ok(());
+    };
+    let months = months(&models);
+    let announced =
+        date_at(&calendar, &models, chrono::Utc::now()).map(|d| month_number(d, &months));
+    let mut calendar = calendar.into_active_model();
+    calendar.announced = Set(announced);
+    calendar.update(db).await?;
+    Ok(())
+}
+
+/// Posts in the calendar's channel when a new in-universe month begins.
+pub async fn announce_months(ctx: &Context, data: &Data) -> Result<(), crate::Error> {
+    let Some((calendar, models)) = calendar(&data.db).await? else {
+        return Ok(());
+    };
+    let Some(ref channel) = calendar.channel else {
+        return Ok(());
+    };
+    let months = months(&models);
+    let Some(today) = date_at(&calendar, &models, chrono::Utc::now()) else {
+        return Ok(());
+    };
+    let number = month_number(today, &months);
+    if calendar.announced == Some(number) {
+        return Ok(());
+    }
+    let year = match calendar.era {
+        Some(ref era) => format!("{} {}", today.year, era),
+        None => today.year.to_string(),
+    };
+    channel
+        .parse::<ChannelId>()
+        .unwrap()
+        .send_message(
+            ctx,
+            CreateMessage::new().embed(
+                Embed::plain(EmbedStyle::Info)
+                    .title(format!("{} begins", months[today.month].name))
+                    .description(format!(
+                        "A new month begins in the year {}. Today is {}.",
+                        year,
+                        format_date(&calendar, &months, today)
+                    ))
+                    .build(),
+            ),
+        )
+        .await?;
+    let mut calendar = calendar.into_active_model();
+    calendar.announced = Set(Some(number));
+    calendar.update(&data.db).await?;
+    Ok(())
+}
+
+async fn no_calendar(ctx: &Ctx<'_>) -> Result<(), crate::Error> {
+    Embed::error(ctx)
+        .description("The calendar hasn't been set up yet.")
+        .send(ctx)
+        .await
+}
+
+async fn autocomplete_month(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    entity::calendar_month::Entity::find()
+        .filter(entity::calendar_month::Column::Name.contains(partial))
+        .order_by_asc(entity::calendar_month::Column::Id)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|months| months.into_iter().map(|m| m.name).collect())
+        .unwrap_or_default()
+}
+
+/// Finds a month's index in the calendar and checks a day falls in it.
+async fn find_day(
+    ctx: &Ctx<'_>,
+    months: &[Month<'_>],
+    month: &str,
+    day: i32,
+) -> Result<Option<usize>, crate::Error> {
+    let Some(index) = months
+        .iter()
+        .position(|m| m.name.eq_ignore_ascii_case(month.trim()))
+    else {
+        Embed::error(ctx)
+            .description(format!("There is no month named {}.", month))
+            .send(ctx)
+            .await?;
+        return Ok(None);
+    };
+    if day > months[index].days {
+        Embed::error(ctx)
+            .description(format!(
+                "{} only has {} days.",
+                months[index].name, months[index].days
+            ))
+            .send(ctx)
+            .await?;
+        return Ok(None);
+    }
+    Ok(Some(index))
+}
+
+/// Explains why a month can't drop below `days` days, if a recorded date is on
+/// a day it would lose.
+async fn dated(
+    db: &DatabaseConnection,
+    month: &entity::calendar_month::Model,
+    days: i32,
+) -> Result<Option<String>, sea_orm::DbErr> {
+    let calendar = entity::calendar::Entity::find_by_id(CALENDAR)
+        .one(db)
+        .await?;
+    if calendar.is_some_and(|c| c.month == month.id && c.day > days as i64) {
+        return Ok(Some(
+            "the date set with `/calendar set` is in it".to_string(),
+        ));
+    }
+    let events = entity::timeline_event::Entity::find()
+        .filter(entity::timeline_event::Column::Month.eq(month.id))
+        .filter(entity::timeline_event::Column::Day.gt(days as i64))
+        .count(db)
+        .await?;
+    Ok((events > 0).then(|| format!("{} timeline events are dated in it", events)))
+}
+
+/// Shows today's in-universe date.
+#[poise::command(slash_command, rename = "date")]
+pub async fn today(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let Some((calendar, models)) = calendar(&ctx.data().db).await? else {
+        return no_calendar(&ctx).await;
+    };
+    let months = months(&models);
+    let Some(today) = date_at(&calendar, &models, chrono::Utc::now()) else {
+        return no_calendar(&ctx).await;
+    };
+    Embed::info(&ctx)
+        .title(format_date(&calendar, &months, today))
+        .description(format!(
+            "Day {} of {} in {}, month {} of {}.",
+            today.day,
+            months[today.month].days,
+            months[today.month].name,
+            today.month + 1,
+            months.len()
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("set", "set_month", "remove_month", "announce", "view"),
+    subcommand_required,
+    rename = "calendar",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn calendar_command(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Sets today's in-universe date and how quickly in-universe time passes.
+#[poise::command(slash_command)]
+pub async fn set(
+    ctx: Ctx<'_>,
+    year: i32,
+    #[autocomplete = "autocomplete_month"] month: String,
+    #[min = 1] day: i32,
+    #[description = "In-universe days that pass each real day"]
+    #[min = 0]
+    #[max = 1000]
+    ratio: f64,
+    #[description = "Written after the year, e.g. ABY"]
+    #[max_length = 20]
+    era: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let models = entity::calendar_month::Entity::find()
+        .order_by_asc(entity::calendar_month::Column::Id)
+        .all(db)
+        .await?;
+    let months = months(&models);
+    let Some(index) = find_day(&ctx, &months, &month, day).await? else {
+        return Ok(());
+    };
+    let before = entity::calendar::Entity::find_by_id(CALENDAR)
+        .one(db)
+        .await?;
+    let calendar = entity::calendar::ActiveModel {
+        id:        Set(CALENDAR),
+        epoch:     Set(chrono::Utc::now()),
+        year:      Set(year),
+        month:     Set(models[index].id),
+        day:       Set(day as i64),
+        ratio:     Set(ratio),
+        era:       Set(era),
+        channel:   Set(before.as_ref().and_then(|c| c.channel.clone())),
+        announced: Set(None),
+    };
+    let calendar = match before {
+        Some(_) => calendar.update(db).await?,
+        None => calendar.insert(db).await?,
+    };
+    mark_announced(db).await?;
+    let today = Date {
+        year,
+        month: index,
+        day,
+    };
+    let mut audit = Audit::new(&ctx).after(format!(
+        "{}, {} days per day",
+        format_date(&calendar, &months, today),
+        ratio
+    ));
+    if let Some(ref before) = before {
+        if let Some(date) = date_at(before, &models, calendar.epoch) {
+            audit = audit.before(format!(
+                "{}, {} days per day",
+                format_date(before, &months, date),
+                before.ratio
+            ));
+        }
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Today is {}, and {} in-universe days pass each real day.",
+            format_date(&calendar, &months, today),
+            ratio
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Adds a month to the end of the year, or changes how long a month is.
+#[poise::command(slash_command, rename = "month")]
+pub async fn set_month(
+    ctx: Ctx<'_>,
+    #[max_length = 50] name: String,
+    #[min = 1] days: i32,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let before = entity::calendar_month::Entity::find()
+        .filter(entity::calendar_month::Column::Name.like(name.trim()))
+        .one(db)
+        .await?;
+    if let Some(ref before) = before {
+        if let Some(problem) = dated(db, before, days).await? {
+            return Embed::error(&ctx)
+                .description(format!(
+                    "{} can't be shortened to {} days, {}.",
+                    before.name, days, problem
+                ))
+                .send(&ctx)
+                .await;
+        }
+    }
+    let month = match before {
+        Some(ref before) => {
+            let mut month = before.clone().into_active_model();
+            month.days = Set(days);
+            month.update(db).await?
+        },
+        None => {
+            entity::calendar_month::ActiveModel {
+                name: Set(name.trim().to_string()),
+                days: Set(days),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?
+        },
+    };
+    mark_announced(db).await?;
+    let mut audit = Audit::new(&ctx).after(format!("{}, {} days", month.name, month.days));
+    if let Some(ref before) = before {
+        audit = audit.before(format!("{}, {} days", before.name, before.days));
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(format!("{} has {} days.", month.name, month.days))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, rename = "remove-month")]
+pub async fn remove_month(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_month"] name: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let Some(month) = entity::calendar_month::Entity::find()
+        .filter(entity::calendar_month::Column::Name.like(name.trim()))
+        .one(db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("There is no month named {}.", name))
+            .send(&ctx)
+            .await;
+    };
+    if let Some(problem) = dated(db, &month, 0).await? {
+        return Embed::error(&ctx)
+            .description(format!("{} can't be removed, {}.", month.name, problem))
+            .send(&ctx)
+            .await;
+    }
+    let before = format!("{}, {} days", month.name, month.days);
+    month.delete(db).await?;
+    mark_announced(db).await?;
+    Audit::new(&ctx).before(&before).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Removed the month {}.", before))
+        .send(&ctx)
+        .await
+}
+
+/// Sets where new months are announced, or stops announcing them.
+#[poise::command(slash_command)]
+pub async fn announce(ctx: Ctx<'_>, channel: Option<GuildChannel>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let Some(calendar) = entity::calendar::Entity::find_by_id(CALENDAR)
+        .one(db)
+        .await?
+    else {
+        return no_calendar(&ctx).await;
+    };
+    let before = calendar.channel.clone();
+    let mut calendar = calendar.into_active_model();
+    calendar.channel = Set(channel.as_ref().map(|c| c.id.to_string()));
+    calendar.update(db).await?;
+    // only months that begin from now on are announced
+    mark_announced(db).await?;
+    let mut audit = Audit::new(&ctx);
+    if let Some(before) = before {
+        audit = audit.before(format!("New months announced in <#{}>", before));
+    }
+    if let Some(ref channel) = channel {
+        audit = audit.after(format!("New months announced in {}", channel));
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(match channel {
+            Some(channel) => format!("New months will be announced in {}.", channel),
+            None => "New months won't be announced.".to_string(),
+        })
+        .send(&ctx)
+        .await
+}
+
+/// Shows the calendar's months and settings.
+#[poise::command(slash_command)]
+pub async fn view(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let models = entity::calendar_month::Entity::find()
+        .order_by_asc(entity::calendar_month::Column::Id)
+        .all(db)
+        .await?;
+    let mut embed = Embed::info(&ctx).title("Calendar").field(
+        "Months",
+        if models.is_empty() {
+            "None.".to_string()
+        } else {
+            models
+                .iter()
+                .map(|m| format!("{}: {} days", m.name, m.days))
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+        false,
+    );
+    if let Some(calendar) = entity::calendar::Entity::find_by_id(CALENDAR)
+        .one(db)
+        .await?
+    {
+        let months = months(&models);
+        if let Some(today) = date_at(&calendar, &models, chrono::Utc::now()) {
+            embed = embed.field("Today", format_date(&calendar, &months, today), true);
+        }
+        embed = embed
+            .field(
+                "Speed",
+                format!("{} days per real day", calendar.ratio),
+                true,
+            )
+            .field(
+                "Announcements",
+                calendar
+                    .channel
+                    .map(|c| format!("<#{}>", c))
+                    .unwrap_or_else(|| "Off".to_string()),
+                true,
+            );
+    }
+    embed.send(&ctx).await
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("view_timeline", "add_event", "remove_event"),
+    subcommand_required
+)]
+pub async fn timeline(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Shows the timeline of in-universe events, earliest first.
+#[poise::command(slash_command, rename = "view")]
+pub async fn view_timeline(ctx: Ctx<'_>, #[min = 1] page: Option<u64>) -> Result<(), crate::Error> {
+    let db = &ctx.data().db;
+    let Some((calendar, models)) = calendar(db).await? else {
+        return no_calendar(&ctx).await;
+    };
+    let months = months(&models);
+    let events = entity::timeline_event::Entity::find()
+        .order_by_asc(entity::timeline_event::Column::Year)
+        .order_by_asc(entity::timeline_event::Column::Month)
+        .order_by_asc(entity::timeline_event::Column::Day)
+        .order_by_asc(entity::timeline_event::Column::Id)
+        .limit(10)
+        .offset((page.unwrap_or(1) - 1) * 10)
+        .all(db)
+        .await?;
+    if events.is_empty() {
+        return Embed::error(&ctx)
+            .description("No events found on this page.")
+            .send(&ctx)
+            .await;
+    }
+    let mut embed = Embed::info(&ctx).title("Timeline");
+    for event in events {
+        let when = month_index(&models, event.month)
+            .map(|month| {
+                format_date(&calendar, &months, Date {
+                    year: event.year,
+                    month,
+                    day: event.day as i32,
+                })
+            })
+            .unwrap_or_else(|| "Unknown date".to_string());
+        embed = embed.field(
+            format!("{}: {}", when, event.title),
+            format!(
+                "{}\n-# #{}",
+                event.description.unwrap_or_default(),
+                event.id
+            ),
+            false,
+        );
+    }
+    embed.send(&ctx).await
+}
+
+/// Records an event on the timeline, dated today unless a date is given.
+#[poise::command(
+    slash_command,
+    rename = "add",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn add_event(
+    ctx: Ctx<'_>,
+    #[max_length = 200] title: String,
+    #[max_length = 1000] description: Option<String>,
+    year: Option<i32>,
+    #[autocomplete = "autocomplete_month"] month: Option<String>,
+    #[min = 1] day: Option<i32>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let Some((calendar, models)) = calendar(db).await? else {
+        return no_calendar(&ctx).await;
+    };
+    let months = months(&models);
+    let when = match (year, month, day) {
+        (None, None, None) => {
+            let Some(today) = date_at(&calendar, &models, chrono::Utc::now()) else {
+                return no_calendar(&ctx).await;
+            };
+            today
+        },
+        (Some(year), Some(month), Some(day)) => {
+            let Some(index) = find_day(&ctx, &months, &month, day).await? else {
+                return Ok(());
+            };
+            Date {
+                year,
+                month: index,
+                day,
+            }
+        },
+        _ => {
+            return Embed::error(&ctx)
+                .description("Give the year, month and day, or none of them for today.")
+                .send(&ctx)
+                .await;
+        },
+    };
+    let event = entity::timeline_event::ActiveModel {
+        title: Set(title),
+        description: Set(description),
+        year: Set(when.year),
+        month: Set(models[when.month].id),
+        day: Set(when.day as i64),
+        author: Set(ctx.author().id.to_string()),
+        created_at: Set(chrono::Utc::now()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    let when = format_date(&calendar, &months, when);
+    Audit::new(&ctx)
+        .after(format!("Timeline: {}, {}", when, event.title))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Added {} to the timeline on {}.",
+            event.title, when
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "remove",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn remove_event(
+    ctx: Ctx<'_>,
+    #[description = "Number shown under the event"] id: i32,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let Some(event) = entity::timeline_event::Entity::find_by_id(id)
+        .one(db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("There is no timeline event #{}.", id))
+            .send(&ctx)
+            .await;
+    };
+    let title = event.title.clone();
+    event.delete(db).await?;
+    Audit::new(&ctx)
+        .before(format!("Timeline: {}", title))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Removed {} from the timeline.", title))
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![today(), calendar_command(), timeline()]
+}
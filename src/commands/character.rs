@@ -1,101 +1,733 @@
+use entity::character::CharacterStatus;
 use poise::serenity_prelude::{
     ChannelId,
-    ChannelType,
+    CreateForumPost,
     CreateMessage,
     EditThread,
+    ForumTag,
     ForumTagId,
+    GuildChannel,
     Mention,
     RoleId,
+    User,
+    UserId,
 };
+use sea_orm::{prelude::*, Condition, IntoActiveModel, Iterable, QueryOrder, QuerySelect, Set};
 
 use crate::{
+    commands::{
+        faction::{remove_from_faction, sync_rank_roles},
+        galaxy::sync_location_role,
+    },
     utils::{
+        audit::Audit,
+        db::get_member,
         embed::{not_admin, Embed},
+        inventory::add_item,
         perms::admin,
     },
     Ctx,
     Data,
 };
 
-const CHARACTERS_CHANNEL: ChannelId = ChannelId::new(1259348869943394304);
-const APPROVED_TAG: ForumTagId = ForumTagId::new(1259350583417110549);
-const APPROVED_CHARACTER_ROLE: RoleId = RoleId::new(661973550894415903);
+pub const CHARACTERS_CHANNEL: ChannelId = ChannelId::new(1259348869943394304);
+pub const APPROVED_TAG: ForumTagId = ForumTagId::new(1259350583417110549);
+pub const APPROVED_CHARACTER_ROLE: RoleId = RoleId::new(661973550894415903);
+
+#[derive(Debug, poise::Modal)]
+#[name = "Character Registration"]
+struct CharacterModal {
+    #[name = "Name"]
+    #[max_length = 100]
+    name:        String,
+    #[name = "Species"]
+    #[max_length = 100]
+    species:     String,
+    #[name = "Age"]
+    #[placeholder = "In standard years"]
+    #[max_length = 5]
+    age:         Option<String>,
+    #[name = "Faction"]
+    #[max_length = 100]
+    faction:     Option<String>,
+    #[name = "Affiliation"]
+    #[placeholder = "Organisations, families, crews, etc."]
+    #[max_length = 200]
+    affiliation: Option<String>,
+}
+
+pub fn status_name(status: CharacterStatus) -> &'static str {
+    match status {
+        CharacterStatus::Submitted => "Submitted",
+        CharacterStatus::InReview => "In Review",
+        CharacterStatus::ChangesRequested => "Changes Requested",
+        CharacterStatus::Approved => "Approved",
+        CharacterStatus::Rejected => "Rejected",
+        CharacterStatus::Retired => "Retired",
+        CharacterStatus::Deceased => "Deceased",
+    }
+}
+
+/// The tags available on [`CHARACTERS_CHANNEL`].
+pub async fn forum_tags(ctx: &Ctx<'_>) -> Result<Vec<ForumTag>, crate::Error> {
+    Ok(CHARACTERS_CHANNEL
+        .to_channel(ctx)
+        .await?
+        .guild()
+        .map(|forum| forum.available_tags)
+        .unwrap_or_default())
+}
+
+/// The forum tag marking a character in the given state. Tags other than
+/// [`APPROVED_TAG`] are looked up by the state's name, and are `None` if the
+/// forum doesn't have one.
+pub fn state_tag(
+    available: &[ForumTag],
+    status: entity::character::CharacterStatus,
+) -> Option<ForumTagId> {
+    if status == entity::character::CharacterStatus::Approved {
+        return Some(APPROVED_TAG);
+    }
+    available
+        .iter()
+        .find(|tag| tag.name.eq_ignore_ascii_case(status_name(status)))
+        .map(|tag| tag.id)
+}
+
+/// The thread's tags with any state tags replaced by the tag for `status`.
+pub async fn retag(
+    ctx: &Ctx<'_>,
+    thread: &GuildChannel,
+    status: CharacterStatus,
+) -> Result<Vec<ForumTagId>, crate::Error> {
+    let available = forum_tags(ctx).await?;
+    let state_tags = CharacterStatus::iter()
+        .filter_map(|s| state_tag(&available, s))
+        .collect::<Vec<_>>();
+    let mut tags = thread
+        .applied_tags
+        .iter()
+        .filter(|tag| !state_tags.contains(tag))
+        .copied()
+        .collect::<Vec<_>>();
+    tags.extend(state_tag(&available, status));
+    Ok(tags)
+}
+
+fn character_embed(ctx: &Ctx<'_>, character: &entity::character::Model) -> Embed {
+    let mut embed = Embed::info(ctx)
+        .title(character.name.clone())
+        .field(
+            "Owner",
+            Mention::from(character.owner.parse::<UserId>().unwrap()).to_string(),
+            true,
+        )
+        .field("Species", character.species.clone(), true)
+        .field(
+            "Age",
+            character
+                .age
+                .map(|a| a.to_string())
+                .unwrap_or_else(|| "Unknown".to_string()),
+            true,
+        )
+        .field(
+            "Faction",
+            character
+                .faction
+                .clone()
+                .unwrap_or_else(|| "None".to_string()),
+            true,
+        )
+        .field(
+            "Affiliation",
+            character
+                .affiliation
+                .clone()
+                .unwrap_or_else(|| "None".to_string()),
+            true,
+        )
+        .field("Status", status_name(character.status), true);
+    if let Some(ref avatar) = character.avatar {
+        embed = embed.thumbnail(avatar.clone());
+    }
+    if let Some(ref thread_id) = character.thread_id {
+        embed = embed.field(
+            "Thread",
+            Mention::from(thread_id.parse::<ChannelId>().unwrap()).to_string(),
+            false,
+        );
+    }
+    embed
+}
+
+fn character_line(character: &entity::character::Model) -> String {
+    format!(
+        "**{}** - {} ({}) - {}",
+        character.name,
+        character.species,
+        Mention::from(character.owner.parse::<UserId>().unwrap()),
+        status_name(character.status)
+    )
+}
+
+async fn autocomplete_own_name(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    entity::character::Entity::find()
+        .filter(entity::character::Column::Owner.eq(ctx.author().id.to_string()))
+        .filter(entity::character::Column::Status.eq(entity::character::CharacterStatus::Approved))
+        .filter(entity::character::Column::Name.contains(partial))
+        .order_by_asc(entity::character::Column::Name)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|characters| characters.into_iter().map(|c| c.name).collect())
+        .unwrap_or_default()
+}
+
+async fn autocomplete_name(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    entity::character::Entity::find()
+        .filter(entity::character::Column::Name.contains(partial))
+        .order_by_asc(entity::character::Column::Name)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|characters| characters.into_iter().map(|c| c.name).collect())
+        .unwrap_or_default()
+}
+
+#[poise::command(
+    slash_command,
+    subcommands(
+        "register", "view", "list", "search", "switch", "proxy", "retire", "kill"
+    ),
+    subcommand_required
+)]
+pub async fn character(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
 
 #[poise::command(slash_command)]
-pub async fn approve(ctx: Ctx<'_>) -> Result<(), crate::Error> {
-    if !admin(&ctx).await? {
-        return not_admin(&ctx).await;
+pub async fn register(
+    actx: poise::ApplicationContext<'_, Data, crate::Error>,
+) -> Result<(), crate::Error> {
+    use poise::Modal as _;
+
+    let Some(modal) = CharacterModal::execute(actx).await? else {
+        return Ok(());
+    };
+    let ctx = Ctx::Application(actx);
+    let age = match modal.age.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(age) => {
+            match age.parse::<i32>() {
+                Ok(age) if age >= 0 => Some(age),
+                _ => {
+                    return Embed::error(&ctx)
+                        .description(format!("`{}` is not a valid age.", age))
+                        .send(&ctx)
+                        .await;
+                },
+            }
+        },
+    };
+    let owner = get_member(&ctx.data().db, ctx.author().id).await?;
+    let character = entity::character::ActiveModel {
+        owner: Set(owner.id),
+        name: Set(modal.name.trim().to_string()),
+        species: Set(modal.species.trim().to_string()),
+        faction: Set(modal.faction.filter(|f| !f.trim().is_empty())),
+        affiliation: Set(modal.affiliation.filter(|a| !a.trim().is_empty())),
+        age: Set(age),
+        status: Set(entity::character::CharacterStatus::Submitted),
+        created_at: Set(chrono::Utc::now()),
+        ..Default::default()
     }
-    ctx.defer_ephemeral().await?;
-    let channel = ctx.guild_channel().await;
-    match channel {
+    .insert(&ctx.data().db)
+    .await?;
+    let mut post = CreateForumPost::new(
+        character.name.clone(),
+        CreateMessage::new()
+            .content(format!(
+                "{} submitted a new character. Post the character's backstory and any other \
+                 details below.",
+                ctx.author()
+            ))
+            .embed(character_embed(&ctx, &character).build()),
+    );
+    if let Some(tag) = state_tag(
+        &forum_tags(&ctx).await?,
+        entity::character::CharacterStatus::Submitted,
+    ) {
+        post = post.add_applied_tag(tag);
+    }
+    let thread = CHARACTERS_CHANNEL
+        .create_forum_post(ctx.http(), post)
+        .await?;
+    let mut character = character.into_active_model();
+    character.thread_id = Set(Some(thread.id.to_string()));
+    character.save(&ctx.data().db).await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Your character has been submitted for approval in {}.",
+            thread
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn view(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_name"] name: String,
+    user: Option<User>,
+) -> Result<(), crate::Error> {
+    let mut query =
+        entity::character::Entity::find().filter(entity::character::Column::Name.like(&name));
+    if let Some(user) = user {
+        query = query.filter(entity::character::Column::Owner.eq(user.id.to_string()));
+    }
+    let character = query
+        .order_by_asc(entity::character::Column::Id)
+        .one(&ctx.data().db)
+        .await?;
+    match character {
+        Some(character) => character_embed(&ctx, &character).send(&ctx).await,
         None => {
             Embed::error(&ctx)
-                .description("This command must be used in a thread.")
+                .description(format!("No character named {} was found.", name))
                 .send(&ctx)
                 .await
         },
-        Some(mut thread) => {
-            match thread.kind {
-                ChannelType::PublicThread | ChannelType::PrivateThread => {
-                    let parent = thread.parent_id.unwrap();
-                    if parent != CHARACTERS_CHANNEL {
-                        return Embed::error(&ctx)
-                            .description(format!(
-                                "This command must be used in {}.",
-                                Mention::from(CHARACTERS_CHANNEL)
-                            ))
-                            .send(&ctx)
-                            .await;
-                    }
-                    let mut tags = thread.applied_tags.clone();
-                    if tags.contains(&APPROVED_TAG) {
-                        return Embed::error(&ctx)
-                            .description("This character is already approved.")
-                            .send(&ctx)
-                            .await;
-                    }
-                    tags.push(APPROVED_TAG);
-                    thread
-                        .edit_thread(
-                            ctx.http(),
-                            EditThread::new().applied_tags(tags).locked(true),
-                        )
-                        .await?;
-                    ctx.http()
-                        .add_member_role(
-                            ctx.data().primary_guild_id,
-                            thread.owner_id.unwrap(),
-                            APPROVED_CHARACTER_ROLE,
-                            Some("Character approved."),
-                        )
-                        .await?;
-                    thread
-                        .send_message(
-                            ctx.http(),
-                            CreateMessage::new().content(format!(
-                                "Congratulations, {}! Your character has been approved. You can \
-                                 select your faction by using the `/role` command.",
-                                Mention::from(thread.owner_id.unwrap())
-                            )),
-                        )
-                        .await?;
-                    Embed::success(&ctx)
-                        .description(format!("Character approved by {}.", ctx.author()))
-                        .send(&ctx)
-                        .await
-                },
-                _ => {
-                    Embed::error(&ctx)
-                        .description("This command must be used in a thread.")
-                        .send(&ctx)
-                        .await
-                },
+    }
+}
+
+#[poise::command(slash_command)]
+pub async fn list(ctx: Ctx<'_>, user: Option<User>) -> Result<(), crate::Error> {
+    let user = user.as_ref().unwrap_or_else(|| ctx.author());
+    let characters = entity::character::Entity::find()
+        .filter(entity::character::Column::Owner.eq(user.id.to_string()))
+        .order_by_asc(entity::character::Column::Id)
+        .all(&ctx.data().db)
+        .await?;
+    if characters.is_empty() {
+        return Embed::info(&ctx)
+            .description(format!("{} has no characters.", user))
+            .send(&ctx)
+            .await;
+    }
+    Embed::info(&ctx)
+        .author(user)
+        .description(
+            characters
+                .iter()
+                .map(character_line)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn search(
+    ctx: Ctx<'_>,
+    query: String,
+    #[min = 1] page: Option<u64>,
+) -> Result<(), crate::Error> {
+    let characters = entity::character::Entity::find()
+        .filter(
+            Condition::any()
+                .add(entity::character::Column::Name.contains(&query))
+                .add(entity::character::Column::Species.contains(&query))
+                .add(entity::character::Column::Faction.contains(&query))
+                .add(entity::character::Column::Affiliation.contains(&query)),
+        )
+        .order_by_asc(entity::character::Column::Name)
+        .limit(10)
+        .offset((page.unwrap_or(1) - 1) * 10)
+        .all(&ctx.data().db)
+        .await?;
+    if characters.is_empty() {
+        return Embed::error(&ctx)
+            .description("No characters found on this page.")
+            .send(&ctx)
+            .await;
+    }
+    Embed::info(&ctx)
+        .description(
+            characters
+                .iter()
+                .map(character_line)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn switch(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_own_name"] name: String,
+) -> Result<(), crate::Error> {
+    let character = entity::character::Entity::find()
+        .filter(entity::character::Column::Owner.eq(ctx.author().id.to_string()))
+        .filter(entity::character::Column::Name.like(&name))
+        .one(&ctx.data().db)
+        .await?;
+    let Some(character) = character else {
+        return Embed::error(&ctx)
+            .description(format!("You don't have a character named {}.", name))
+            .send(&ctx)
+            .await;
+    };
+    if character.status != entity::character::CharacterStatus::Approved {
+        return Embed::error(&ctx)
+            .description(format!("{} has not been approved yet.", character.name))
+            .send(&ctx)
+            .await;
+    }
+    let mut member = get_member(&ctx.data().db, ctx.author().id)
+        .await?
+        .into_active_model();
+    if member.active_character.clone().unwrap() == Some(character.id) {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is already your active character.",
+                character.name
+            ))
+            .send(&ctx)
+            .await;
+    }
+    member.active_character = Set(Some(character.id));
+    member.save(&ctx.data().db).await?;
+    // location and rank roles follow whichever character is being played
+    sync_location_role(ctx.serenity_context(), ctx.data(), ctx.author().id).await?;
+    sync_rank_roles(ctx.serenity_context(), ctx.data(), ctx.author().id).await?;
+    Embed::success(&ctx)
+        .description(format!("Switched to {}.", character.name))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn proxy(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_own_name"] name: String,
+    trigger: Option<String>,
+    avatar: Option<String>,
+) -> Result<(), crate::Error> {
+    let character = entity::character::Entity::find()
+        .filter(entity::character::Column::Owner.eq(ctx.author().id.to_string()))
+        .filter(entity::character::Column::Name.like(&name))
+        .one(&ctx.data().db)
+        .await?;
+    let Some(character) = character else {
+        return Embed::error(&ctx)
+            .description(format!("You don't have a character named {}.", name))
+            .send(&ctx)
+            .await;
+    };
+    let trigger = trigger.map(|t| {
+        t.trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .trim()
+            .to_string()
+    });
+    if let Some(ref trigger) = trigger {
+        if trigger.is_empty() {
+            return Embed::error(&ctx)
+                .description("The trigger cannot be empty.")
+                .send(&ctx)
+                .await;
+        }
+        let conflict = entity::character::Entity::find()
+            .filter(entity::character::Column::Owner.eq(ctx.author().id.to_string()))
+            .filter(entity::character::Column::Trigger.like(trigger))
+            .filter(entity::character::Column::Id.ne(character.id))
+            .one(&ctx.data().db)
+            .await?;
+        if let Some(conflict) = conflict {
+            return Embed::error(&ctx)
+                .description(format!(
+                    "{} already uses the trigger `[{}]`.",
+                    conflict.name, trigger
+                ))
+                .send(&ctx)
+                .await;
+        }
+    }
+    if let Some(ref avatar) = avatar {
+        if !avatar.starts_with("https://") {
+            return Embed::error(&ctx)
+                .description("The avatar must be an image URL.")
+                .send(&ctx)
+                .await;
+        }
+    }
+    let name = character.name.clone();
+    let mut character = character.into_active_model();
+    if let Some(trigger) = trigger {
+        character.trigger = Set(Some(trigger));
+    }
+    if let Some(avatar) = avatar {
+        character.avatar = Set(Some(avatar));
+    }
+    let character = character.update(&ctx.data().db).await?;
+    let mut embed = Embed::success(&ctx).title(name).field(
+        "Trigger",
+        character
+            .trigger
+            .map(|t| format!("`[{}] text`", t))
+            .unwrap_or_else(|| "None".to_string()),
+        false,
+    );
+    if let Some(avatar) = character.avatar {
+        embed = embed.thumbnail(avatar);
+    }
+    embed.send(&ctx).await
+}
+
+/// Takes a character out of play: marks its new status, archives its thread,
+/// hands its belongings to an heir and strips the roles that depended on it.
+async fn end_character(
+    ctx: &Ctx<'_>,
+    character: entity::character::Model,
+    status: CharacterStatus,
+    heir: Option<entity::character::Model>,
+    message: String,
+) -> Result<(), crate::Error> {
+    let db = &ctx.data().db;
+    let owner = character.owner.parse::<UserId>().unwrap();
+    let before = character.status;
+    let name = character.name.clone();
+    if let Some(ref heir) = heir {
+        let items = entity::inventory_item::Entity::find()
+            .filter(entity::inventory_item::Column::Character.eq(character.id))
+            .all(db)
+            .await?;
+        for item in items {
+            add_item(db, heir.id, &item.name, item.quantity).await?;
+            item.delete(db).await?;
+        }
+        let mut wallet = heir.clone().into_active_model();
+        wallet.balance = Set(heir.balance + character.balance);
+        wallet.save(db).await?;
+        let ships = entity::ship::Entity::find()
+            .filter(entity::ship::Column::OwnerCharacter.eq(character.id))
+            .all(db)
+            .await?;
+        for ship in ships {
+            // the heir now owns the ship, so they can't also be its crew
+            entity::ship_crew::Entity::delete_many()
+                .filter(entity::ship_crew::Column::Ship.eq(ship.id))
+                .filter(entity::ship_crew::Column::Character.eq(heir.id))
+                .exec(db)
+                .await?;
+            let mut ship = ship.into_active_model();
+            ship.owner_character = Set(Some(heir.id));
+            ship.update(db).await?;
+        }
+    }
+    remove_from_faction(ctx, &character).await?;
+    let thread = character.thread_id.clone();
+    let id = character.id;
+    let balance = character.balance;
+
+    let member = get_member(db, owner).await?;
+    let remaining = entity::character::Entity::find()
+        .filter(entity::character::Column::Owner.eq(owner.to_string()))
+        .filter(entity::character::Column::Status.eq(CharacterStatus::Approved))
+        .filter(entity::character::Column::Id.ne(id))
+        .order_by_asc(entity::character::Column::Id)
+        .all(db)
+        .await?;
+    // with no character left to play, money nobody inherited goes back to the
+    // member's own account instead of out of reach
+    let returned = heir.is_none() && remaining.is_empty() && member.active_character == Some(id);
+    let mut character = character.into_active_model();
+    character.status = Set(status);
+    if heir.is_some() || returned {
+        character.balance = Set(0);
+    }
+    character.save(db).await?;
+    if member.active_character == Some(id) {
+        let total = member.balance + if returned { balance } else { 0 };
+        let mut member = member.into_active_model();
+        member.active_character = Set(remaining.first().map(|c| c.id));
+        member.balance = Set(total);
+        member.save(db).await?;
+    }
+    // faction ranks are the roles that belong to a character, so they follow
+    // the next character up or go with the last one
+    sync_rank_roles(ctx.serenity_context(), ctx.data(), owner).await?;
+    if remaining.is_empty() {
+        ctx.http()
+            .remove_member_role(
+                ctx.data().primary_guild_id,
+                owner,
+                APPROVED_CHARACTER_ROLE,
+                Some("No approved characters left."),
+            )
+            .await?;
+    }
+
+    if let Some(thread) = thread {
+        let thread = thread.parse::<ChannelId>().unwrap();
+        if let Some(mut thread) = thread.to_channel(ctx).await?.guild() {
+            let tags = retag(ctx, &thread, status).await?;
+            thread
+                .send_message(ctx.http(), CreateMessage::new().content(message))
+                .await?;
+            thread
+                .edit_thread(
+                    ctx.http(),
+                    EditThread::new()
+                        .applied_tags(tags)
+                        .locked(true)
+                        .archived(true),
+                )
+                .await?;
+        }
+    }
+    Audit::new(ctx)
+        .target(owner)
+        .before(format!("{} ({})", name, status_name(before)))
+        .after(match heir {
+            Some(heir) => {
+                format!(
+                    "{} ({}), belongings to {}",
+                    name,
+                    status_name(status),
+                    heir.name
+                )
+            },
+            None => format!("{} ({})", name, status_name(status)),
+        })
+        .log()
+        .await
+}
+
+/// Looks up the approved character `name` owned by `owner` and the heir named
+/// `heir` owned by `heir_owner`, replying with an error if either doesn't
+/// exist.
+async fn character_and_heir(
+    ctx: &Ctx<'_>,
+    owner: &User,
+    name: &str,
+    heir_owner: &User,
+    heir: Option<String>,
+) -> Result<Option<(entity::character::Model, Option<entity::character::Model>)>, crate::Error> {
+    let character = entity::character::Entity::find()
+        .filter(entity::character::Column::Owner.eq(owner.id.to_string()))
+        .filter(entity::character::Column::Name.like(name))
+        .filter(entity::character::Column::Status.eq(CharacterStatus::Approved))
+        .one(&ctx.data().db)
+        .await?;
+    let Some(character) = character else {
+        Embed::error(ctx)
+            .description(format!(
+                "{} has no approved character named {}.",
+                owner, name
+            ))
+            .send(ctx)
+            .await?;
+        return Ok(None);
+    };
+    let heir = match heir {
+        Some(heir) => {
+            let found = entity::character::Entity::find()
+                .filter(entity::character::Column::Owner.eq(heir_owner.id.to_string()))
+                .filter(entity::character::Column::Name.like(&heir))
+                .filter(entity::character::Column::Status.eq(CharacterStatus::Approved))
+                .filter(entity::character::Column::Id.ne(character.id))
+                .order_by_asc(entity::character::Column::Id)
+                .one(&ctx.data().db)
+                .await?;
+            if found.is_none() {
+                Embed::error(ctx)
+                    .description(format!(
+                        "{} has no other approved character named {}.",
+                        heir_owner, heir
+                    ))
+                    .send(ctx)
+                    .await?;
+                return Ok(None);
             }
+            found
         },
+        None => None,
+    };
+    Ok(Some((character, heir)))
+}
+
+#[poise::command(slash_command)]
+pub async fn retire(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_name"] name: String,
+    user: Option<User>,
+    #[autocomplete = "autocomplete_name"] heir: Option<String>,
+    #[description = "Owner of the heir, if not the same player"] heir_owner: Option<User>,
+) -> Result<(), crate::Error> {
+    let owner = user.as_ref().unwrap_or_else(|| ctx.author());
+    let heir_owner = heir_owner.as_ref().unwrap_or(owner);
+    // only admins can hand belongings to another player's character
+    if (owner.id != ctx.author().id || heir_owner.id != owner.id) && !admin(&ctx).await? {
+        return not_admin(&ctx).await;
     }
+    ctx.defer_ephemeral().await?;
+    let Some((character, heir)) = character_and_heir(&ctx, owner, &name, heir_owner, heir).await?
+    else {
+        return Ok(());
+    };
+    let name = character.name.clone();
+    end_character(
+        &ctx,
+        character,
+        CharacterStatus::Retired,
+        heir,
+        format!("{} has been retired by {}.", name, ctx.author()),
+    )
+    .await?;
+    Embed::success(&ctx)
+        .description(format!("{} has been retired.", name))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn kill(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_name"] name: String,
+    user: User,
+    #[autocomplete = "autocomplete_name"] heir: Option<String>,
+    #[description = "Owner of the heir, if not the same player"] heir_owner: Option<User>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    ctx.defer_ephemeral().await?;
+    let heir_owner = heir_owner.as_ref().unwrap_or(&user);
+    let Some((character, heir)) = character_and_heir(&ctx, &user, &name, heir_owner, heir).await?
+    else {
+        return Ok(());
+    };
+    let name = character.name.clone();
+    end_character(
+        &ctx,
+        character,
+        CharacterStatus::Deceased,
+        heir,
+        format!("{} has died. May they rest in peace.", name),
+    )
+    .await?;
+    Embed::success(&ctx)
+        .description(format!("{} has been marked as deceased.", name))
+        .send(&ctx)
+        .await
 }
 
 pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
-    vec![approve()]
+    vec![character()]
 }
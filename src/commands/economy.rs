@@ -1,9 +1,9 @@
 use poise::serenity_prelude::User;
-use sea_orm::{prelude::*, ActiveValue, IntoActiveModel, Set};
 
 use crate::{
     utils::{
-        db::get_member,
+        audit::Audit,
+        db::{find_member, get_member, get_wallet},
         embed::{not_admin, Embed},
         num::{money, to_money},
         perms::{admin, is_admin},
@@ -15,24 +15,32 @@ use crate::{
 #[poise::command(slash_command)]
 pub async fn balance(ctx: Ctx<'_>, user: Option<User>) -> Result<(), crate::Error> {
     let user_id = ctx.author().id.to_string();
-    let mut mem = get_member(&ctx.data().db, &user_id).await?;
+    let mem = get_member(&ctx.data().db, &user_id).await?;
     let mut author = ctx.author();
     if let Some(ref user) = user {
         if is_admin(&mem) {
-            mem = get_member(&ctx.data().db, &user.id.to_string()).await?;
             author = user;
         } else {
-            Embed::error(&ctx)
+            return Embed::error(&ctx)
                 .description("You do not have permission to view other users' balances.")
                 .send(&ctx)
-                .await?;
+                .await;
         }
     }
-    Embed::info(&ctx)
+    if author.id != ctx.author().id && find_member(&ctx.data().db, author.id).await?.is_none() {
+        return Embed::error(&ctx)
+            .description(format!("{} doesn't have a balance yet.", author))
+            .send(&ctx)
+            .await;
+    }
+    let wallet = get_wallet(&ctx.data().db, author.id).await?;
+    let mut embed = Embed::info(&ctx)
         .author(author)
-        .description(format!("Balance is: {}", money(mem.balance)))
-        .send(&ctx)
-        .await
+        .description(format!("Balance is: {}", money(wallet.balance())));
+    if let Some(name) = wallet.character_name() {
+        embed = embed.title(name);
+    }
+    embed.send(&ctx).await
 }
 
 #[poise::command(
@@ -48,13 +56,17 @@ pub async fn add_money(
     if !admin(&ctx).await? {
         return not_admin(&ctx).await;
     }
-    let user_id = user.id.to_string();
-    let mut member = get_member(&ctx.data().db, &user_id)
-        .await?
-        .into_active_model();
+    let mut wallet = get_wallet(&ctx.data().db, user.id).await?;
     let amount = to_money(amount);
-    member.balance = ActiveValue::Set(member.balance.unwrap() + amount);
-    member.save(&ctx.data().db).await?;
+    let balance = wallet.balance();
+    wallet.set_balance(balance + amount);
+    wallet.save(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(money(balance))
+        .after(money(balance + amount))
+        .log()
+        .await?;
     Embed::success(&ctx)
         .description(format!("Added {} to {}'s balance.", money(amount), user))
         .send_pub(&ctx)
@@ -74,20 +86,23 @@ pub async fn remove_money(
     if !admin(&ctx).await? {
         return not_admin(&ctx).await;
     }
-    let user_id = user.id.to_string();
-    let mut mem = get_member(&ctx.data().db, &user_id)
-        .await?
-        .into_active_model();
+    let mut wallet = get_wallet(&ctx.data().db, user.id).await?;
     let amount = to_money(amount);
-    let balance = mem.balance.unwrap();
+    let balance = wallet.balance();
     if amount > balance {
         return Embed::error(&ctx)
             .description("Cannot remove more money than the user has.")
             .send(&ctx)
             .await;
     }
-    mem.balance = ActiveValue::Set(balance - amount);
-    mem.save(&ctx.data().db).await?;
+    wallet.set_balance(balance - amount);
+    wallet.save(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(money(balance))
+        .after(money(balance - amount))
+        .log()
+        .await?;
     Embed::success(&ctx)
         .description(format!(
             "Removed {} from {}'s balance.",
@@ -104,21 +119,17 @@ pub async fn transfer(
     user: User,
     #[min = 0] amount: f64,
 ) -> Result<(), crate::Error> {
-    let mut me = get_member(&ctx.data().db, &ctx.author().id.to_string())
-        .await?
-        .into_active_model();
-    let mut them = get_member(&ctx.data().db, &user.id.to_string())
-        .await?
-        .into_active_model();
+    let mut me = get_wallet(&ctx.data().db, ctx.author().id).await?;
+    let mut them = get_wallet(&ctx.data().db, user.id).await?;
     let amount = to_money(amount);
-    if amount > me.balance.clone().unwrap() {
+    if amount > me.balance() {
         return Embed::error(&ctx)
             .description("Cannot transfer more money than you have.")
             .send(&ctx)
             .await;
     }
-    me.balance = Set(me.balance.unwrap() - amount);
-    them.balance = Set(them.balance.unwrap() + amount);
+    me.set_balance(me.balance() - amount);
+    them.set_balance(them.balance() + amount);
     me.save(&ctx.data().db).await?;
     them.save(&ctx.data().db).await?;
     Embed::success(&ctx)
@@ -129,17 +140,15 @@ pub async fn transfer(
 
 #[poise::command(slash_command)]
 pub async fn pay(ctx: Ctx<'_>, #[min = 0] amount: f64) -> Result<(), crate::Error> {
-    let mut me = get_member(&ctx.data().db, &ctx.author().id.to_string())
-        .await?
-        .into_active_model();
+    let mut me = get_wallet(&ctx.data().db, ctx.author().id).await?;
     let amount = to_money(amount);
-    if amount > me.balance.clone().unwrap() {
+    if amount > me.balance() {
         return Embed::error(&ctx)
             .description("Cannot pay more money than you have.")
             .send(&ctx)
             .await;
     }
-    me.balance = Set(me.balance.unwrap() - amount);
+    me.set_balance(me.balance() - amount);
     me.save(&ctx.data().db).await?;
     Embed::success(&ctx)
         .description(format!("Paid {}.", money(amount)))
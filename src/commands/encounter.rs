@@ -0,0 +1,776 @@
+use poise::serenity_prelude::{
+    parse_user_mention,
+    ChannelId,
+    Context,
+    CreateMessage,
+    EditMessage,
+    Mention,
+    MessageId,
+    UserId,
+};
+use sea_orm::{prelude::*, IntoActiveModel, Set};
+
+use crate::{
+    commands::{
+        roll::{capped_breakdown, with_outcome},
+        stats::{autocomplete_stat, stat_modifier},
+    },
+    utils::{
+        audit::Audit,
+        db::find_active_character,
+        dice::{parse, roll, Expr},
+        embed::{Embed, EmbedStyle},
+        encounter::{change_hp, next_turn, sort_turns, Turn},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// Hit points of a character without an `HP` stat.
+const DEFAULT_HP: i32 = 10;
+/// What an attack has to roll to hit, before the target's `Defense` stat.
+const BASE_DEFENSE: i32 = 10;
+
+/// The encounter running in a channel, if any.
+async fn active_encounter(
+    db: &DatabaseConnection,
+    channel: ChannelId,
+) -> Result<Option<entity::encounter::Model>, sea_orm::DbErr> {
+    entity::encounter::Entity::find()
+        .filter(entity::encounter::Column::Channel.eq(channel.to_string()))
+        .filter(entity::encounter::Column::EndedAt.is_null())
+        .one(db)
+        .await
+}
+
+/// Looks up the encounter in the command's channel, telling the author when
+/// there isn't one.
+async fn current_encounter(
+    ctx: &Ctx<'_>,
+) -> Result<Option<entity::encounter::Model>, crate::Error> {
+    let encounter = active_encounter(&ctx.data().db, ctx.channel_id()).await?;
+    if encounter.is_none() {
+        Embed::error(ctx)
+            .description("There is no encounter running in this channel.")
+            .send(ctx)
+            .await?;
+    }
+    Ok(encounter)
+}
+
+/// Whether the author runs the encounter, as its gm or an admin. Tells them
+/// off when they don't.
+async fn runs(ctx: &Ctx<'_>, encounter: &entity::encounter::Model) -> Result<bool, crate::Error> {
+    if encounter.gm == ctx.author().id.to_string() || admin(ctx).await? {
+        return Ok(true);
+    }
+    Embed::error(ctx)
+        .description(format!(
+            "Only the GM, {}, can do that.",
+            Mention::User(encounter.gm.parse().unwrap())
+        ))
+        .send(ctx)
+        .await?;
+    Ok(false)
+}
+
+/// The combatants of an encounter in turn order.
+async fn combatants(
+    db: &DatabaseConnection,
+    encounter: i32,
+) -> Result<Vec<entity::combatant::Model>, sea_orm::DbErr> {
+    let mut combatants = entity::combatant::Entity::find()
+        .filter(entity::combatant::Column::Encounter.eq(encounter))
+        .all(db)
+        .await?;
+    let mut turns = turns(&combatants);
+    sort_turns(&mut turns);
+    combatants.sort_by_key(|c| turns.iter().position(|t| t.id == c.id));
+    Ok(combatants)
+}
+
+fn turns(combatants: &[entity::combatant::Model]) -> Vec<Turn> {
+    combatants
+        .iter()
+        .map(|c| {
+            Turn {
+                id:         c.id,
+                initiative: c.initiative,
+                defeated:   c.hp <= 0,
+            }
+        })
+        .collect()
+}
+
+/// Lets a combatant attack again, as a new turn of theirs is starting.
+async fn reset_acted(db: &DatabaseConnection, combatant: i32) -> Result<(), sea_orm::DbErr> {
+    entity::combatant::Entity::update_many()
+        .col_expr(
+            entity::combatant::Column::Acted,
+            sea_orm::sea_query::Expr::value(false),
+        )
+        .filter(entity::combatant::Column::Id.eq(combatant))
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+async fn find_combatant(
+    ctx: &Ctx<'_>,
+    encounter: &entity::encounter::Model,
+    name: &str,
+) -> Result<Option<entity::combatant::Model>, crate::Error> {
+    let combatant = entity::combatant::Entity::find()
+        .filter(entity::combatant::Column::Encounter.eq(encounter.id))
+        .filter(entity::combatant::Column::Name.like(name))
+        .one(&ctx.data().db)
+        .await?;
+    if combatant.is_none() {
+        Embed::error(ctx)
+            .description(format!("{} isn't in this encounter.", name))
+            .send(ctx)
+            .await?;
+    }
+    Ok(combatant)
+}
+
+async fn autocomplete_combatant(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    let Ok(Some(encounter)) = active_encounter(&ctx.data().db, ctx.channel_id()).await else {
+        return vec![];
+    };
+    combatants(&ctx.data().db, encounter.id)
+        .await
+        .map(|combatants| {
+            combatants
+                .into_iter()
+                .map(|c| c.name)
+                .filter(|name| name.to_lowercase().contains(&partial.to_lowercase()))
+                .take(25)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn conditions(combatant: &entity::combatant::Model) -> Vec<&str> {
+    combatant
+        .conditions
+        .split(", ")
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+fn describe_combatant(combatant: &entity::combatant::Model, current: bool) -> String {
+    let conditions = conditions(combatant);
+    let line = format!(
+        "`{:>3}` **{}** {}/{} HP{}",
+        combatant.initiative,
+        combatant.name,
+        combatant.hp,
+        combatant.max_hp,
+        if conditions.is_empty() {
+            String::new()
+        } else {
+            format!(" · {}", conditions.join(", "))
+        }
+    );
+    if combatant.hp <= 0 {
+        format!("~~{}~~ (defeated)", line)
+    } else if current {
+        format!("▶ {}", line)
+    } else {
+        line
+    }
+}
+
+fn tracker_embed(
+    encounter: &entity::encounter::Model,
+    combatants: &[entity::combatant::Model],
+) -> Embed {
+    let order = if combatants.is_empty() {
+        "Nobody has joined yet.".to_string()
+    } else {
+        combatants
+            .iter()
+            .map(|c| describe_combatant(c, encounter.current == Some(c.id)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let (style, title) = match encounter.ended_at {
+        Some(_) => {
+            (
+                EmbedStyle::Success,
+                format!("Encounter ended after {} rounds", encounter.round),
+            )
+        },
+        None => {
+            (
+                EmbedStyle::Info,
+                format!("Encounter, round {}", encounter.round),
+            )
+        },
+    };
+    Embed::plain(style).title(title).description(format!(
+        "GM: {}\n\n{}",
+        Mention::User(encounter.gm.parse().unwrap()),
+        order
+    ))
+}
+
+/// Updates the tracker message of an encounter, posting a new one if it has
+/// been deleted.
+async fn refresh(
+    ctx: &Context,
+    data: &Data,
+    encounter: &entity::encounter::Model,
+) -> Result<(), crate::Error> {
+    let embed = tracker_embed(encounter, &combatants(&data.db, encounter.id).await?).build();
+    let channel = encounter.channel.parse::<ChannelId>().unwrap();
+    if let Some(ref message) = encounter.message {
+        let message = message.parse::<MessageId>().unwrap();
+        match channel
+            .edit_message(ctx, message, EditMessage::new().embed(embed.clone()))
+            .await
+        {
+            Err(poise::serenity_prelude::Error::Http(e))
+                if e.status_code().map(|s| s.as_u16()) == Some(404) => {},
+            result => {
+                result?;
+                return Ok(());
+            },
+        }
+    }
+    let message = channel
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+    let mut encounter = encounter.clone().into_active_model();
+    encounter.message = Set(Some(message.id.to_string()));
+    encounter.update(&data.db).await?;
+    Ok(())
+}
+
+/// Rolls a d20 plus a character's stat, if they have it.
+async fn roll_with_stat(
+    db: &DatabaseConnection,
+    character: Option<i32>,
+    stat: &str,
+    bonus: i32,
+) -> Result<i32, crate::Error> {
+    let modifier = match character {
+        Some(character) => {
+            stat_modifier(db, character, stat)
+                .await?
+                .map(|(_, m)| m)
+                .unwrap_or_default()
+        },
+        None => 0,
+    };
+    Ok(roll(&parse(&format!("1d20{:+}", modifier + bonus))?).total() as i32)
+}
+
+/// Adds a member's active character to an encounter, taking their hit points
+/// and defense from their `HP` and `Defense` stats. Returns `None` if they
+/// have no active character or it's already fighting.
+async fn join_encounter(
+    db: &DatabaseConnection,
+    encounter: &entity::encounter::Model,
+    user: UserId,
+) -> Result<Option<entity::combatant::Model>, crate::Error> {
+    let Some(character) = find_active_character(db, user).await? else {
+        return Ok(None);
+    };
+    let joined = entity::combatant::Entity::find()
+        .filter(entity::combatant::Column::Encounter.eq(encounter.id))
+        .filter(entity::combatant::Column::Character.eq(character.id))
+        .one(db)
+        .await?;
+    if joined.is_some() {
+        return Ok(None);
+    }
+    let hp = stat_modifier(db, character.id, "HP")
+        .await?
+        .map(|(_, hp)| hp.max(1))
+        .unwrap_or(DEFAULT_HP);
+    let defense = BASE_DEFENSE
+        + stat_modifier(db, character.id, "Defense")
+            .await?
+            .map(|(_, d)| d)
+            .unwrap_or_default();
+    let initiative = roll_with_stat(db, Some(character.id), "Initiative", 0).await?;
+    Ok(Some(
+        entity::combatant::ActiveModel {
+            encounter: Set(encounter.id),
+            name: Set(character.name),
+            character: Set(Some(character.id)),
+            owner: Set(Some(user.to_string())),
+            initiative: Set(initiative),
+            hp: Set(hp),
+            max_hp: Set(hp),
+            defense: Set(defense),
+            conditions: Set(String::new()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?,
+    ))
+}
+
+/// Starts the first turn once somebody can take it.
+async fn start_turns(
+    db: &DatabaseConnection,
+    encounter: entity::encounter::Model,
+) -> Result<entity::encounter::Model, sea_orm::DbErr> {
+    if encounter.current.is_some() {
+        return Ok(encounter);
+    }
+    let Some((first, _)) = next_turn(&turns(&combatants(db, encounter.id).await?), None) else {
+        return Ok(encounter);
+    };
+    let mut encounter = encounter.into_active_model();
+    encounter.current = Set(Some(first));
+    encounter.update(db).await
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("start", "join", "npc", "next", "hp", "condition", "remove", "end"),
+    subcommand_required
+)]
+pub async fn encounter(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Starts an encounter in this channel, run by you.
+#[poise::command(slash_command)]
+pub async fn start(
+    ctx: Ctx<'_>,
+    #[description = "Members whose characters fight, e.g. @a @b"] participants: Option<String>,
+) -> Result<(), crate::Error> {
+    let db = &ctx.data().db;
+    if active_encounter(db, ctx.channel_id()).await?.is_some() {
+        return Embed::error(&ctx)
+            .description("There is already an encounter running in this channel.")
+            .send(&ctx)
+            .await;
+    }
+    let encounter = entity::encounter::ActiveModel {
+        channel: Set(ctx.channel_id().to_string()),
+        gm: Set(ctx.author().id.to_string()),
+        round: Set(1),
+        created_at: Set(chrono::Utc::now()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    let mut skipped = vec![];
+    for user in participants
+        .as_deref()
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter_map(parse_user_mention)
+    {
+        if join_encounter(db, &encounter, user).await?.is_none() {
+            skipped.push(Mention::User(user).to_string());
+        }
+    }
+    let encounter = start_turns(db, encounter).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    Audit::new(&ctx)
+        .target(ctx.channel_id())
+        .after(format!("Encounter {}", encounter.id))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Started an encounter. Players can `/encounter join` and you can add NPCs with \
+             `/encounter npc`.{}",
+            if skipped.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "\n\nSkipped {}, who have no active character or were already added.",
+                    skipped.join(", ")
+                )
+            }
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Joins the encounter in this channel as your active character.
+#[poise::command(slash_command)]
+pub async fn join(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let Some(encounter) = current_encounter(&ctx).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let Some(combatant) = join_encounter(db, &encounter, ctx.author().id).await? else {
+        return Embed::error(&ctx)
+            .description("You need an active character that isn't already in the encounter.")
+            .send(&ctx)
+            .await;
+    };
+    let encounter = start_turns(db, encounter).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    Embed::info(&ctx)
+        .description(format!(
+            "{} joins the fight with an initiative of {}.",
+            combatant.name, combatant.initiative
+        ))
+        .send_pub(&ctx)
+        .await
+}
+
+/// Adds an NPC to the encounter in this channel.
+#[poise::command(slash_command)]
+pub async fn npc(
+    ctx: Ctx<'_>,
+    #[max_length = 50] name: String,
+    #[min = 1] hp: i32,
+    #[description = "What attacks need to roll to hit, 10 by default"] defense: Option<i32>,
+    #[description = "Added to the initiative roll"] initiative: Option<i32>,
+) -> Result<(), crate::Error> {
+    let Some(encounter) = current_encounter(&ctx).await? else {
+        return Ok(());
+    };
+    if !runs(&ctx, &encounter).await? {
+        return Ok(());
+    }
+    let db = &ctx.data().db;
+    let name = name.trim().to_string();
+    let taken = entity::combatant::Entity::find()
+        .filter(entity::combatant::Column::Encounter.eq(encounter.id))
+        .filter(entity::combatant::Column::Name.like(&name))
+        .one(db)
+        .await?;
+    if taken.is_some() {
+        return Embed::error(&ctx)
+            .description(format!("{} is already in the encounter.", name))
+            .send(&ctx)
+            .await;
+    }
+    let combatant = entity::combatant::ActiveModel {
+        encounter: Set(encounter.id),
+        name: Set(name),
+        initiative: Set(
+            roll_with_stat(db, None, "Initiative", initiative.unwrap_or_default()).await?,
+        ),
+        hp: Set(hp),
+        max_hp: Set(hp),
+        defense: Set(defense.unwrap_or(BASE_DEFENSE)),
+        conditions: Set(String::new()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    let encounter = start_turns(db, encounter).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Added {} with an initiative of {}.",
+            combatant.name, combatant.initiative
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Ends the current turn and moves on to the next combatant.
+#[poise::command(slash_command)]
+pub async fn next(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let Some(encounter) = current_encounter(&ctx).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let combatants = combatants(db, encounter.id).await?;
+    let current = combatants.iter().find(|c| encounter.current == Some(c.id));
+    // whoever is taking their turn can end it themselves
+    let author = ctx.author().id.to_string();
+    if current.and_then(|c| c.owner.as_ref()) != Some(&author) && !runs(&ctx, &encounter).await? {
+        return Ok(());
+    }
+    let Some((next, new_round)) = next_turn(&turns(&combatants), encounter.current) else {
+        return Embed::error(&ctx)
+            .description("Nobody is left standing.")
+            .send(&ctx)
+            .await;
+    };
+    let round = encounter.round + new_round as i32;
+    let mut encounter = encounter.into_active_model();
+    encounter.current = Set(Some(next));
+    encounter.round = Set(round);
+    let encounter = encounter.update(db).await?;
+    reset_acted(db, next).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    let next = combatants.iter().find(|c| c.id == next).unwrap();
+    let mut description = format!("It's {}'s turn", next.name);
+    if let Some(ref owner) = next.owner {
+        description.push_str(&format!(", {}", Mention::User(owner.parse().unwrap())));
+    }
+    description.push('.');
+    Embed::info(&ctx)
+        .title(format!("Round {}", round))
+        .description(description)
+        .send_pub(&ctx)
+        .await
+}
+
+/// Damages or heals a combatant.
+#[poise::command(slash_command)]
+pub async fn hp(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_combatant"] combatant: String,
+    #[description = "Negative to damage, positive to heal"] change: i32,
+) -> Result<(), crate::Error> {
+    let Some(encounter) = current_encounter(&ctx).await? else {
+        return Ok(());
+    };
+    if !runs(&ctx, &encounter).await? {
+        return Ok(());
+    }
+    let Some(combatant) = find_combatant(&ctx, &encounter, &combatant).await? else {
+        return Ok(());
+    };
+    let hp = change_hp(combatant.hp, combatant.max_hp, change);
+    let mut combatant = combatant.into_active_model();
+    combatant.hp = Set(hp);
+    let combatant = combatant.update(&ctx.data().db).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} is at {}/{} HP.",
+            combatant.name, combatant.hp, combatant.max_hp
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Gives a combatant a condition, or takes it away if they already have it.
+#[poise::command(slash_command)]
+pub async fn condition(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_combatant"] combatant: String,
+    #[description = "e.g. Stunned or Prone"]
+    #[max_length = 30]
+    condition: String,
+) -> Result<(), crate::Error> {
+    let Some(encounter) = current_encounter(&ctx).await? else {
+        return Ok(());
+    };
+    if !runs(&ctx, &encounter).await? {
+        return Ok(());
+    }
+    let Some(combatant) = find_combatant(&ctx, &encounter, &combatant).await? else {
+        return Ok(());
+    };
+    let condition = condition.trim().replace(',', "");
+    let mut list = conditions(&combatant)
+        .into_iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+    let had = list.len();
+    list.retain(|c| !c.eq_ignore_ascii_case(&condition));
+    let added = list.len() == had;
+    if added {
+        list.push(condition.clone());
+    }
+    let name = combatant.name.clone();
+    let mut combatant = combatant.into_active_model();
+    combatant.conditions = Set(list.join(", "));
+    combatant.update(&ctx.data().db).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    Embed::success(&ctx)
+        .description(if added {
+            format!("{} is now {}.", name, condition)
+        } else {
+            format!("{} is no longer {}.", name, condition)
+        })
+        .send(&ctx)
+        .await
+}
+
+/// Takes a combatant out of the encounter, e.g. when they flee.
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_combatant"] combatant: String,
+) -> Result<(), crate::Error> {
+    let Some(encounter) = current_encounter(&ctx).await? else {
+        return Ok(());
+    };
+    if !runs(&ctx, &encounter).await? {
+        return Ok(());
+    }
+    let Some(combatant) = find_combatant(&ctx, &encounter, &combatant).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let mut encounter = encounter;
+    if encounter.current == Some(combatant.id) {
+        // pass the turn on before they go so the order doesn't start over
+        let turns = turns(&combatants(db, encounter.id).await?)
+            .into_iter()
+            .filter(|t| t.id == combatant.id || !t.defeated)
+            .collect::<Vec<_>>();
+        let next = next_turn(&turns, Some(combatant.id))
+            .map(|(id, _)| id)
+            .filter(|id| *id != combatant.id);
+        let mut active = encounter.into_active_model();
+        active.current = Set(next);
+        encounter = active.update(db).await?;
+        if let Some(next) = next {
+            reset_acted(db, next).await?;
+        }
+    }
+    let name = combatant.name.clone();
+    combatant.delete(db).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    Embed::success(&ctx)
+        .description(format!("Removed {} from the encounter.", name))
+        .send(&ctx)
+        .await
+}
+
+/// Ends the encounter in this channel.
+#[poise::command(slash_command)]
+pub async fn end(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let Some(encounter) = current_encounter(&ctx).await? else {
+        return Ok(());
+    };
+    if !runs(&ctx, &encounter).await? {
+        return Ok(());
+    }
+    let mut encounter = encounter.into_active_model();
+    encounter.current = Set(None);
+    encounter.ended_at = Set(Some(chrono::Utc::now()));
+    let encounter = encounter.update(&ctx.data().db).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    Audit::new(&ctx)
+        .target(ctx.channel_id())
+        .before(format!("Encounter {}", encounter.id))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description("The encounter is over.")
+        .send_pub(&ctx)
+        .await
+}
+
+/// Attacks a combatant on your turn, rolling to hit against their defense.
+#[poise::command(slash_command)]
+pub async fn attack(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_combatant"] target: String,
+    #[description = "Damage dealt on a hit, e.g. 2d6+3"] damage: String,
+    #[description = "Skill or attribute added to the attack roll"]
+    #[autocomplete = "autocomplete_stat"]
+    stat: Option<String>,
+) -> Result<(), crate::Error> {
+    let Some(encounter) = current_encounter(&ctx).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let damage_expr = match parse(&damage) {
+        Ok(expr @ Expr::Standard(_)) => expr,
+        Ok(Expr::Narrative(_)) => {
+            return Embed::error(&ctx)
+                .description("Damage can't be a narrative dice pool.")
+                .send(&ctx)
+                .await;
+        },
+        Err(e) => {
+            return Embed::error(&ctx)
+                .description(format!("`{}` is not a valid roll. {}", damage, e))
+                .send(&ctx)
+                .await;
+        },
+    };
+    let Some(attacker) = (match encounter.current {
+        Some(id) => entity::combatant::Entity::find_by_id(id).one(db).await?,
+        None => None,
+    }) else {
+        return Embed::error(&ctx)
+            .description("Nobody is taking a turn yet.")
+            .send(&ctx)
+            .await;
+    };
+    // players attack on their own turn, the gm attacks for npcs
+    let author = ctx.author().id.to_string();
+    if attacker.owner.as_ref() != Some(&author) && !runs(&ctx, &encounter).await? {
+        return Ok(());
+    }
+    if attacker.acted {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} has already attacked this turn, end it with `/encounter next`.",
+                attacker.name
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let Some(target) = find_combatant(&ctx, &encounter, &target).await? else {
+        return Ok(());
+    };
+    if target.hp <= 0 {
+        return Embed::error(&ctx)
+            .description(format!("{} is already defeated.", target.name))
+            .send(&ctx)
+            .await;
+    }
+    let modifier = match (attacker.character, stat.as_deref()) {
+        (Some(character), Some(stat)) => {
+            stat_modifier(db, character, stat)
+                .await?
+                .map(|(_, m)| m)
+                .unwrap_or_default()
+        },
+        _ => 0,
+    };
+    let to_hit = roll(&parse(&format!("1d20{:+}", modifier))?);
+    let mut acted = attacker.clone().into_active_model();
+    acted.acted = Set(true);
+    acted.update(db).await?;
+    let hit = to_hit.total() >= target.defense as i64;
+    let embed = if hit {
+        Embed::success(&ctx)
+    } else {
+        Embed::error(&ctx)
+    }
+    .title(format!("{} attacks {}", attacker.name, target.name));
+    let embed = with_outcome(embed, &to_hit);
+    if !hit {
+        return embed
+            .description(format!("**Miss** against a defense of {}.", target.defense))
+            .send_pub(&ctx)
+            .await;
+    }
+    let dealt = roll(&damage_expr);
+    let amount = i32::try_from(dealt.total().max(0)).unwrap_or(i32::MAX);
+    let hp = change_hp(target.hp, target.max_hp, -amount);
+    let name = target.name.clone();
+    let (defense, max_hp) = (target.defense, target.max_hp);
+    let mut target = target.into_active_model();
+    target.hp = Set(hp);
+    target.update(db).await?;
+    refresh(ctx.serenity_context(), ctx.data(), &encounter).await?;
+    embed
+        .description(format!(
+            "**Hit** against a defense of {}. {} takes {} damage and is at {}/{} HP.{}",
+            defense,
+            name,
+            amount,
+            hp,
+            max_hp,
+            if hp <= 0 {
+                format!(" **{} is defeated!**", name)
+            } else {
+                String::new()
+            }
+        ))
+        .field("Damage", capped_breakdown(&dealt), false)
+        .send_pub(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![encounter(), attack()]
+}
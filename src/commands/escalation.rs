@@ -0,0 +1,219 @@
+use entity::mod_case::ModAction;
+use poise::serenity_prelude::{Context, CreateMessage, User};
+use sea_orm::{prelude::*, sea_query::OnConflict, QueryOrder, Set};
+
+use crate::{
+    commands::moderation::{action_name, sanction, Sanction},
+    utils::{
+        audit::Audit,
+        duration::{format_duration, parse_duration},
+        embed::{not_admin, Embed},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum EscalationAction {
+    Timeout,
+    Kick,
+    Ban,
+}
+
+fn describe(escalation: &entity::escalation::Model) -> String {
+    format!(
+        "{} warning{}: {}{}",
+        escalation.warnings,
+        if escalation.warnings == 1 { "" } else { "s" },
+        action_name(escalation.action),
+        escalation
+            .duration
+            .map(|d| format!(" for {}", format_duration(chrono::Duration::seconds(d))))
+            .unwrap_or_default()
+    )
+}
+
+/// Counts a member's warnings that haven't expired yet.
+pub async fn active_warnings(data: &Data, member: &User) -> Result<u64, crate::Error> {
+    Ok(entity::mod_case::Entity::find()
+        .filter(entity::mod_case::Column::Member.eq(member.id.to_string()))
+        .filter(entity::mod_case::Column::Action.eq(ModAction::Warn))
+        .filter(entity::mod_case::Column::CreatedAt.gt(chrono::Utc::now() - data.warning_expiry))
+        .count(&data.db)
+        .await?)
+}
+
+/// Applies the escalation set for exactly the member's number of active
+/// warnings, called whenever they're warned, so each threshold fires once as
+/// it's reached. The action is taken by the bot and recorded as its own case,
+/// and refusals are posted to the mod log.
+pub async fn escalate(ctx: &Context, data: &Data, member: &User) -> Result<(), crate::Error> {
+    let warnings = active_warnings(data, member).await? as i32;
+    let escalation = entity::escalation::Entity::find()
+        .filter(entity::escalation::Column::Warnings.eq(warnings))
+        .one(&data.db)
+        .await?;
+    let Some(escalation) = escalation else {
+        return Ok(());
+    };
+    let bot = ctx.cache.current_user().id;
+    let result = Box::pin(sanction(ctx, data, member, bot, Sanction {
+        action:   escalation.action,
+        reason:   format!("Automatic escalation after {} active warnings.", warnings),
+        duration: escalation.duration.map(chrono::Duration::seconds),
+        evidence: None,
+    }))
+    .await?;
+    if let (Err(embed), Some(channel)) = (result, data.mod_log_channel) {
+        let _ = channel
+            .send_message(ctx, CreateMessage::new().embed(embed.build()))
+            .await;
+    }
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("set", "remove", "list"),
+    subcommand_required,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn escalation(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Sets what happens when a member reaches a number of active warnings.
+#[poise::command(slash_command)]
+pub async fn set(
+    ctx: Ctx<'_>,
+    #[description = "Number of active warnings"]
+    #[min = 1]
+    warnings: i32,
+    action: EscalationAction,
+    #[description = "How long to time out for, e.g. 1h or 3d"] duration: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let action = match action {
+        EscalationAction::Timeout => ModAction::Timeout,
+        EscalationAction::Kick => ModAction::Kick,
+        EscalationAction::Ban => ModAction::Ban,
+    };
+    let duration = match (action, duration) {
+        (ModAction::Timeout, Some(duration)) => {
+            match parse_duration(&duration) {
+                Some(length) if length <= chrono::Duration::days(28) => Some(length),
+                _ => {
+                    return Embed::error(&ctx)
+                        .description(format!(
+                            "{} is not a valid timeout, use something like `1h` or `3d`, up to 28 \
+                             days.",
+                            duration
+                        ))
+                        .send(&ctx)
+                        .await;
+                },
+            }
+        },
+        (ModAction::Timeout, None) => {
+            return Embed::error(&ctx)
+                .description("Timeouts need a duration.")
+                .send(&ctx)
+                .await;
+        },
+        _ => None,
+    };
+    let before = entity::escalation::Entity::find_by_id(warnings)
+        .one(&ctx.data().db)
+        .await?;
+    let escalation = entity::escalation::Model {
+        warnings,
+        action,
+        duration: duration.map(|d| d.num_seconds()),
+    };
+    entity::escalation::Entity::insert(entity::escalation::ActiveModel {
+        warnings: Set(escalation.warnings),
+        action:   Set(escalation.action),
+        duration: Set(escalation.duration),
+    })
+    .on_conflict(
+        OnConflict::column(entity::escalation::Column::Warnings)
+            .update_columns([
+                entity::escalation::Column::Action,
+                entity::escalation::Column::Duration,
+            ])
+            .to_owned(),
+    )
+    .exec(&ctx.data().db)
+    .await?;
+    let mut audit = Audit::new(&ctx).after(describe(&escalation));
+    if let Some(ref before) = before {
+        audit = audit.before(describe(before));
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(format!("Set escalation at {}.", describe(&escalation)))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Ctx<'_>,
+    #[description = "Number of active warnings"] warnings: i32,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(escalation) = entity::escalation::Entity::find_by_id(warnings)
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("There is no escalation at {} warnings.", warnings))
+            .send(&ctx)
+            .await;
+    };
+    let before = describe(&escalation);
+    escalation.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).before(&before).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Removed escalation at {}.", before))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn list(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let escalations = entity::escalation::Entity::find()
+        .order_by_asc(entity::escalation::Column::Warnings)
+        .all(&ctx.data().db)
+        .await?;
+    let policy = if escalations.is_empty() {
+        "No escalations.".to_string()
+    } else {
+        escalations
+            .iter()
+            .map(describe)
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    Embed::info(&ctx)
+        .title("Escalation Policy")
+        .description(format!(
+            "Warnings expire after {}.\n\n{}",
+            format_duration(ctx.data().warning_expiry),
+            policy
+        ))
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![escalation()]
+}
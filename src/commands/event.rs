@@ -0,0 +1,742 @@
+use entity::event_rsvp::RsvpStatus;
+use poise::serenity_prelude::{
+    ButtonStyle,
+    ChannelId,
+    ComponentInteraction,
+    Context,
+    CreateActionRow,
+    CreateButton,
+    CreateInteractionResponse,
+    CreateInteractionResponseMessage,
+    CreateMessage,
+    EditMessage,
+    GuildChannel,
+    MessageId,
+    User,
+    UserId,
+};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, QuerySelect, Set};
+
+use crate::{
+    utils::{
+        audit::Audit,
+        db::{get_member, get_wallet},
+        duration::{parse_duration, parse_time},
+        embed::{not_admin, Embed, EmbedStyle},
+        num::{money, to_money, Ths},
+        perms::admin,
+        xp::{add_character_xp, level_up},
+    },
+    Ctx,
+    Data,
+};
+
+/// Custom id prefix of the RSVP buttons on event posts.
+pub const EVENT_PREFIX: &str = "event";
+
+/// How long before an event starts attendees are reminded, unless the event
+/// says otherwise.
+const DEFAULT_REMINDER: chrono::Duration = chrono::Duration::hours(1);
+
+/// Longest an embed field's value can be.
+const FIELD_LIMIT: usize = 1024;
+
+fn status_id(status: RsvpStatus) -> &'static str {
+    match status {
+        RsvpStatus::Going => "going",
+        RsvpStatus::Maybe => "maybe",
+        RsvpStatus::NotGoing => "no",
+    }
+}
+
+/// Mentions members one per line, counting the rest once they'd no longer fit
+/// in an embed field.
+fn mentions<'a>(rsvps: impl Iterator<Item = &'a entity::event_rsvp::Model>, empty: &str) -> String {
+    let mentions = rsvps
+        .map(|r| format!("<@{}>", r.member))
+        .collect::<Vec<_>>();
+    if mentions.is_empty() {
+        return empty.to_string();
+    }
+    let mut shown = 0;
+    let mut length = 0;
+    for mention in &mentions {
+        // leave room for the line counting the rest
+        if length + mention.len() + 1 > FIELD_LIMIT - 32 {
+            break;
+        }
+        length += mention.len() + 1;
+        shown += 1;
+    }
+    let mut list = mentions[..shown].join("\n");
+    if shown < mentions.len() {
+        list.push_str(&format!("\n… and {} more", mentions.len() - shown));
+    }
+    list
+}
+
+fn rewards(event: &entity::event::Model) -> Option<String> {
+    let mut rewards = vec![];
+    if event.xp > 0 {
+        rewards.push(format!("{} XP", event.xp.ths()));
+    }
+    if event.credits > 0 {
+        rewards.push(money(event.credits));
+    }
+    (!rewards.is_empty()).then(|| rewards.join(" and "))
+}
+
+fn event_embed(event: &entity::event::Model, rsvps: &[entity::event_rsvp::Model]) -> Embed {
+    let going = rsvps
+        .iter()
+        .filter(|r| r.status == RsvpStatus::Going)
+        .count();
+    let mut embed = Embed::plain(if event.ended_at.is_some() {
+        EmbedStyle::Success
+    } else {
+        EmbedStyle::Info
+    })
+    .title(event.title.clone())
+    .description(format!(
+        "{}\n\nStarts <t:{1}:F> (<t:{1}:R>), hosted by <@{2}>.",
+        event.description.clone().unwrap_or_default(),
+        event.starts_at.timestamp(),
+        event.host
+    ));
+    if event.ended_at.is_some() {
+        let attended = rsvps.iter().filter(|r| r.attended == Some(true));
+        embed = embed.field(
+            format!("Attended ({})", attended.clone().count()),
+            mentions(attended, "Nobody."),
+            false,
+        );
+    } else {
+        embed = embed
+            .field(
+                match event.capacity {
+                    Some(capacity) => format!("Going ({}/{})", going, capacity),
+                    None => format!("Going ({})", going),
+                },
+                mentions(
+                    rsvps.iter().filter(|r| r.status == RsvpStatus::Going),
+                    "Nobody yet.",
+                ),
+                true,
+            )
+            .field(
+                "Maybe",
+                mentions(
+                    rsvps.iter().filter(|r| r.status == RsvpStatus::Maybe),
+                    "Nobody yet.",
+                ),
+                true,
+            )
+            .field(
+                "Can't make it",
+                rsvps
+                    .iter()
+                    .filter(|r| r.status == RsvpStatus::NotGoing)
+                    .count()
+                    .to_string(),
+                true,
+            );
+    }
+    if let Some(rewards) = rewards(event) {
+        embed = embed.field("Rewards", rewards, false);
+    }
+    embed.footer(
+        if event.ended_at.is_some() {
+            format!("Event #{} has ended", event.id)
+        } else {
+            format!("Event #{}", event.id)
+        },
+        None::<String>,
+    )
+}
+
+fn event_components(event: &entity::event::Model) -> Vec<CreateActionRow> {
+    if event.ended_at.is_some() {
+        return vec![];
+    }
+    vec![CreateActionRow::Buttons(
+        [
+            (RsvpStatus::Going, "Going", ButtonStyle::Success),
+            (RsvpStatus::Maybe, "Maybe", ButtonStyle::Secondary),
+            (RsvpStatus::NotGoing, "Can't make it", ButtonStyle::Danger),
+        ]
+        .into_iter()
+        .map(|(status, label, style)| {
+            CreateButton::new(format!(
+                "{}:{}:{}",
+                EVENT_PREFIX,
+                event.id,
+                status_id(status)
+            ))
+            .label(label)
+            .style(style)
+        })
+        .collect(),
+    )]
+}
+
+async fn rsvps(
+    db: &DatabaseConnection,
+    event: i32,
+) -> Result<Vec<entity::event_rsvp::Model>, sea_orm::DbErr> {
+    entity::event_rsvp::Entity::find()
+        .filter(entity::event_rsvp::Column::Event.eq(event))
+        .order_by_asc(entity::event_rsvp::Column::RespondedAt)
+        .all(db)
+        .await
+}
+
+/// Brings an event's post up to date, posting it again if it was deleted.
+async fn refresh(
+    ctx: &Context,
+    data: &Data,
+    event: &entity::event::Model,
+) -> Result<(), crate::Error> {
+    let embed = event_embed(event, &rsvps(&data.db, event.id).await?).build();
+    let components = event_components(event);
+    let channel = event.channel.parse::<ChannelId>().unwrap();
+    if let Some(ref message) = event.message {
+        let message = message.parse::<MessageId>().unwrap();
+        match channel
+            .edit_message(
+                ctx,
+                message,
+                EditMessage::new()
+                    .embed(embed.clone())
+                    .components(components.clone()),
+            )
+            .await
+        {
+            Err(poise::serenity_prelude::Error::Http(e))
+                if e.status_code().map(|s| s.as_u16()) == Some(404) => {},
+            result => {
+                result?;
+                return Ok(());
+            },
+        }
+    }
+    let message = channel
+        .send_message(
+            ctx,
+            CreateMessage::new().embed(embed).components(components),
+        )
+        .await?;
+    let mut event = event.clone().into_active_model();
+    event.message = Set(Some(message.id.to_string()));
+    event.update(&data.db).await?;
+    Ok(())
+}
+
+/// DMs everyone who is going or might go to an event, best effort.
+async fn notify(
+    ctx: &Context,
+    data: &Data,
+    event: &entity::event::Model,
+    content: &str,
+) -> Result<(), crate::Error> {
+    for rsvp in rsvps(&data.db, event.id).await? {
+        if rsvp.status == RsvpStatus::NotGoing {
+            continue;
+        }
+        let user = rsvp.member.parse::<UserId>().unwrap();
+        if let Ok(channel) = user.create_dm_channel(ctx).await {
+            let _ = channel
+                .send_message(ctx, CreateMessage::new().content(content))
+                .await;
+        }
+    }
+    Ok(())
+}
+
+/// Reminds attendees of events that are about to start.
+pub async fn remind_events(ctx: &Context, data: &Data) -> Result<(), crate::Error> {
+    let due = entity::event::Entity::find()
+        .filter(entity::event::Column::RemindAt.lte(chrono::Utc::now()))
+        .filter(entity::event::Column::EndedAt.is_null())
+        .all(&data.db)
+        .await?;
+    for event in due {
+        notify(
+            ctx,
+            data,
+            &event,
+            &format!(
+                "Reminder: {} starts <t:{}:R> in <#{}>.",
+                event.title,
+                event.starts_at.timestamp(),
+                event.channel
+            ),
+        )
+        .await?;
+        let mut event = event.into_active_model();
+        event.remind_at = Set(None);
+        event.update(&data.db).await?;
+    }
+    Ok(())
+}
+
+/// Handles the RSVP buttons on an event, `args` being the event id and the
+/// answer.
+pub async fn rsvp_button(
+    ctx: &Context,
+    data: &Data,
+    interaction: &ComponentInteraction,
+    args: &str,
+) -> Result<(), crate::Error> {
+    let Some((id, status)) = args.split_once(':') else {
+        return Ok(());
+    };
+    let status = match status {
+        "going" => RsvpStatus::Going,
+        "maybe" => RsvpStatus::Maybe,
+        "no" => RsvpStatus::NotGoing,
+        _ => return Ok(()),
+    };
+    let reply = |embed: Embed| {
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .embed(embed.build())
+                .ephemeral(true),
+        )
+    };
+    let event = entity::event::Entity::find_by_id(id.parse::<i32>()?)
+        .one(&data.db)
+        .await?;
+    let Some(event) = event.filter(|e| e.ended_at.is_none()) else {
+        return Ok(interaction
+            .create_response(
+                ctx,
+                reply(Embed::plain(EmbedStyle::Error).description("This event is over.")),
+            )
+            .await?);
+    };
+    let rsvps = rsvps(&data.db, event.id).await?;
+    let member = interaction.user.id.to_string();
+    let own = rsvps.iter().find(|r| r.member == member);
+    if status == RsvpStatus::Going && own.is_none_or(|r| r.status != RsvpStatus::Going) {
+        let going = rsvps
+            .iter()
+            .filter(|r| r.status == RsvpStatus::Going)
+            .count() as i32;
+        if event.capacity.is_some_and(|capacity| going >= capacity) {
+            return Ok(interaction
+                .create_response(
+                    ctx,
+                    reply(
+                        Embed::plain(EmbedStyle::Error)
+                            .description("This event is full, but you can still say maybe."),
+                    ),
+                )
+                .await?);
+        }
+    }
+    match own {
+        Some(own) => {
+            let mut rsvp = own.clone().into_active_model();
+            rsvp.status = Set(status);
+            rsvp.responded_at = Set(chrono::Utc::now());
+            rsvp.update(&data.db).await?;
+        },
+        None => {
+            entity::event_rsvp::ActiveModel {
+                event: Set(event.id),
+                member: Set(member),
+                status: Set(status),
+                attended: Set(None),
+                responded_at: Set(chrono::Utc::now()),
+                ..Default::default()
+            }
+            .insert(&data.db)
+            .await?;
+        },
+    }
+    interaction
+        .create_response(
+            ctx,
+            reply(Embed::plain(EmbedStyle::Success).description(match status {
+                RsvpStatus::Going => {
+                    format!(
+                        "You're going to {}. You'll be reminded before it starts.",
+                        event.title
+                    )
+                },
+                RsvpStatus::Maybe => {
+                    format!(
+                        "You might go to {}. You'll be reminded before it starts.",
+                        event.title
+                    )
+                },
+                RsvpStatus::NotGoing => format!("You won't be at {}.", event.title),
+            })),
+        )
+        .await?;
+    refresh(ctx, data, &event).await
+}
+
+async fn find_event(ctx: &Ctx<'_>, id: i32) -> Result<Option<entity::event::Model>, crate::Error> {
+    let event = entity::event::Entity::find_by_id(id)
+        .one(&ctx.data().db)
+        .await?;
+    match event {
+        Some(event) if event.ended_at.is_some() => {
+            Embed::error(ctx)
+                .description(format!("{} has already ended.", event.title))
+                .send(ctx)
+                .await?;
+            Ok(None)
+        },
+        Some(event) => Ok(Some(event)),
+        None => {
+            Embed::error(ctx)
+                .description(format!("There is no event #{}.", id))
+                .send(ctx)
+                .await?;
+            Ok(None)
+        },
+    }
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("create", "list", "attendance", "end", "cancel"),
+    subcommand_required
+)]
+pub async fn event(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Posts an event members can RSVP to.
+#[allow(clippy::too_many_arguments)]
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn create(
+    ctx: Ctx<'_>,
+    #[max_length = 200] title: String,
+    #[description = "UTC time like 2026-10-24 19:30, or how long from now like 2d"] time: String,
+    #[description = "Where to post the event, this channel by default"] channel: Option<
+        GuildChannel,
+    >,
+    #[min = 1] capacity: Option<i32>,
+    #[max_length = 2000] description: Option<String>,
+    #[description = "How long before the start to remind attendees, 1h by default"]
+    reminder: Option<String>,
+    #[description = "XP each attendee earns"]
+    #[min = 0]
+    xp: Option<i32>,
+    #[description = "Credits each attendee earns"]
+    #[min = 0]
+    credits: Option<f64>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let now = chrono::Utc::now();
+    let Some(starts_at) = parse_time(&time, now).filter(|t| *t > now) else {
+        return Embed::error(&ctx)
+            .description(format!("{} isn't a time in the future.", time))
+            .send(&ctx)
+            .await;
+    };
+    let reminder = match reminder {
+        Some(reminder) => {
+            let Some(reminder) = parse_duration(&reminder) else {
+                return Embed::error(&ctx)
+                    .description(format!("{} isn't a valid duration.", reminder))
+                    .send(&ctx)
+                    .await;
+            };
+            reminder
+        },
+        None => DEFAULT_REMINDER,
+    };
+    let channel = channel.map(|c| c.id).unwrap_or(ctx.channel_id());
+    let event = entity::event::ActiveModel {
+        title: Set(title),
+        description: Set(description),
+        channel: Set(channel.to_string()),
+        message: Set(None),
+        host: Set(ctx.author().id.to_string()),
+        starts_at: Set(starts_at),
+        // events starting sooner than the reminder are reminded of right away
+        remind_at: Set(Some(
+            starts_at
+                .checked_sub_signed(reminder)
+                .map_or(now, |at| at.max(now)),
+        )),
+        capacity: Set(capacity),
+        xp: Set(xp.unwrap_or_default()),
+        credits: Set(to_money(credits.unwrap_or_default())),
+        ended_at: Set(None),
+        ..Default::default()
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    refresh(ctx.serenity_context(), ctx.data(), &event).await?;
+    Audit::new(&ctx)
+        .target(channel)
+        .after(format!(
+            "Event #{} {} at {}",
+            event.id, event.title, event.starts_at
+        ))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Posted {} (event #{}) in {}.",
+            event.title, event.id, channel
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Lists upcoming events.
+#[poise::command(slash_command)]
+pub async fn list(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let events = entity::event::Entity::find()
+        .filter(entity::event::Column::EndedAt.is_null())
+        .order_by_asc(entity::event::Column::StartsAt)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await?;
+    Embed::info(&ctx)
+        .title("Upcoming Events")
+        .description(if events.is_empty() {
+            "None.".to_string()
+        } else {
+            events
+                .iter()
+                .map(|e| {
+                    format!(
+                        "#{} {}, <t:{}:R> in <#{}>",
+                        e.id,
+                        e.title,
+                        e.starts_at.timestamp(),
+                        e.channel
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .send(&ctx)
+        .await
+}
+
+/// Records whether a member was at an event, whatever they answered.
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn attendance(
+    ctx: Ctx<'_>,
+    #[description = "Number shown on the event"] event: i32,
+    user: User,
+    attended: bool,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(event) = find_event(&ctx, event).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let rsvp = entity::event_rsvp::Entity::find()
+        .filter(entity::event_rsvp::Column::Event.eq(event.id))
+        .filter(entity::event_rsvp::Column::Member.eq(user.id.to_string()))
+        .one(db)
+        .await?;
+    match rsvp {
+        Some(rsvp) => {
+            let mut rsvp = rsvp.into_active_model();
+            rsvp.attended = Set(Some(attended));
+            rsvp.update(db).await?;
+        },
+        // members who turn up without answering count as going
+        None => {
+            entity::event_rsvp::ActiveModel {
+                event: Set(event.id),
+                member: Set(user.id.to_string()),
+                status: Set(RsvpStatus::Going),
+                attended: Set(Some(attended)),
+                responded_at: Set(chrono::Utc::now()),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+            refresh(ctx.serenity_context(), ctx.data(), &event).await?;
+        },
+    }
+    Embed::success(&ctx)
+        .description(if attended {
+            format!("Recorded {} as at {}.", user, event.title)
+        } else {
+            format!("Recorded {} as absent from {}.", user, event.title)
+        })
+        .send(&ctx)
+        .await
+}
+
+/// Ends an event and rewards everyone marked as there with `/event attendance`.
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn end(
+    ctx: Ctx<'_>,
+    #[description = "Number shown on the event"] event: i32,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(event) = find_event(&ctx, event).await? else {
+        return Ok(());
+    };
+    if event.starts_at > chrono::Utc::now() {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} hasn't started yet, it starts <t:{}:R>.",
+                event.title,
+                event.starts_at.timestamp()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let db = &ctx.data().db;
+    // ending it before paying out means a failure part way through can't be
+    // retried into paying the same attendees twice
+    let ended = entity::event::Entity::update_many()
+        .col_expr(
+            entity::event::Column::EndedAt,
+            Expr::value(Some(chrono::Utc::now())),
+        )
+        .col_expr(
+            entity::event::Column::RemindAt,
+            Expr::value(Option::<chrono::DateTime<chrono::Utc>>::None),
+        )
+        .filter(entity::event::Column::Id.eq(event.id))
+        .filter(entity::event::Column::EndedAt.is_null())
+        .exec(db)
+        .await?;
+    if ended.rows_affected == 0 {
+        return Embed::error(&ctx)
+            .description(format!("{} has already ended.", event.title))
+            .send(&ctx)
+            .await;
+    }
+    let event = entity::event::Entity::find_by_id(event.id)
+        .one(db)
+        .await?
+        .unwrap();
+    let mut attendees = vec![];
+    let mut unmarked = 0;
+    for rsvp in rsvps(db, event.id).await? {
+        // saying you'd go isn't proof you went
+        if rsvp.attended.is_none() && rsvp.status == RsvpStatus::Going {
+            unmarked += 1;
+        }
+        let attended = rsvp.attended.unwrap_or(false);
+        if attended {
+            attendees.push(rsvp.member.parse::<UserId>().unwrap());
+        }
+        let mut rsvp = rsvp.into_active_model();
+        rsvp.attended = Set(Some(attended));
+        rsvp.update(db).await?;
+    }
+    for attendee in &attendees {
+        if event.xp > 0 {
+            let mut member = get_member(db, attendee).await?.into_active_model();
+            member.xp = Set(member.xp.unwrap().saturating_add(event.xp));
+            add_character_xp(db, &member, event.xp).await?;
+            // the XP counts even if Discord won't take the level roles
+            if let Err(e) = level_up(ctx.http(), ctx.data(), &mut member).await {
+                tracing::error!(
+                    "Failed to level up {} after event #{}: {:?}",
+                    attendee,
+                    event.id,
+                    e
+                );
+            }
+            member.save(db).await?;
+        }
+        if event.credits > 0 {
+            let mut wallet = get_wallet(db, attendee).await?;
+            wallet.set_balance(wallet.balance().saturating_add(event.credits));
+            wallet.save(db).await?;
+        }
+    }
+    refresh(ctx.serenity_context(), ctx.data(), &event).await?;
+    Audit::new(&ctx)
+        .after(format!(
+            "Event #{} {} ended with {} attendees",
+            event.id,
+            event.title,
+            attendees.len()
+        ))
+        .log()
+        .await?;
+    let mut description = match rewards(&event) {
+        Some(rewards) if !attendees.is_empty() => {
+            format!(
+                "{} has ended. {} attendees earned {} each.",
+                event.title,
+                attendees.len(),
+                rewards
+            )
+        },
+        _ => {
+            format!(
+                "{} has ended with {} attendees.",
+                event.title,
+                attendees.len()
+            )
+        },
+    };
+    if unmarked > 0 {
+        description.push_str(&format!(
+            " {} members going weren't marked as there and count as absent.",
+            unmarked
+        ));
+    }
+    Embed::success(&ctx)
+        .description(description)
+        .send(&ctx)
+        .await
+}
+
+/// Cancels an event, letting everyone who might have gone know.
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn cancel(
+    ctx: Ctx<'_>,
+    #[description = "Number shown on the event"] event: i32,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(event) = find_event(&ctx, event).await? else {
+        return Ok(());
+    };
+    notify(
+        ctx.serenity_context(),
+        ctx.data(),
+        &event,
+        &format!("{} has been cancelled.", event.title),
+    )
+    .await?;
+    if let Some(ref message) = event.message {
+        // the post may already be gone, the event is cancelled either way
+        let _ = event
+            .channel
+            .parse::<ChannelId>()
+            .unwrap()
+            .delete_message(&ctx, message.parse::<MessageId>().unwrap())
+            .await;
+    }
+    let before = format!("Event #{} {}", event.id, event.title);
+    let title = event.title.clone();
+    event.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).before(before).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Cancelled {}.", title))
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![event()]
+}
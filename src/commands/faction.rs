@@ -0,0 +1,943 @@
+use poise::serenity_prelude::{AutocompleteChoice, Context, Mention, Role, RoleId, UserId};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, QuerySelect, Set};
+
+use crate::{
+    utils::{
+        audit::Audit,
+        db::{find_active_character, get_active_character, get_member},
+        embed::{not_admin, Embed},
+        perms::{admin, is_admin},
+    },
+    Ctx,
+    Data,
+};
+
+async fn autocomplete_faction(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    entity::faction::Entity::find()
+        .filter(entity::faction::Column::Name.contains(partial))
+        .order_by_asc(entity::faction::Column::Name)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|factions| factions.into_iter().map(|f| f.name).collect())
+        .unwrap_or_default()
+}
+
+/// Suggests characters by name and owner, picking them by id since names
+/// aren't unique.
+async fn autocomplete_character(ctx: Ctx<'_>, partial: &str) -> Vec<AutocompleteChoice> {
+    entity::character::Entity::find()
+        .filter(entity::character::Column::Name.contains(partial))
+        .filter(entity::character::Column::Status.eq(entity::character::CharacterStatus::Approved))
+        .order_by_asc(entity::character::Column::Name)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|characters| {
+            characters
+                .into_iter()
+                .map(|c| {
+                    let owner = c
+                        .owner
+                        .parse::<UserId>()
+                        .ok()
+                        .and_then(|id| ctx.cache().user(id).map(|u| u.name.clone()))
+                        .unwrap_or_else(|| c.owner.clone());
+                    AutocompleteChoice::new(format!("{} ({})", c.name, owner), c.id.to_string())
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn find_faction(
+    ctx: &Ctx<'_>,
+    name: &str,
+) -> Result<Option<entity::faction::Model>, crate::Error> {
+    let faction = entity::faction::Entity::find_by_id(name)
+        .one(&ctx.data().db)
+        .await?;
+    if faction.is_none() {
+        Embed::error(ctx)
+            .description(format!("Faction {} does not exist.", name))
+            .send(ctx)
+            .await?;
+    }
+    Ok(faction)
+}
+
+/// Finds an approved character by the id autocomplete fills in, or by name if
+/// only one character has it.
+async fn find_character(
+    ctx: &Ctx<'_>,
+    name: &str,
+) -> Result<Option<entity::character::Model>, crate::Error> {
+    let query = entity::character::Entity::find()
+        .filter(entity::character::Column::Status.eq(entity::character::CharacterStatus::Approved));
+    let mut characters = match name.trim().parse::<i32>() {
+        Ok(id) => {
+            query
+                .filter(entity::character::Column::Id.eq(id))
+                .all(&ctx.data().db)
+                .await?
+        },
+        Err(_) => {
+            query
+                .filter(entity::character::Column::Name.like(name.trim()))
+                .limit(2)
+                .all(&ctx.data().db)
+                .await?
+        },
+    };
+    if characters.len() > 1 {
+        Embed::error(ctx)
+            .description(format!(
+                "More than one character is named {}, pick one from the list.",
+                name
+            ))
+            .send(ctx)
+            .await?;
+        return Ok(None);
+    }
+    let character = characters.pop();
+    if character.is_none() {
+        Embed::error(ctx)
+            .description(format!("There is no approved character named {}.", name))
+            .send(ctx)
+            .await?;
+    }
+    Ok(character)
+}
+
+/// The faction membership of a character along with its rank.
+pub async fn membership(
+    db: &DatabaseConnection,
+    character: i32,
+) -> Result<Option<(entity::faction_member::Model, entity::faction_rank::Model)>, sea_orm::DbErr> {
+    Ok(entity::faction_member::Entity::find_by_id(character)
+        .find_also_related(entity::faction_rank::Entity)
+        .one(db)
+        .await?
+        .and_then(|(member, rank)| rank.map(|rank| (member, rank))))
+}
+
+/// The highest rank position the author can act below in a faction, which is
+/// their highest officer rank in it, or `None` if they aren't an officer.
+/// Admins can act on every rank.
+pub async fn authority(ctx: &Ctx<'_>, faction: &str) -> Result<Option<i32>, crate::Error> {
+    let member = get_member(&ctx.data().db, ctx.author().id).await?;
+    if is_admin(&member) {
+        return Ok(Some(i32::MAX));
+    }
+    Ok(entity::faction_member::Entity::find()
+        .inner_join(entity::character::Entity)
+        .filter(entity::character::Column::Owner.eq(ctx.author().id.to_string()))
+        .filter(entity::faction_member::Column::Faction.eq(faction))
+        .find_also_related(entity::faction_rank::Entity)
+        .all(&ctx.data().db)
+        .await?
+        .into_iter()
+        .filter_map(|(_, rank)| rank.filter(|r| r.officer).map(|r| r.position))
+        .max())
+}
+
+async fn not_officer(ctx: &Ctx<'_>, faction: &str) -> Result<(), crate::Error> {
+    Embed::error(ctx)
+        .description(format!("You are not an officer of {}.", faction))
+        .send(ctx)
+        .await
+}
+
+/// Gives a member the rank role of their active character's faction rank and
+/// takes away any other rank roles, so rank roles follow whichever character
+/// is being played.
+pub async fn sync_rank_roles(ctx: &Context, data: &Data, user: UserId) -> Result<(), crate::Error> {
+    let ranks = entity::faction_rank::Entity::find().all(&data.db).await?;
+    if ranks.is_empty() {
+        return Ok(());
+    }
+    let current = match find_active_character(&data.db, user).await? {
+        Some(character) => {
+            membership(&data.db, character.id)
+                .await?
+                .map(|(_, rank)| rank.id)
+        },
+        None => None,
+    };
+    // members who have left have no roles to sync
+    let Ok(guild_member) = data.primary_guild_id.member(ctx, user).await else {
+        return Ok(());
+    };
+    for rank in ranks {
+        let role = rank.role.parse::<RoleId>().unwrap();
+        let held = current == Some(rank.id);
+        let has = guild_member.roles.contains(&role);
+        if held && !has {
+            ctx.http
+                .add_member_role(
+                    data.primary_guild_id,
+                    user,
+                    role,
+                    Some("Faction rank changed."),
+                )
+                .await?;
+        } else if !held && has {
+            ctx.http
+                .remove_member_role(
+                    data.primary_guild_id,
+                    user,
+                    role,
+                    Some("Faction rank changed."),
+                )
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+async fn set_character_faction(
+    db: &DatabaseConnection,
+    character: entity::character::Model,
+    faction: Option<String>,
+) -> Result<(), sea_orm::DbErr> {
+    let mut character = character.into_active_model();
+    character.faction = Set(faction);
+    character.save(db).await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands(
+        "create",
+        "delete",
+        "add_rank",
+        "remove_rank",
+        "list",
+        "roster",
+        "join",
+        "leave",
+        "requests",
+        "accept",
+        "deny",
+        "promote",
+        "demote",
+        "kick"
+    ),
+    subcommand_required
+)]
+pub async fn faction(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+pub async fn create(
+    ctx: Ctx<'_>,
+    name: String,
+    description: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    if entity::faction::Entity::find_by_id(&name)
+        .one(&ctx.data().db)
+        .await?
+        .is_some()
+    {
+        return Embed::error(&ctx)
+            .description(format!("Faction {} already exists.", name))
+            .send(&ctx)
+            .await;
+    }
+    entity::faction::ActiveModel {
+        name:        Set(name.clone()),
+        description: Set(description),
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    Audit::new(&ctx)
+        .after(format!("Faction {}", name))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Created faction {}. Add ranks to it with `/faction add-rank`.",
+            name
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn delete(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_faction"] name: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(faction) = find_faction(&ctx, &name).await? else {
+        return Ok(());
+    };
+    if entity::faction_member::Entity::find()
+        .filter(entity::faction_member::Column::Faction.eq(&faction.name))
+        .one(&ctx.data().db)
+        .await?
+        .is_some()
+    {
+        return Embed::error(&ctx)
+            .description(format!("Faction {} still has members.", name))
+            .send(&ctx)
+            .await;
+    }
+    // the faction's ships are left without an owner for staff to hand on
+    let ships = entity::ship::Entity::find()
+        .filter(entity::ship::Column::OwnerFaction.eq(&faction.name))
+        .count(&ctx.data().db)
+        .await?;
+    faction.delete(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .before(format!("Faction {}", name))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(if ships == 0 {
+            format!("Deleted faction {}.", name)
+        } else {
+            format!(
+                "Deleted faction {}. Its {} ships have no owner until given away with `/ship \
+                 transfer`.",
+                name, ships
+            )
+        })
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, rename = "add-rank")]
+pub async fn add_rank(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_faction"] faction: String,
+    name: String,
+    role: Role,
+    officer: Option<bool>,
+    #[min = 0] position: Option<i32>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(faction) = find_faction(&ctx, &faction).await? else {
+        return Ok(());
+    };
+    if entity::auto_role::Entity::find_by_id(role.id.to_string())
+        .one(&ctx.data().db)
+        .await?
+        .is_some()
+    {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is an auto role, remove it with `/remove-auto-role` first.",
+                role
+            ))
+            .send(&ctx)
+            .await;
+    }
+    if entity::faction_rank::Entity::find()
+        .filter(entity::faction_rank::Column::Role.eq(role.id.to_string()))
+        .one(&ctx.data().db)
+        .await?
+        .is_some()
+    {
+        return Embed::error(&ctx)
+            .description(format!("{} is already a faction rank.", role))
+            .send(&ctx)
+            .await;
+    }
+    let ranks = entity::faction_rank::Entity::find()
+        .filter(entity::faction_rank::Column::Faction.eq(&faction.name))
+        .all(&ctx.data().db)
+        .await?;
+    // new ranks go on top unless a position is given, in which case the ranks
+    // at and above it move up to make room
+    let position = match position {
+        Some(position) => {
+            for rank in ranks.into_iter().filter(|r| r.position >= position) {
+                let p = rank.position;
+                let mut rank = rank.into_active_model();
+                rank.position = Set(p + 1);
+                rank.save(&ctx.data().db).await?;
+            }
+            position
+        },
+        None => ranks.iter().map(|r| r.position + 1).max().unwrap_or(0),
+    };
+    let officer = officer.unwrap_or(false);
+    entity::faction_rank::ActiveModel {
+        faction: Set(faction.name.clone()),
+        name: Set(name.clone()),
+        position: Set(position),
+        role: Set(role.id.to_string()),
+        officer: Set(officer),
+        ..Default::default()
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    Audit::new(&ctx)
+        .target(role.id)
+        .after(format!(
+            "{} rank {}{}",
+            faction.name,
+            name,
+            if officer { " (officer)" } else { "" }
+        ))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Added rank {} ({}) to {}.",
+            name, role, faction.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, rename = "remove-rank")]
+pub async fn remove_rank(ctx: Ctx<'_>, role: Role) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let rank = entity::faction_rank::Entity::find()
+        .filter(entity::faction_rank::Column::Role.eq(role.id.to_string()))
+        .one(&ctx.data().db)
+        .await?;
+    let Some(rank) = rank else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not a faction rank.", role))
+            .send(&ctx)
+            .await;
+    };
+    if entity::faction_member::Entity::find()
+        .filter(entity::faction_member::Column::Rank.eq(rank.id))
+        .one(&ctx.data().db)
+        .await?
+        .is_some()
+    {
+        return Embed::error(&ctx)
+            .description(format!(
+                "Rank {} still has members, promote or demote them first.",
+                rank.name
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let before = format!("{} rank {}", rank.faction, rank.name);
+    rank.delete(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(role.id)
+        .before(before)
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Removed {} as a faction rank.", role))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn list(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let factions = entity::faction::Entity::find()
+        .order_by_asc(entity::faction::Column::Name)
+        .all(&ctx.data().db)
+        .await?;
+    if factions.is_empty() {
+        return Embed::info(&ctx)
+            .description("No factions.")
+            .send(&ctx)
+            .await;
+    }
+    let mut embed = Embed::info(&ctx).title("Factions");
+    for faction in factions {
+        let members = entity::faction_member::Entity::find()
+            .filter(entity::faction_member::Column::Faction.eq(&faction.name))
+            .count(&ctx.data().db)
+            .await?;
+        embed = embed.field(
+            faction.name,
+            format!(
+                "{}{} member{}",
+                faction
+                    .description
+                    .map(|d| format!("{}\n", d))
+                    .unwrap_or_default(),
+                members,
+                if members != 1 { "s" } else { "" }
+            ),
+            false,
+        );
+    }
+    embed.send(&ctx).await
+}
+
+#[poise::command(slash_command)]
+pub async fn roster(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_faction"] faction: String,
+) -> Result<(), crate::Error> {
+    let Some(faction) = find_faction(&ctx, &faction).await? else {
+        return Ok(());
+    };
+    let ranks = entity::faction_rank::Entity::find()
+        .filter(entity::faction_rank::Column::Faction.eq(&faction.name))
+        .order_by_desc(entity::faction_rank::Column::Position)
+        .all(&ctx.data().db)
+        .await?;
+    let mut embed = Embed::info(&ctx).title(format!("{} Roster", faction.name));
+    if ranks.is_empty() {
+        embed = embed.description("This faction has no ranks.");
+    }
+    for rank in ranks {
+        let members = entity::character::Entity::find()
+            .inner_join(entity::faction_member::Entity)
+            .filter(entity::faction_member::Column::Rank.eq(rank.id))
+            .order_by_asc(entity::faction_member::Column::JoinedAt)
+            .all(&ctx.data().db)
+            .await?;
+        embed = embed.field(
+            format!(
+                "{}{}",
+                rank.name,
+                if rank.officer { " (Officer)" } else { "" }
+            ),
+            if members.is_empty() {
+                "None".to_string()
+            } else {
+                members
+                    .iter()
+                    .map(|c| {
+                        format!(
+                            "{} ({})",
+                            c.name,
+                            Mention::from(c.owner.parse::<UserId>().unwrap())
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            },
+            false,
+        );
+    }
+    embed.send(&ctx).await
+}
+
+#[poise::command(slash_command)]
+pub async fn join(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_faction"] faction: String,
+) -> Result<(), crate::Error> {
+    let Some(faction) = find_faction(&ctx, &faction).await? else {
+        return Ok(());
+    };
+    let member = get_member(&ctx.data().db, ctx.author().id).await?;
+    let Some(character) = get_active_character(&ctx.data().db, &member).await? else {
+        return Embed::error(&ctx)
+            .description("You need an active character to join a faction.")
+            .send(&ctx)
+            .await;
+    };
+    if let Some((membership, _)) = membership(&ctx.data().db, character.id).await? {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is already a member of {}.",
+                character.name, membership.faction
+            ))
+            .send(&ctx)
+            .await;
+    }
+    if let Some(request) = entity::faction_request::Entity::find_by_id(character.id)
+        .one(&ctx.data().db)
+        .await?
+    {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} has already requested to join {}.",
+                character.name, request.faction
+            ))
+            .send(&ctx)
+            .await;
+    }
+    entity::faction_request::ActiveModel {
+        character:  Set(character.id),
+        faction:    Set(faction.name.clone()),
+        created_at: Set(chrono::Utc::now()),
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} has requested to join {}. A faction officer will review the request.",
+            character.name, faction.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn leave(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let member = get_member(&ctx.data().db, ctx.author().id).await?;
+    let Some(character) = get_active_character(&ctx.data().db, &member).await? else {
+        return Embed::error(&ctx)
+            .description("You don't have an active character.")
+            .send(&ctx)
+            .await;
+    };
+    if let Some(request) = entity::faction_request::Entity::find_by_id(character.id)
+        .one(&ctx.data().db)
+        .await?
+    {
+        let faction = request.faction.clone();
+        request.delete(&ctx.data().db).await?;
+        return Embed::success(&ctx)
+            .description(format!(
+                "Withdrew {}'s request to join {}.",
+                character.name, faction
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let Some((membership, _)) = membership(&ctx.data().db, character.id).await? else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not in a faction.", character.name))
+            .send(&ctx)
+            .await;
+    };
+    let faction = membership.faction.clone();
+    membership.delete(&ctx.data().db).await?;
+    sync_rank_roles(
+        ctx.serenity_context(),
+        ctx.data(),
+        character.owner.parse().unwrap(),
+    )
+    .await?;
+    let name = character.name.clone();
+    set_character_faction(&ctx.data().db, character, None).await?;
+    Embed::success(&ctx)
+        .description(format!("{} has left {}.", name, faction))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn requests(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_faction"] faction: String,
+) -> Result<(), crate::Error> {
+    let Some(faction) = find_faction(&ctx, &faction).await? else {
+        return Ok(());
+    };
+    if authority(&ctx, &faction.name).await?.is_none() {
+        return not_officer(&ctx, &faction.name).await;
+    }
+    let requests = entity::character::Entity::find()
+        .find_also_related(entity::faction_request::Entity)
+        .filter(entity::faction_request::Column::Faction.eq(&faction.name))
+        .order_by_asc(entity::faction_request::Column::CreatedAt)
+        .all(&ctx.data().db)
+        .await?;
+    if requests.is_empty() {
+        return Embed::info(&ctx)
+            .description(format!("No pending requests to join {}.", faction.name))
+            .send(&ctx)
+            .await;
+    }
+    Embed::info(&ctx)
+        .title(format!("Requests to join {}", faction.name))
+        .description(
+            requests
+                .iter()
+                .filter_map(|(c, r)| r.as_ref().map(|r| (c, r)))
+                .map(|(c, r)| {
+                    format!(
+                        "{} ({}) - <t:{}:R>",
+                        c.name,
+                        Mention::from(c.owner.parse::<UserId>().unwrap()),
+                        r.created_at.timestamp()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn accept(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_character"] character: String,
+) -> Result<(), crate::Error> {
+    let Some(character) = find_character(&ctx, &character).await? else {
+        return Ok(());
+    };
+    let Some(request) = entity::faction_request::Entity::find_by_id(character.id)
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} has not requested to join a faction.",
+                character.name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    let faction = request.faction.clone();
+    if authority(&ctx, &faction).await?.is_none() {
+        return not_officer(&ctx, &faction).await;
+    }
+    let rank = entity::faction_rank::Entity::find()
+        .filter(entity::faction_rank::Column::Faction.eq(&faction))
+        .order_by_asc(entity::faction_rank::Column::Position)
+        .one(&ctx.data().db)
+        .await?;
+    let Some(rank) = rank else {
+        return Embed::error(&ctx)
+            .description(format!("{} has no ranks to join at.", faction))
+            .send(&ctx)
+            .await;
+    };
+    request.delete(&ctx.data().db).await?;
+    entity::faction_member::ActiveModel {
+        character: Set(character.id),
+        faction:   Set(faction.clone()),
+        rank:      Set(rank.id),
+        joined_at: Set(chrono::Utc::now()),
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    sync_rank_roles(
+        ctx.serenity_context(),
+        ctx.data(),
+        character.owner.parse().unwrap(),
+    )
+    .await?;
+    let owner = character.owner.parse::<UserId>().unwrap();
+    let name = character.name.clone();
+    set_character_faction(&ctx.data().db, character, Some(faction.clone())).await?;
+    Audit::new(&ctx)
+        .target(owner)
+        .after(format!("{}, {} of {}", name, rank.name, faction))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("{} has joined {} as {}.", name, faction, rank.name))
+        .send_pub(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn deny(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_character"] character: String,
+) -> Result<(), crate::Error> {
+    let Some(character) = find_character(&ctx, &character).await? else {
+        return Ok(());
+    };
+    let Some(request) = entity::faction_request::Entity::find_by_id(character.id)
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} has not requested to join a faction.",
+                character.name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    let faction = request.faction.clone();
+    if authority(&ctx, &faction).await?.is_none() {
+        return not_officer(&ctx, &faction).await;
+    }
+    request.delete(&ctx.data().db).await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Denied {}'s request to join {}.",
+            character.name, faction
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Moves a character one rank up or down within their faction.
+async fn change_rank(ctx: Ctx<'_>, character: String, up: bool) -> Result<(), crate::Error> {
+    let Some(character) = find_character(&ctx, &character).await? else {
+        return Ok(());
+    };
+    let Some((membership, rank)) = membership(&ctx.data().db, character.id).await? else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not in a faction.", character.name))
+            .send(&ctx)
+            .await;
+    };
+    let Some(authority) = authority(&ctx, &membership.faction).await? else {
+        return not_officer(&ctx, &membership.faction).await;
+    };
+    let query = entity::faction_rank::Entity::find()
+        .filter(entity::faction_rank::Column::Faction.eq(&membership.faction));
+    let next = if up {
+        query
+            .filter(entity::faction_rank::Column::Position.gt(rank.position))
+            .order_by_asc(entity::faction_rank::Column::Position)
+    } else {
+        query
+            .filter(entity::faction_rank::Column::Position.lt(rank.position))
+            .order_by_desc(entity::faction_rank::Column::Position)
+    }
+    .one(&ctx.data().db)
+    .await?;
+    let Some(next) = next else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is already at the {} rank of {}.",
+                character.name,
+                if up { "highest" } else { "lowest" },
+                membership.faction
+            ))
+            .send(&ctx)
+            .await;
+    };
+    // officers can only manage characters below them, and only up to the rank
+    // just below their own
+    if rank.position.max(next.position) >= authority {
+        return Embed::error(&ctx)
+            .description(format!(
+                "You can only {} characters below your own rank.",
+                if up { "promote" } else { "demote" }
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let faction = membership.faction.clone();
+    let mut membership = membership.into_active_model();
+    membership.rank = Set(next.id);
+    membership.save(&ctx.data().db).await?;
+    sync_rank_roles(
+        ctx.serenity_context(),
+        ctx.data(),
+        character.owner.parse().unwrap(),
+    )
+    .await?;
+    Audit::new(&ctx)
+        .target(character.owner.parse::<UserId>().unwrap())
+        .before(format!("{}, {} of {}", character.name, rank.name, faction))
+        .after(format!("{}, {} of {}", character.name, next.name, faction))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} has been {} to {}.",
+            character.name,
+            if up { "promoted" } else { "demoted" },
+            next.name
+        ))
+        .send_pub(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn promote(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_character"] character: String,
+) -> Result<(), crate::Error> {
+    change_rank(ctx, character, true).await
+}
+
+#[poise::command(slash_command)]
+pub async fn demote(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_character"] character: String,
+) -> Result<(), crate::Error> {
+    change_rank(ctx, character, false).await
+}
+
+#[poise::command(slash_command)]
+pub async fn kick(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_character"] character: String,
+) -> Result<(), crate::Error> {
+    let Some(character) = find_character(&ctx, &character).await? else {
+        return Ok(());
+    };
+    let Some((membership, rank)) = membership(&ctx.data().db, character.id).await? else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not in a faction.", character.name))
+            .send(&ctx)
+            .await;
+    };
+    let Some(authority) = authority(&ctx, &membership.faction).await? else {
+        return not_officer(&ctx, &membership.faction).await;
+    };
+    if rank.position >= authority {
+        return Embed::error(&ctx)
+            .description("You can only kick characters below your own rank.")
+            .send(&ctx)
+            .await;
+    }
+    let faction = membership.faction.clone();
+    membership.delete(&ctx.data().db).await?;
+    sync_rank_roles(
+        ctx.serenity_context(),
+        ctx.data(),
+        character.owner.parse().unwrap(),
+    )
+    .await?;
+    let owner = character.owner.parse::<UserId>().unwrap();
+    let name = character.name.clone();
+    set_character_faction(&ctx.data().db, character, None).await?;
+    Audit::new(&ctx)
+        .target(owner)
+        .before(format!("{}, {} of {}", name, rank.name, faction))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("{} has been kicked from {}.", name, faction))
+        .send_pub(&ctx)
+        .await
+}
+
+/// Removes a character from their faction, if they're in one, and syncs their
+/// owner's rank roles. Used when a character leaves play.
+pub async fn remove_from_faction(
+    ctx: &Ctx<'_>,
+    character: &entity::character::Model,
+) -> Result<(), crate::Error> {
+    if let Some(request) = entity::faction_request::Entity::find_by_id(character.id)
+        .one(&ctx.data().db)
+        .await?
+    {
+        request.delete(&ctx.data().db).await?;
+    }
+    if let Some((membership, _)) = membership(&ctx.data().db, character.id).await? {
+        membership.delete(&ctx.data().db).await?;
+        sync_rank_roles(
+            ctx.serenity_context(),
+            ctx.data(),
+            character.owner.parse().unwrap(),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+/// Whether a role is the role of a faction rank.
+pub async fn is_rank_role(db: &DatabaseConnection, role: RoleId) -> Result<bool, sea_orm::DbErr> {
+    Ok(entity::faction_rank::Entity::find()
+        .filter(entity::faction_rank::Column::Role.eq(role.to_string()))
+        .one(db)
+        .await?
+        .is_some())
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![faction()]
+}
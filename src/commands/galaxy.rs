@@ -0,0 +1,609 @@
+use poise::serenity_prelude::{Context, CreateMessage, Mention, Role, RoleId, User, UserId};
+use sea_orm::{prelude::*, Condition, IntoActiveModel, QueryOrder, QuerySelect, Set};
+
+use crate::{
+    utils::{
+        audit::Audit,
+        db::{find_active_character, get_active_character, get_member},
+        duration::{format_duration, parse_duration},
+        embed::{not_admin, Embed},
+        galaxy::{plan_journey, Journey, Route},
+        num::{money, to_money},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// When a character travelling between planets arrives, if they're still on
+/// their way.
+fn arrival(character: &entity::character::Model) -> Option<DateTimeUtc> {
+    character.arrives_at.filter(|at| *at > chrono::Utc::now())
+}
+
+/// Where a character is, e.g. `On Tatooine` or `On the way to Kessel`.
+fn describe_location(character: &entity::character::Model) -> String {
+    match (&character.location, arrival(character)) {
+        (Some(planet), Some(at)) => {
+            format!(
+                "On the way to {}, arriving <t:{}:R>",
+                planet,
+                at.timestamp()
+            )
+        },
+        (Some(planet), None) => format!("On {}", planet),
+        (None, _) => "Nowhere yet".to_string(),
+    }
+}
+
+fn describe_route(route: &entity::route::Model) -> String {
+    format!(
+        "{} ↔ {}: {}, {}",
+        route.from,
+        route.to,
+        format_duration(chrono::Duration::seconds(route.duration)),
+        money(route.cost)
+    )
+}
+
+/// Gives a member the role of the planet their active character is on, and
+/// takes away the roles of every other planet.
+pub async fn sync_location_role(
+    ctx: &Context,
+    data: &Data,
+    user: UserId,
+) -> Result<(), crate::Error> {
+    let planets = entity::planet::Entity::find()
+        .filter(entity::planet::Column::Role.is_not_null())
+        .all(&data.db)
+        .await?;
+    if planets.is_empty() {
+        return Ok(());
+    }
+    let here = match find_active_character(&data.db, user).await? {
+        Some(character) if arrival(&character).is_none() => character.location,
+        _ => None,
+    };
+    // members who have left have no roles to sync
+    let Ok(guild_member) = data.primary_guild_id.member(ctx, user).await else {
+        return Ok(());
+    };
+    for planet in planets {
+        let role = planet.role.unwrap().parse::<RoleId>().unwrap();
+        let there = here.as_ref() == Some(&planet.name);
+        let has = guild_member.roles.contains(&role);
+        if there && !has {
+            ctx.http
+                .add_member_role(data.primary_guild_id, user, role, Some("Arrived."))
+                .await?;
+        } else if !there && has {
+            ctx.http
+                .remove_member_role(data.primary_guild_id, user, role, Some("Departed."))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Lands characters whose journey is over, giving their owners the planet's
+/// role and letting them know.
+pub async fn arrive_travellers(ctx: &Context, data: &Data) -> Result<(), crate::Error> {
+    let arrived = entity::character::Entity::find()
+        .filter(entity::character::Column::ArrivesAt.lte(chrono::Utc::now()))
+        .all(&data.db)
+        .await?;
+    for character in arrived {
+        let owner = character.owner.parse::<UserId>().unwrap();
+        let name = character.name.clone();
+        let planet = character.location.clone().unwrap_or_default();
+        let mut character = character.into_active_model();
+        character.arrives_at = Set(None);
+        character.update(&data.db).await?;
+        // one member's roles failing to sync shouldn't strand everyone else
+        if let Err(e) = sync_location_role(ctx, data, owner).await {
+            tracing::error!("Failed to sync the location role of {}: {:?}", owner, e);
+        }
+        if let Ok(channel) = owner.create_dm_channel(ctx).await {
+            let _ = channel
+                .send_message(
+                    ctx,
+                    CreateMessage::new().content(format!("{} has arrived on {}.", name, planet)),
+                )
+                .await;
+        }
+    }
+    Ok(())
+}
+
+/// Plans the quickest journey between two planets over the map's routes.
+pub async fn journey(
+    db: &DatabaseConnection,
+    from: &str,
+    to: &str,
+) -> Result<Option<Journey>, sea_orm::DbErr> {
+    let routes = entity::route::Entity::find().all(db).await?;
+    Ok(plan_journey(
+        &routes
+            .iter()
+            .map(|r| {
+                Route {
+                    from:     &r.from,
+                    to:       &r.to,
+                    duration: r.duration,
+                    cost:     r.cost,
+                }
+            })
+            .collect::<Vec<_>>(),
+        from,
+        to,
+    ))
+}
+
+pub async fn find_planet(
+    ctx: &Ctx<'_>,
+    name: &str,
+) -> Result<Option<entity::planet::Model>, crate::Error> {
+    let planet = entity::planet::Entity::find()
+        .filter(entity::planet::Column::Name.like(name.trim()))
+        .one(&ctx.data().db)
+        .await?;
+    if planet.is_none() {
+        Embed::error(ctx)
+            .description(format!("There is no planet named {}.", name))
+            .send(ctx)
+            .await?;
+    }
+    Ok(planet)
+}
+
+/// The route between two planets, whichever way round it was added.
+async fn find_route(
+    db: &DatabaseConnection,
+    a: &str,
+    b: &str,
+) -> Result<Option<entity::route::Model>, sea_orm::DbErr> {
+    entity::route::Entity::find()
+        .filter(
+            Condition::any()
+                .add(
+                    entity::route::Column::From
+                        .eq(a)
+                        .and(entity::route::Column::To.eq(b)),
+                )
+                .add(
+                    entity::route::Column::From
+                        .eq(b)
+                        .and(entity::route::Column::To.eq(a)),
+                ),
+        )
+        .one(db)
+        .await
+}
+
+pub async fn autocomplete_planet(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    entity::planet::Entity::find()
+        .filter(entity::planet::Column::Name.contains(partial))
+        .order_by_asc(entity::planet::Column::Name)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|planets| planets.into_iter().map(|p| p.name).collect())
+        .unwrap_or_default()
+}
+
+/// Travels to another planet, paying the fare and taking the journey's time.
+#[poise::command(slash_command)]
+pub async fn travel(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_planet"] planet: String,
+) -> Result<(), crate::Error> {
+    let db = &ctx.data().db;
+    let member = get_member(db, ctx.author().id).await?;
+    let Some(character) = get_active_character(db, &member).await? else {
+        return Embed::error(&ctx)
+            .description("You need an active character to travel.")
+            .send(&ctx)
+            .await;
+    };
+    let Some(destination) = find_planet(&ctx, &planet).await? else {
+        return Ok(());
+    };
+    if let Some(at) = arrival(&character) {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is still on the way to {}, arriving <t:{}:R>.",
+                character.name,
+                character.location.unwrap_or_default(),
+                at.timestamp()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let Some(origin) = character.location.clone() else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} isn't on the map yet, ask staff to place them.",
+                character.name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    if origin == destination.name {
+        return Embed::error(&ctx)
+            .description(format!("{} is already on {}.", character.name, origin))
+            .send(&ctx)
+            .await;
+    }
+    let Some(journey) = journey(db, &origin, &destination.name).await? else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "There is no route from {} to {}.",
+                origin, destination.name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    if character.balance < journey.cost {
+        return Embed::error(&ctx)
+            .description(format!(
+                "The journey costs {} but {} only has {}.",
+                money(journey.cost),
+                character.name,
+                money(character.balance)
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let arrives_at = chrono::Utc::now() + chrono::Duration::seconds(journey.duration);
+    let name = character.name.clone();
+    let mut character = character.into_active_model();
+    character.balance = Set(character.balance.unwrap() - journey.cost);
+    character.location = Set(Some(destination.name.clone()));
+    character.arrives_at = Set((journey.duration > 0).then_some(arrives_at));
+    character.update(db).await?;
+    // leaving takes the old planet's role away straight away
+    sync_location_role(ctx.serenity_context(), ctx.data(), ctx.author().id).await?;
+    Embed::info(&ctx)
+        .title(format!("{} sets off for {}", name, destination.name))
+        .field("Route", journey.stops.join(" → "), false)
+        .field("Arrival", format!("<t:{}:R>", arrives_at.timestamp()), true)
+        .field("Fare", money(journey.cost), true)
+        .send_pub(&ctx)
+        .await
+}
+
+/// Shows where a character is.
+#[poise::command(slash_command)]
+pub async fn location(ctx: Ctx<'_>, user: Option<User>) -> Result<(), crate::Error> {
+    let user = user.as_ref().unwrap_or_else(|| ctx.author());
+    let Some(character) = find_active_character(&ctx.data().db, user.id).await? else {
+        return Embed::error(&ctx)
+            .description(format!("{} doesn't have an active character.", user))
+            .send(&ctx)
+            .await;
+    };
+    Embed::info(&ctx)
+        .title(character.name.clone())
+        .description(format!("{}.", describe_location(&character)))
+        .send(&ctx)
+        .await
+}
+
+/// Lists the planets on the map, or shows one planet and its routes.
+#[poise::command(slash_command)]
+pub async fn planets(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_planet"] planet: Option<String>,
+) -> Result<(), crate::Error> {
+    let db = &ctx.data().db;
+    let Some(planet) = planet else {
+        let planets = entity::planet::Entity::find()
+            .order_by_asc(entity::planet::Column::Name)
+            .all(db)
+            .await?;
+        return Embed::info(&ctx)
+            .title("Planets")
+            .description(if planets.is_empty() {
+                "The map is empty.".to_string()
+            } else {
+                planets
+                    .iter()
+                    .map(|p| p.name.clone())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .send(&ctx)
+            .await;
+    };
+    let Some(planet) = find_planet(&ctx, &planet).await? else {
+        return Ok(());
+    };
+    let routes = entity::route::Entity::find()
+        .filter(
+            Condition::any()
+                .add(entity::route::Column::From.eq(&planet.name))
+                .add(entity::route::Column::To.eq(&planet.name)),
+        )
+        .all(db)
+        .await?;
+    let mut embed = Embed::info(&ctx).title(planet.name.clone()).field(
+        "Routes",
+        if routes.is_empty() {
+            "None.".to_string()
+        } else {
+            routes
+                .iter()
+                .map(describe_route)
+                .collect::<Vec<_>>()
+                .join("\n")
+        },
+        false,
+    );
+    if let Some(description) = planet.description {
+        embed = embed.description(description);
+    }
+    if let Some(role) = planet.role {
+        embed = embed.field(
+            "Role",
+            Mention::Role(role.parse().unwrap()).to_string(),
+            true,
+        );
+    }
+    embed.send(&ctx).await
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("set_planet", "remove_planet", "set_route", "remove_route", "place"),
+    subcommand_required,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn map(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Adds a planet to the map, or replaces its description and role.
+#[poise::command(slash_command, rename = "planet")]
+pub async fn set_planet(
+    ctx: Ctx<'_>,
+    #[max_length = 50] name: String,
+    #[max_length = 1000] description: Option<String>,
+    #[description = "Role given to members whose character is on the planet"] role: Option<Role>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let before = entity::planet::Entity::find()
+        .filter(entity::planet::Column::Name.like(name.trim()))
+        .one(db)
+        .await?;
+    let planet = match before {
+        Some(ref planet) => {
+            let mut planet = planet.clone().into_active_model();
+            planet.description = Set(description);
+            planet.role = Set(role.map(|r| r.id.to_string()));
+            planet.update(db).await?
+        },
+        None => {
+            entity::planet::ActiveModel {
+                name:        Set(name.trim().to_string()),
+                description: Set(description),
+                role:        Set(role.map(|r| r.id.to_string())),
+            }
+            .insert(db)
+            .await?
+        },
+    };
+    Audit::new(&ctx)
+        .after(format!("Planet {}", planet.name))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} {}.",
+            if before.is_some() { "Updated" } else { "Added" },
+            planet.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Removes a planet and its routes. Characters on it are taken off the map.
+#[poise::command(slash_command, rename = "remove-planet")]
+pub async fn remove_planet(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_planet"] name: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(planet) = find_planet(&ctx, &name).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let stranded = entity::character::Entity::find()
+        .filter(entity::character::Column::Location.eq(&planet.name))
+        .all(db)
+        .await?;
+    if let Some(ref role) = planet.role {
+        // the planet's role can't be synced once it's gone, so take it now
+        let role = role.parse::<RoleId>().unwrap();
+        for character in &stranded {
+            let _ = ctx
+                .http()
+                .remove_member_role(
+                    ctx.data().primary_guild_id,
+                    character.owner.parse::<UserId>().unwrap(),
+                    role,
+                    Some("Planet removed."),
+                )
+                .await;
+        }
+    }
+    entity::character::Entity::update_many()
+        .col_expr(
+            entity::character::Column::Location,
+            Expr::value(None::<String>),
+        )
+        .col_expr(
+            entity::character::Column::ArrivesAt,
+            Expr::value(None::<DateTimeUtc>),
+        )
+        .filter(entity::character::Column::Location.eq(&planet.name))
+        .exec(db)
+        .await?;
+    let name = planet.name.clone();
+    planet.delete(db).await?;
+    Audit::new(&ctx)
+        .before(format!("Planet {}", name))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Removed {}. {} character{} taken off the map.",
+            name,
+            stranded.len(),
+            if stranded.len() == 1 {
+                " was"
+            } else {
+                "s were"
+            }
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Adds or replaces the route between two planets, usable both ways.
+#[poise::command(slash_command, rename = "route")]
+pub async fn set_route(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_planet"] from: String,
+    #[autocomplete = "autocomplete_planet"] to: String,
+    #[description = "How long the journey takes, e.g. 2h or 1d"] duration: String,
+    #[description = "Fare or fuel cost of the journey"]
+    #[min = 0]
+    cost: f64,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(duration) = parse_duration(&duration) else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is not a valid duration, use something like `2h` or `1d`.",
+                duration
+            ))
+            .send(&ctx)
+            .await;
+    };
+    let Some(from) = find_planet(&ctx, &from).await? else {
+        return Ok(());
+    };
+    let Some(to) = find_planet(&ctx, &to).await? else {
+        return Ok(());
+    };
+    if from.name == to.name {
+        return Embed::error(&ctx)
+            .description("A route needs two different planets.")
+            .send(&ctx)
+            .await;
+    }
+    let db = &ctx.data().db;
+    let before = find_route(db, &from.name, &to.name).await?;
+    let route = match before {
+        Some(ref route) => {
+            let mut route = route.clone().into_active_model();
+            route.duration = Set(duration.num_seconds());
+            route.cost = Set(to_money(cost));
+            route.update(db).await?
+        },
+        None => {
+            entity::route::ActiveModel {
+                from: Set(from.name),
+                to: Set(to.name),
+                duration: Set(duration.num_seconds()),
+                cost: Set(to_money(cost)),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?
+        },
+    };
+    let mut audit = Audit::new(&ctx).after(describe_route(&route));
+    if let Some(ref before) = before {
+        audit = audit.before(describe_route(before));
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(format!("Set route {}.", describe_route(&route)))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, rename = "remove-route")]
+pub async fn remove_route(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_planet"] from: String,
+    #[autocomplete = "autocomplete_planet"] to: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(route) = find_route(&ctx.data().db, from.trim(), to.trim()).await? else {
+        return Embed::error(&ctx)
+            .description(format!("There is no route between {} and {}.", from, to))
+            .send(&ctx)
+            .await;
+    };
+    let before = describe_route(&route);
+    route.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).before(&before).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Removed route {}.", before))
+        .send(&ctx)
+        .await
+}
+
+/// Puts a member's active character on a planet straight away.
+#[poise::command(slash_command)]
+pub async fn place(
+    ctx: Ctx<'_>,
+    user: User,
+    #[autocomplete = "autocomplete_planet"] planet: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let Some(character) = find_active_character(db, user.id).await? else {
+        return Embed::error(&ctx)
+            .description(format!("{} doesn't have an active character.", user))
+            .send(&ctx)
+            .await;
+    };
+    let Some(planet) = find_planet(&ctx, &planet).await? else {
+        return Ok(());
+    };
+    let before = describe_location(&character);
+    let name = character.name.clone();
+    let mut character = character.into_active_model();
+    character.location = Set(Some(planet.name.clone()));
+    character.arrives_at = Set(None);
+    character.update(db).await?;
+    sync_location_role(ctx.serenity_context(), ctx.data(), user.id).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(format!("{}: {}", name, before))
+        .after(format!("{}: On {}", name, planet.name))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Placed {} on {}.", name, planet.name))
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![travel(), location(), planets(), map()]
+}
@@ -0,0 +1,230 @@
+use entity::greeting::GreetingKind;
+use poise::serenity_prelude::{ChannelId, Context, CreateMessage, GuildChannel, Member, User};
+use sea_orm::{prelude::*, sea_query::OnConflict, Set};
+
+use crate::{
+    utils::{
+        audit::Audit,
+        embed::{not_admin, Embed, EmbedStyle},
+        perms::admin,
+        template::{render, Placeholders},
+    },
+    Ctx,
+    Data,
+};
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum Kind {
+    Welcome,
+    #[name = "Welcome DM"]
+    WelcomeDm,
+    Farewell,
+}
+
+impl From<Kind> for GreetingKind {
+    fn from(kind: Kind) -> Self {
+        match kind {
+            Kind::Welcome => GreetingKind::Welcome,
+            Kind::WelcomeDm => GreetingKind::WelcomeDm,
+            Kind::Farewell => GreetingKind::Farewell,
+        }
+    }
+}
+
+fn kind_name(kind: GreetingKind) -> &'static str {
+    match kind {
+        GreetingKind::Welcome => "welcome message",
+        GreetingKind::WelcomeDm => "welcome DM",
+        GreetingKind::Farewell => "farewell message",
+    }
+}
+
+/// Renders a greeting for a user as an embed.
+fn greeting_embed(
+    ctx: &Context,
+    data: &Data,
+    greeting: &entity::greeting::Model,
+    user: &User,
+) -> Embed {
+    let (server, member_count) = ctx
+        .cache
+        .guild(data.primary_guild_id)
+        .map(|g| (g.name.clone(), g.member_count))
+        .unwrap_or_default();
+    let placeholders = Placeholders {
+        mention: &user.to_string(),
+        user: &user.name,
+        server: &server,
+        member_count,
+        account_age: (chrono::Utc::now() - *user.created_at()).num_days(),
+    };
+    let mut embed = Embed::plain(match greeting.kind {
+        GreetingKind::Farewell => EmbedStyle::Warning,
+        _ => EmbedStyle::Info,
+    })
+    .description(render(&greeting.message, &placeholders))
+    .thumbnail(user.face());
+    if let Some(ref title) = greeting.title {
+        embed = embed.title(render(title, &placeholders));
+    }
+    embed
+}
+
+/// Sends a greeting to its channel, or to the user for welcome DMs. Members
+/// with DMs closed simply don't get one.
+async fn send_greeting(
+    ctx: &Context,
+    data: &Data,
+    kind: GreetingKind,
+    user: &User,
+) -> Result<(), crate::Error> {
+    let Some(greeting) = entity::greeting::Entity::find_by_id(kind)
+        .one(&data.db)
+        .await?
+    else {
+        return Ok(());
+    };
+    let message = CreateMessage::new().embed(greeting_embed(ctx, data, &greeting, user).build());
+    match (kind, greeting.channel) {
+        (GreetingKind::WelcomeDm, _) => {
+            if let Ok(channel) = user.create_dm_channel(ctx).await {
+                let _ = channel.send_message(ctx, message).await;
+            }
+        },
+        (_, Some(channel)) => {
+            channel
+                .parse::<ChannelId>()
+                .unwrap()
+                .send_message(ctx, message)
+                .await?;
+        },
+        (_, None) => {},
+    }
+    Ok(())
+}
+
+pub async fn welcome(ctx: &Context, data: &Data, member: &Member) -> Result<(), crate::Error> {
+    send_greeting(ctx, data, GreetingKind::Welcome, &member.user).await?;
+    send_greeting(ctx, data, GreetingKind::WelcomeDm, &member.user).await
+}
+
+pub async fn farewell(ctx: &Context, data: &Data, user: &User) -> Result<(), crate::Error> {
+    send_greeting(ctx, data, GreetingKind::Farewell, user).await
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("set", "disable", "preview"),
+    subcommand_required,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn greeting(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Sets a greeting. Messages can use {mention}, {user}, {server},
+/// {member_count} and {account_age}.
+#[poise::command(slash_command)]
+pub async fn set(
+    ctx: Ctx<'_>,
+    kind: Kind,
+    #[description = "Supports {mention}, {user}, {server}, {member_count} and {account_age}"]
+    message: String,
+    #[description = "Where to post it, not used for welcome DMs"] channel: Option<GuildChannel>,
+    title: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let kind = GreetingKind::from(kind);
+    if kind != GreetingKind::WelcomeDm && channel.is_none() {
+        return Embed::error(&ctx)
+            .description(format!("A {} needs a channel.", kind_name(kind)))
+            .send(&ctx)
+            .await;
+    }
+    let before = entity::greeting::Entity::find_by_id(kind)
+        .one(&ctx.data().db)
+        .await?;
+    entity::greeting::Entity::insert(entity::greeting::ActiveModel {
+        kind:    Set(kind),
+        channel: Set(channel.as_ref().map(|c| c.id.to_string())),
+        title:   Set(title),
+        message: Set(message.clone()),
+    })
+    .on_conflict(
+        OnConflict::column(entity::greeting::Column::Kind)
+            .update_columns([
+                entity::greeting::Column::Channel,
+                entity::greeting::Column::Title,
+                entity::greeting::Column::Message,
+            ])
+            .to_owned(),
+    )
+    .exec(&ctx.data().db)
+    .await?;
+    let mut audit = Audit::new(&ctx).after(&message);
+    if let Some(before) = before {
+        audit = audit.before(before.message);
+    }
+    if let Some(ref channel) = channel {
+        audit = audit.target(channel.id);
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Set the {}. Use `/greeting preview` to see how it looks.",
+            kind_name(kind)
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn disable(ctx: Ctx<'_>, kind: Kind) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let kind = GreetingKind::from(kind);
+    let Some(greeting) = entity::greeting::Entity::find_by_id(kind)
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("There is no {} set.", kind_name(kind)))
+            .send(&ctx)
+            .await;
+    };
+    let before = greeting.message.clone();
+    greeting.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).before(before).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Disabled the {}.", kind_name(kind)))
+        .send(&ctx)
+        .await
+}
+
+/// Shows a greeting as it would look for you.
+#[poise::command(slash_command)]
+pub async fn preview(ctx: Ctx<'_>, kind: Kind) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let kind = GreetingKind::from(kind);
+    let Some(greeting) = entity::greeting::Entity::find_by_id(kind)
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("There is no {} set.", kind_name(kind)))
+            .send(&ctx)
+            .await;
+    };
+    greeting_embed(ctx.serenity_context(), ctx.data(), &greeting, ctx.author())
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![greeting()]
+}
@@ -0,0 +1,206 @@
+use poise::serenity_prelude::User;
+use sea_orm::{prelude::*, QueryOrder};
+
+use crate::{
+    utils::{
+        audit::Audit,
+        db::{find_active_character, get_member},
+        embed::{not_admin, Embed},
+        inventory::{add_item, remove_item},
+        num::Ths,
+        perms::{admin, is_admin},
+    },
+    Ctx,
+    Data,
+};
+
+async fn active_character(
+    ctx: &Ctx<'_>,
+    user: &User,
+) -> Result<Option<entity::character::Model>, crate::Error> {
+    let character = find_active_character(&ctx.data().db, user.id).await?;
+    if character.is_none() {
+        Embed::error(ctx)
+            .description(format!("{} doesn't have an active character.", user))
+            .send(ctx)
+            .await?;
+    }
+    Ok(character)
+}
+
+#[poise::command(slash_command)]
+pub async fn inventory(ctx: Ctx<'_>, user: Option<User>) -> Result<(), crate::Error> {
+    let mem = get_member(&ctx.data().db, ctx.author().id).await?;
+    let user = match user {
+        Some(ref user) if user.id != ctx.author().id => {
+            if !is_admin(&mem) {
+                return Embed::error(&ctx)
+                    .description("You do not have permission to view other users' inventories.")
+                    .send(&ctx)
+                    .await;
+            }
+            user
+        },
+        _ => ctx.author(),
+    };
+    let Some(character) = active_character(&ctx, user).await? else {
+        return Ok(());
+    };
+    let items = entity::inventory_item::Entity::find()
+        .filter(entity::inventory_item::Column::Character.eq(character.id))
+        .order_by_asc(entity::inventory_item::Column::Name)
+        .all(&ctx.data().db)
+        .await?;
+    Embed::info(&ctx)
+        .author(user)
+        .title(format!("{}'s Inventory", character.name))
+        .description(if items.is_empty() {
+            "Empty.".to_string()
+        } else {
+            items
+                .iter()
+                .map(|item| format!("{} x{}", item.name, item.quantity.ths()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "add-item",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn add_item_command(
+    ctx: Ctx<'_>,
+    user: User,
+    name: String,
+    #[min = 1] quantity: Option<i32>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(character) = active_character(&ctx, &user).await? else {
+        return Ok(());
+    };
+    let quantity = quantity.unwrap_or(1);
+    add_item(&ctx.data().db, character.id, &name, quantity).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .after(format!(
+            "{} x{} on {}",
+            name,
+            quantity.ths(),
+            character.name
+        ))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Added {} x{} to {}'s inventory.",
+            name,
+            quantity.ths(),
+            character.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "remove-item",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn remove_item_command(
+    ctx: Ctx<'_>,
+    user: User,
+    name: String,
+    #[min = 1] quantity: Option<i32>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(character) = active_character(&ctx, &user).await? else {
+        return Ok(());
+    };
+    let quantity = quantity.unwrap_or(1);
+    if !remove_item(&ctx.data().db, character.id, &name, quantity).await? {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} doesn't have {} x{}.",
+                character.name,
+                name,
+                quantity.ths()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(format!(
+            "{} x{} on {}",
+            name,
+            quantity.ths(),
+            character.name
+        ))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Removed {} x{} from {}'s inventory.",
+            name,
+            quantity.ths(),
+            character.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, rename = "give-item")]
+pub async fn give_item(
+    ctx: Ctx<'_>,
+    user: User,
+    name: String,
+    #[min = 1] quantity: Option<i32>,
+) -> Result<(), crate::Error> {
+    let Some(me) = active_character(&ctx, ctx.author()).await? else {
+        return Ok(());
+    };
+    let Some(them) = active_character(&ctx, &user).await? else {
+        return Ok(());
+    };
+    if me.id == them.id {
+        return Embed::error(&ctx)
+            .description("You cannot give items to yourself.")
+            .send(&ctx)
+            .await;
+    }
+    let quantity = quantity.unwrap_or(1);
+    if !remove_item(&ctx.data().db, me.id, &name, quantity).await? {
+        return Embed::error(&ctx)
+            .description(format!("You don't have {} x{}.", name, quantity.ths()))
+            .send(&ctx)
+            .await;
+    }
+    add_item(&ctx.data().db, them.id, &name, quantity).await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} gave {} x{} to {}.",
+            me.name,
+            name,
+            quantity.ths(),
+            them.name
+        ))
+        .send_pub(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![
+        inventory(),
+        add_item_command(),
+        remove_item_command(),
+        give_item(),
+    ]
+}
@@ -1,12 +1,41 @@
 mod admin;
+mod audit;
 mod auto_role;
 mod auto_role_group;
+mod automod;
+mod calendar;
 mod character;
 mod economy;
+mod encounter;
+mod escalation;
+mod event;
+mod faction;
+mod galaxy;
+mod greeting;
+mod inventory;
+mod moderation;
+mod reaction_role;
+mod review;
+mod role_panel;
+mod roll;
+mod ship;
+mod stats;
+mod temp_role;
+mod verification;
 mod xp;
 mod xp_channel;
 mod xp_role;
 
+pub use automod::automod;
+pub use calendar::announce_months;
+pub use event::remind_events;
+pub use galaxy::arrive_travellers;
+pub use greeting::{farewell, welcome};
+use poise::serenity_prelude::{ComponentInteraction, Context, ModalInteraction};
+pub use reaction_role::{reaction_add, reaction_remove};
+pub use temp_role::expire_temp_roles;
+pub use verification::{kick_unverified, member_joined};
+
 use crate::{Data, Error};
 
 pub fn commands() -> Vec<poise::Command<Data, Error>> {
@@ -19,5 +48,63 @@ pub fn commands() -> Vec<poise::Command<Data, Error>> {
     commands.extend(auto_role::commands());
     commands.extend(auto_role_group::commands());
     commands.extend(character::commands());
+    commands.extend(audit::commands());
+    commands.extend(inventory::commands());
+    commands.extend(review::commands());
+    commands.extend(faction::commands());
+    commands.extend(role_panel::commands());
+    commands.extend(reaction_role::commands());
+    commands.extend(temp_role::commands());
+    commands.extend(verification::commands());
+    commands.extend(greeting::commands());
+    commands.extend(moderation::commands());
+    commands.extend(escalation::commands());
+    commands.extend(automod::commands());
+    commands.extend(roll::commands());
+    commands.extend(stats::commands());
+    commands.extend(encounter::commands());
+    commands.extend(galaxy::commands());
+    commands.extend(ship::commands());
+    commands.extend(calendar::commands());
+    commands.extend(event::commands());
     commands
 }
+
+/// Routes a message component interaction to the module that created it, by
+/// the prefix of its custom id.
+pub async fn component(
+    ctx: &Context,
+    data: &Data,
+    interaction: &ComponentInteraction,
+) -> Result<(), Error> {
+    let Some((prefix, args)) = interaction.data.custom_id.split_once(':') else {
+        return Ok(());
+    };
+    match prefix {
+        auto_role::REQUEST_PREFIX => auto_role::request_button(ctx, data, interaction, args).await,
+        role_panel::PANEL_PREFIX => role_panel::panel_component(ctx, data, interaction, args).await,
+        event::EVENT_PREFIX => event::rsvp_button(ctx, data, interaction, args).await,
+        verification::VERIFY_PREFIX => {
+            verification::verification_component(ctx, data, interaction, args).await
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Routes a modal submission to the module that created it, by the prefix of
+/// its custom id.
+pub async fn modal(
+    ctx: &Context,
+    data: &Data,
+    interaction: &ModalInteraction,
+) -> Result<(), Error> {
+    let Some((prefix, _args)) = interaction.data.custom_id.split_once(':') else {
+        return Ok(());
+    };
+    match prefix {
+        verification::VERIFY_PREFIX => {
+            verification::verification_modal(ctx, data, interaction).await
+        },
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,417 @@
+use entity::mod_case::ModAction;
+use poise::serenity_prelude::{Context, CreateMessage, EditMember, Mention, Message, User, UserId};
+use sea_orm::{prelude::*, QueryOrder, Set};
+
+use crate::{
+    commands::escalation::escalate,
+    utils::{
+        audit::Audit,
+        duration::{format_duration, parse_duration},
+        embed::{not_admin, Embed, EmbedStyle},
+        perms::{admin, is_admin, is_owner},
+    },
+    Ctx,
+    Data,
+};
+
+/// Discord doesn't allow timeouts longer than 28 days.
+const MAX_TIMEOUT_DAYS: i64 = 28;
+
+/// A moderation action about to be taken against a member.
+pub struct Sanction {
+    pub action:   ModAction,
+    pub reason:   String,
+    pub duration: Option<chrono::Duration>,
+    pub evidence: Option<String>,
+}
+
+pub fn action_name(action: ModAction) -> &'static str {
+    match action {
+        ModAction::Warn => "Warning",
+        ModAction::Timeout => "Timeout",
+        ModAction::Kick => "Kick",
+        ModAction::Ban => "Ban",
+        ModAction::Unban => "Unban",
+    }
+}
+
+fn past_tense(action: ModAction) -> &'static str {
+    match action {
+        ModAction::Warn => "warned",
+        ModAction::Timeout => "timed out",
+        ModAction::Kick => "kicked",
+        ModAction::Ban => "banned",
+        ModAction::Unban => "unbanned",
+    }
+}
+
+fn action_style(action: ModAction) -> EmbedStyle {
+    match action {
+        ModAction::Warn | ModAction::Timeout => EmbedStyle::Warning,
+        ModAction::Kick | ModAction::Ban => EmbedStyle::Error,
+        ModAction::Unban => EmbedStyle::Success,
+    }
+}
+
+/// The embed posted to the mod log for a case.
+fn case_embed(case: &entity::mod_case::Model) -> Embed {
+    let mut embed = Embed::plain(action_style(case.action))
+        .title(format!("Case #{} - {}", case.id, action_name(case.action)))
+        .field(
+            "Member",
+            Mention::from(case.member.parse::<UserId>().unwrap()).to_string(),
+            true,
+        )
+        .field(
+            "Moderator",
+            Mention::from(case.moderator.parse::<UserId>().unwrap()).to_string(),
+            true,
+        )
+        .field("Reason", &case.reason, false)
+        .timestamp(case.created_at);
+    if let Some(duration) = case.duration {
+        embed = embed.field(
+            "Duration",
+            format_duration(chrono::Duration::seconds(duration)),
+            true,
+        );
+    }
+    if let Some(ref evidence) = case.evidence {
+        embed = embed.field("Evidence", evidence, false);
+    }
+    embed
+}
+
+/// Tells a member about an action taken against them, returning the message
+/// sent. Members with DMs closed simply aren't told.
+async fn notify(ctx: &Context, data: &Data, member: &User, sanction: &Sanction) -> Option<Message> {
+    let server = ctx
+        .cache
+        .guild(data.primary_guild_id)
+        .map(|g| g.name.clone())
+        .unwrap_or_else(|| "the server".to_string());
+    let mut embed = Embed::plain(action_style(sanction.action))
+        .description(format!(
+            "You were {} in {}.",
+            past_tense(sanction.action),
+            server
+        ))
+        .field("Reason", &sanction.reason, false);
+    if let Some(duration) = sanction.duration {
+        embed = embed.field("Duration", format_duration(duration), true);
+    }
+    let channel = member.create_dm_channel(ctx).await.ok()?;
+    channel
+        .send_message(ctx, CreateMessage::new().embed(embed.build()))
+        .await
+        .ok()
+}
+
+/// Takes an action against a member and records it as a case, posting it to
+/// the mod log. If Discord refuses the action, such as for a member above the
+/// bot, nothing is recorded and the reason is returned instead.
+pub async fn sanction(
+    ctx: &Context,
+    data: &Data,
+    member: &User,
+    moderator: UserId,
+    sanction: Sanction,
+) -> Result<Result<entity::mod_case::Model, Embed>, crate::Error> {
+    let guild = data.primary_guild_id;
+    // members can't be reached once they're out of the server, so they're
+    // told first and the message is taken back if the action is refused
+    let notice = if matches!(sanction.action, ModAction::Kick | ModAction::Ban) {
+        notify(ctx, data, member, &sanction).await
+    } else {
+        None
+    };
+    let result = match sanction.action {
+        ModAction::Warn => Ok(()),
+        ModAction::Timeout => {
+            let until = chrono::Utc::now() + sanction.duration.unwrap_or_default();
+            guild
+                .edit_member(
+                    ctx,
+                    member.id,
+                    EditMember::new()
+                        .disable_communication_until_datetime(until.into())
+                        .audit_log_reason(&sanction.reason),
+                )
+                .await
+                .map(|_| ())
+        },
+        ModAction::Kick => {
+            guild
+                .kick_with_reason(ctx, member.id, &sanction.reason)
+                .await
+        },
+        ModAction::Ban => {
+            guild
+                .ban_with_reason(ctx, member.id, 0, &sanction.reason)
+                .await
+        },
+        ModAction::Unban => guild.unban(ctx, member.id).await,
+    };
+    if let Err(e) = result {
+        if let Some(notice) = notice {
+            let _ = notice.delete(ctx).await;
+        }
+        return Ok(Err(Embed::plain(EmbedStyle::Error).description(format!(
+            "Couldn't {} {}: {}",
+            action_name(sanction.action).to_lowercase(),
+            member,
+            e
+        ))));
+    }
+    if matches!(sanction.action, ModAction::Warn | ModAction::Timeout) {
+        notify(ctx, data, member, &sanction).await;
+    }
+    let case = entity::mod_case::ActiveModel {
+        member: Set(member.id.to_string()),
+        moderator: Set(moderator.to_string()),
+        action: Set(sanction.action),
+        reason: Set(sanction.reason),
+        duration: Set(sanction.duration.map(|d| d.num_seconds())),
+        evidence: Set(sanction.evidence),
+        created_at: Set(chrono::Utc::now()),
+        ..Default::default()
+    }
+    .insert(&data.db)
+    .await?;
+    if let Some(channel) = data.mod_log_channel {
+        // the mirror is best effort, the case is already in the database
+        let _ = channel
+            .send_message(ctx, CreateMessage::new().embed(case_embed(&case).build()))
+            .await;
+    }
+    if case.action == ModAction::Warn {
+        escalate(ctx, data, member).await?;
+    }
+    Ok(Ok(case))
+}
+
+/// Runs a moderation command once its arguments have been checked.
+async fn moderate(ctx: Ctx<'_>, member: User, sanction: Sanction) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    if member.id == ctx.author().id {
+        return Embed::error(&ctx)
+            .description("You can't take action against yourself.")
+            .send(&ctx)
+            .await;
+    }
+    // looked up without get_member so moderating someone doesn't create a row
+    let target = entity::member::Entity::find_by_id(member.id.to_string())
+        .one(&ctx.data().db)
+        .await?;
+    if is_owner(&member.id.to_string()) || target.is_some_and(|t| is_admin(&t)) {
+        return Embed::error(&ctx)
+            .description(format!("{} is an admin.", member))
+            .send(&ctx)
+            .await;
+    }
+    let action = sanction.action;
+    let case = match self::sanction(
+        ctx.serenity_context(),
+        ctx.data(),
+        &member,
+        ctx.author().id,
+        sanction,
+    )
+    .await?
+    {
+        Ok(case) => case,
+        Err(embed) => return embed.author(ctx.author()).send(&ctx).await,
+    };
+    Audit::new(&ctx)
+        .target(member.id)
+        .after(format!("Case #{}: {}", case.id, case.reason))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} {} (case #{}).",
+            member,
+            past_tense(action),
+            case.id
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn warn(
+    ctx: Ctx<'_>,
+    user: User,
+    #[max_length = 1000] reason: String,
+    #[description = "Links to messages or screenshots"]
+    #[max_length = 1000]
+    evidence: Option<String>,
+) -> Result<(), crate::Error> {
+    moderate(ctx, user, Sanction {
+        action: ModAction::Warn,
+        reason,
+        duration: None,
+        evidence,
+    })
+    .await
+}
+
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn timeout(
+    ctx: Ctx<'_>,
+    user: User,
+    #[description = "How long to time out for, e.g. 1h or 3d"] duration: String,
+    #[max_length = 1000] reason: String,
+    #[description = "Links to messages or screenshots"]
+    #[max_length = 1000]
+    evidence: Option<String>,
+) -> Result<(), crate::Error> {
+    let Some(length) = parse_duration(&duration) else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is not a valid duration, use something like `10m`, `1h` or `3d`.",
+                duration
+            ))
+            .send(&ctx)
+            .await;
+    };
+    if length > chrono::Duration::days(MAX_TIMEOUT_DAYS) {
+        return Embed::error(&ctx)
+            .description(format!(
+                "Timeouts can't be longer than {} days.",
+                MAX_TIMEOUT_DAYS
+            ))
+            .send(&ctx)
+            .await;
+    }
+    moderate(ctx, user, Sanction {
+        action: ModAction::Timeout,
+        reason,
+        duration: Some(length),
+        evidence,
+    })
+    .await
+}
+
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn kick(
+    ctx: Ctx<'_>,
+    user: User,
+    #[max_length = 1000] reason: String,
+    #[description = "Links to messages or screenshots"]
+    #[max_length = 1000]
+    evidence: Option<String>,
+) -> Result<(), crate::Error> {
+    moderate(ctx, user, Sanction {
+        action: ModAction::Kick,
+        reason,
+        duration: None,
+        evidence,
+    })
+    .await
+}
+
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn ban(
+    ctx: Ctx<'_>,
+    user: User,
+    #[max_length = 1000] reason: String,
+    #[description = "Links to messages or screenshots"]
+    #[max_length = 1000]
+    evidence: Option<String>,
+) -> Result<(), crate::Error> {
+    moderate(ctx, user, Sanction {
+        action: ModAction::Ban,
+        reason,
+        duration: None,
+        evidence,
+    })
+    .await
+}
+
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn unban(
+    ctx: Ctx<'_>,
+    user: User,
+    #[max_length = 1000] reason: String,
+) -> Result<(), crate::Error> {
+    moderate(ctx, user, Sanction {
+        action: ModAction::Unban,
+        reason,
+        duration: None,
+        evidence: None,
+    })
+    .await
+}
+
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn cases(ctx: Ctx<'_>, user: User) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let cases = entity::mod_case::Entity::find()
+        .filter(entity::mod_case::Column::Member.eq(user.id.to_string()))
+        .order_by_desc(entity::mod_case::Column::Id)
+        .all(&ctx.data().db)
+        .await?;
+    let expired_before = chrono::Utc::now() - ctx.data().warning_expiry;
+    if cases.is_empty() {
+        return Embed::info(&ctx)
+            .description(format!("{} has no cases.", user))
+            .send(&ctx)
+            .await;
+    }
+    // embed descriptions stop at 4096 characters, the footer still counts
+    // every case
+    let mut description = String::new();
+    let lines = cases.iter().take(20).map(|c| {
+        format!(
+            "**#{}** {}{}{} <t:{}:d> by {} - {}{}",
+            c.id,
+            action_name(c.action),
+            if c.action == ModAction::Warn && c.created_at <= expired_before {
+                " (expired)"
+            } else {
+                ""
+            },
+            c.duration
+                .map(|d| format!(" ({})", format_duration(chrono::Duration::seconds(d))))
+                .unwrap_or_default(),
+            c.created_at.timestamp(),
+            Mention::from(c.moderator.parse::<UserId>().unwrap()),
+            c.reason,
+            c.evidence
+                .as_ref()
+                .map(|e| format!("\nEvidence: {}", e))
+                .unwrap_or_default()
+        )
+    });
+    for line in lines {
+        if description.chars().count() + line.chars().count() + 1 > 4096 {
+            break;
+        }
+        if !description.is_empty() {
+            description.push('\n');
+        }
+        description.push_str(&line);
+    }
+    Embed::info(&ctx)
+        .title(format!("Cases for {}", user.name))
+        .description(description)
+        .footer(
+            format!(
+                "{} case{} in total",
+                cases.len(),
+                if cases.len() == 1 { "" } else { "s" }
+            ),
+            None::<String>,
+        )
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![warn(), timeout(), kick(), ban(), unban(), cases()]
+}
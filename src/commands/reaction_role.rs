@@ -0,0 +1,333 @@
+use poise::serenity_prelude::{
+    ChannelId,
+    Context,
+    CreateMessage,
+    Mention,
+    Message,
+    MessageId,
+    Reaction,
+    ReactionType,
+    Role,
+    RoleId,
+};
+use sea_orm::{prelude::*, QueryOrder, Set};
+
+use crate::{
+    commands::auto_role::{drop_auto_role, take_auto_role},
+    utils::{
+        audit::Audit,
+        embed::{not_admin, Embed},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// Whether two reactions are the same emoji. Custom emojis are compared by id
+/// since their names can change.
+fn same_emoji(a: &ReactionType, b: &ReactionType) -> bool {
+    match (a, b) {
+        (ReactionType::Custom { id: a, .. }, ReactionType::Custom { id: b, .. }) => a == b,
+        (ReactionType::Unicode(a), ReactionType::Unicode(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// The reaction roles bound on a message, with their parsed emoji.
+async fn bindings(
+    data: &Data,
+    message: MessageId,
+) -> Result<Vec<(ReactionType, entity::reaction_role::Model)>, crate::Error> {
+    Ok(entity::reaction_role::Entity::find()
+        .filter(entity::reaction_role::Column::Message.eq(message.to_string()))
+        .order_by_asc(entity::reaction_role::Column::Id)
+        .all(&data.db)
+        .await?
+        .into_iter()
+        .filter_map(|binding| {
+            ReactionType::try_from(binding.emoji.as_str())
+                .ok()
+                .map(|emoji| (emoji, binding))
+        })
+        .collect())
+}
+
+/// Gives the role bound to a reaction, taking back the user's other reactions
+/// on the message for roles it swapped out of an exclusive group. If the role
+/// is refused the reaction is removed and the user is told why by DM.
+pub async fn reaction_add(
+    ctx: &Context,
+    data: &Data,
+    reaction: &Reaction,
+) -> Result<(), crate::Error> {
+    let Some(user) = reaction.user_id else {
+        return Ok(());
+    };
+    if user == ctx.cache.current_user().id {
+        return Ok(());
+    }
+    let bindings = bindings(data, reaction.message_id).await?;
+    let Some((_, binding)) = bindings
+        .iter()
+        .find(|(emoji, _)| same_emoji(emoji, &reaction.emoji))
+    else {
+        return Ok(());
+    };
+    let Some(auto_role) = entity::auto_role::Entity::find_by_id(&binding.role)
+        .one(&data.db)
+        .await?
+    else {
+        return Ok(());
+    };
+    let user = user.to_user(ctx).await?;
+    match take_auto_role(ctx, data, &user, &auto_role).await? {
+        Ok(_) => {
+            // only exclusive groups swap roles out, and only once the role is
+            // actually given rather than requested
+            let Some(ref group) = auto_role.group else {
+                return Ok(());
+            };
+            if auto_role.approval
+                || entity::auto_role_group::Entity::find_by_id(group)
+                    .one(&data.db)
+                    .await?
+                    .is_none_or(|g| g.max_roles != Some(1))
+            {
+                return Ok(());
+            }
+            let group_roles = entity::auto_role::Entity::find()
+                .filter(entity::auto_role::Column::Group.eq(group))
+                .all(&data.db)
+                .await?;
+            for (emoji, other) in &bindings {
+                if other.role != auto_role.id && group_roles.iter().any(|r| r.id == other.role) {
+                    let _ = ctx
+                        .http
+                        .delete_reaction(reaction.channel_id, reaction.message_id, user.id, emoji)
+                        .await;
+                }
+            }
+        },
+        Err(embed) => {
+            let _ = ctx
+                .http
+                .delete_reaction(
+                    reaction.channel_id,
+                    reaction.message_id,
+                    user.id,
+                    &reaction.emoji,
+                )
+                .await;
+            // the member may have DMs closed, there's nowhere else to explain
+            if let Ok(channel) = user.create_dm_channel(ctx).await {
+                let _ = channel
+                    .send_message(ctx, CreateMessage::new().embed(embed.build()))
+                    .await;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Takes away the role bound to a reaction when it's removed.
+pub async fn reaction_remove(
+    ctx: &Context,
+    data: &Data,
+    reaction: &Reaction,
+) -> Result<(), crate::Error> {
+    let Some(user) = reaction.user_id else {
+        return Ok(());
+    };
+    if user == ctx.cache.current_user().id {
+        return Ok(());
+    }
+    let bindings = bindings(data, reaction.message_id).await?;
+    let Some((_, binding)) = bindings
+        .iter()
+        .find(|(emoji, _)| same_emoji(emoji, &reaction.emoji))
+    else {
+        return Ok(());
+    };
+    let Some(auto_role) = entity::auto_role::Entity::find_by_id(&binding.role)
+        .one(&data.db)
+        .await?
+    else {
+        return Ok(());
+    };
+    let user = user.to_user(ctx).await?;
+    // the reaction is already gone, so the member can only be told the role stays
+    if let Err(embed) = drop_auto_role(ctx, data, &user, &auto_role).await? {
+        if let Ok(channel) = user.create_dm_channel(ctx).await {
+            let _ = channel
+                .send_message(ctx, CreateMessage::new().embed(embed.build()))
+                .await;
+        }
+    }
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "reaction-role",
+    subcommands("bind", "unbind", "list"),
+    subcommand_required,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn reaction_role(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+pub async fn bind(
+    ctx: Ctx<'_>,
+    #[description = "Link or ID of the message"] message: Message,
+    emoji: String,
+    role: Role,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Ok(emoji) = ReactionType::try_from(emoji.trim()) else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not an emoji.", emoji))
+            .send(&ctx)
+            .await;
+    };
+    if entity::auto_role::Entity::find_by_id(role.id.to_string())
+        .one(&ctx.data().db)
+        .await?
+        .is_none()
+    {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is not an auto role, add it with `/add-auto-role` first.",
+                role
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let bindings = bindings(ctx.data(), message.id).await?;
+    if let Some((_, existing)) = bindings.iter().find(|(e, _)| same_emoji(e, &emoji)) {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is already bound to {} on that message.",
+                emoji,
+                Mention::from(existing.role.parse::<RoleId>().unwrap())
+            ))
+            .send(&ctx)
+            .await;
+    }
+    // reacting first makes sure the bot can actually use the emoji
+    if message.react(ctx, emoji.clone()).await.is_err() {
+        return Embed::error(&ctx)
+            .description(format!("I can't react with {} on that message.", emoji))
+            .send(&ctx)
+            .await;
+    }
+    entity::reaction_role::ActiveModel {
+        channel: Set(message.channel_id.to_string()),
+        message: Set(message.id.to_string()),
+        emoji: Set(emoji.to_string()),
+        role: Set(role.id.to_string()),
+        ..Default::default()
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    Audit::new(&ctx)
+        .target(role.id)
+        .after(format!("{} on {}", emoji, message.link()))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Reacting with {} on {} now gives {}.",
+            emoji,
+            message.link(),
+            role
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn unbind(
+    ctx: Ctx<'_>,
+    #[description = "Link or ID of the message"] message: Message,
+    emoji: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Ok(emoji) = ReactionType::try_from(emoji.trim()) else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not an emoji.", emoji))
+            .send(&ctx)
+            .await;
+    };
+    let bindings = bindings(ctx.data(), message.id).await?;
+    let Some((bound, binding)) = bindings.into_iter().find(|(e, _)| same_emoji(e, &emoji)) else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not bound on that message.", emoji))
+            .send(&ctx)
+            .await;
+    };
+    let role = binding.role.parse::<RoleId>().unwrap();
+    binding.delete(&ctx.data().db).await?;
+    // the bot's own reaction is only a prompt, members keep theirs
+    let _ = message
+        .channel_id
+        .delete_reaction(ctx, message.id, None, bound)
+        .await;
+    Audit::new(&ctx)
+        .target(role)
+        .before(format!("{} on {}", emoji, message.link()))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Unbound {} on {}.", emoji, message.link()))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn list(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let bindings = entity::reaction_role::Entity::find()
+        .order_by_asc(entity::reaction_role::Column::Message)
+        .order_by_asc(entity::reaction_role::Column::Id)
+        .all(&ctx.data().db)
+        .await?;
+    if bindings.is_empty() {
+        return Embed::info(&ctx)
+            .description("No reaction roles.")
+            .send(&ctx)
+            .await;
+    }
+    Embed::info(&ctx)
+        .title("Reaction Roles")
+        .description(
+            bindings
+                .iter()
+                .map(|b| {
+                    format!(
+                        "{} {} on {}",
+                        b.emoji,
+                        Mention::from(b.role.parse::<RoleId>().unwrap()),
+                        b.message.parse::<MessageId>().unwrap().link(
+                            b.channel.parse::<ChannelId>().unwrap(),
+                            Some(ctx.data().primary_guild_id)
+                        )
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![reaction_role()]
+}
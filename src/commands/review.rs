@@ -0,0 +1,566 @@
+use entity::character::CharacterStatus;
+use poise::serenity_prelude::{
+    ChannelId,
+    ChannelType,
+    CreateMessage,
+    EditThread,
+    GuildChannel,
+    Mention,
+    UserId,
+};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, Set};
+
+use super::character::{
+    retag,
+    status_name,
+    APPROVED_CHARACTER_ROLE,
+    APPROVED_TAG,
+    CHARACTERS_CHANNEL,
+};
+use crate::{
+    utils::{
+        audit::Audit,
+        db::get_member,
+        embed::{not_admin, Embed},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// Resolves the character thread the command was used in along with its
+/// registered character, if any. Replies with an error and returns `None` if
+/// the command wasn't used in a character thread.
+async fn character_thread(
+    ctx: &Ctx<'_>,
+) -> Result<Option<(GuildChannel, Option<entity::character::Model>)>, crate::Error> {
+    let thread = match ctx.guild_channel().await {
+        Some(thread)
+            if matches!(
+                thread.kind,
+                ChannelType::PublicThread | ChannelType::PrivateThread
+            ) =>
+        {
+            thread
+        },
+        _ => {
+            Embed::error(ctx)
+                .description("This command must be used in a thread.")
+                .send(ctx)
+                .await?;
+            return Ok(None);
+        },
+    };
+    if thread.parent_id != Some(CHARACTERS_CHANNEL) {
+        Embed::error(ctx)
+            .description(format!(
+                "This command must be used in {}.",
+                Mention::from(CHARACTERS_CHANNEL)
+            ))
+            .send(ctx)
+            .await?;
+        return Ok(None);
+    }
+    let character = entity::character::Entity::find()
+        .filter(entity::character::Column::ThreadId.eq(thread.id.to_string()))
+        .one(&ctx.data().db)
+        .await?;
+    Ok(Some((thread, character)))
+}
+
+/// Like [`character_thread`], but also requires the thread to belong to a
+/// registered character.
+async fn registered_character_thread(
+    ctx: &Ctx<'_>,
+) -> Result<Option<(GuildChannel, entity::character::Model)>, crate::Error> {
+    match character_thread(ctx).await? {
+        Some((thread, Some(character))) => Ok(Some((thread, character))),
+        Some((_, None)) => {
+            Embed::error(ctx)
+                .description("This thread doesn't belong to a registered character.")
+                .send(ctx)
+                .await?;
+            Ok(None)
+        },
+        None => Ok(None),
+    }
+}
+
+/// Whether review can move a character from `from` to `to`. Only characters
+/// still awaiting a decision can move, everything else is final as far as
+/// review is concerned.
+fn can_transition(from: CharacterStatus, to: CharacterStatus) -> bool {
+    use CharacterStatus::*;
+    from != to
+        && matches!(from, Submitted | InReview | ChangesRequested)
+        && matches!(
+            to,
+            Submitted | InReview | ChangesRequested | Approved | Rejected
+        )
+}
+
+/// Checks a character can move to `status`, replying with an error if not.
+async fn check_transition(
+    ctx: &Ctx<'_>,
+    character: &entity::character::Model,
+    status: CharacterStatus,
+) -> Result<bool, crate::Error> {
+    if can_transition(character.status, status) {
+        return Ok(true);
+    }
+    Embed::error(ctx)
+        .description(format!(
+            "This character is {}, so it can't be moved to {}.",
+            status_name(character.status).to_lowercase(),
+            status_name(status).to_lowercase()
+        ))
+        .send(ctx)
+        .await?;
+    Ok(false)
+}
+
+/// Moves a character to a new review state, retagging its thread, posting
+/// `message` in it and recording the change in the audit log. Returns `false`
+/// without changing anything if the state machine doesn't allow the move.
+async fn transition(
+    ctx: &Ctx<'_>,
+    thread: &mut GuildChannel,
+    character: entity::character::Model,
+    status: CharacterStatus,
+    reviewer: Option<String>,
+    message: String,
+) -> Result<bool, crate::Error> {
+    if !check_transition(ctx, &character, status).await? {
+        return Ok(false);
+    }
+    let before = character.status;
+    let owner = character.owner.parse::<UserId>().unwrap();
+    let tags = retag(ctx, thread, status).await?;
+    let locked = matches!(
+        status,
+        CharacterStatus::Approved | CharacterStatus::Rejected
+    );
+    thread
+        .edit_thread(
+            ctx.http(),
+            EditThread::new().applied_tags(tags).locked(locked),
+        )
+        .await?;
+    let mut character = character.into_active_model();
+    character.status = Set(status);
+    character.reviewer = Set(reviewer);
+    character.save(&ctx.data().db).await?;
+    Audit::new(ctx)
+        .target(owner)
+        .before(status_name(before))
+        .after(status_name(status))
+        .log()
+        .await?;
+    thread
+        .send_message(ctx.http(), CreateMessage::new().content(message))
+        .await?;
+    Ok(true)
+}
+
+#[poise::command(slash_command)]
+pub async fn approve(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    ctx.defer_ephemeral().await?;
+    let Some((mut thread, character)) = character_thread(&ctx).await? else {
+        return Ok(());
+    };
+    let already_approved = match character {
+        Some(ref character) => character.status == CharacterStatus::Approved,
+        None => thread.applied_tags.contains(&APPROVED_TAG),
+    };
+    if already_approved {
+        return Embed::error(&ctx)
+            .description("This character is already approved.")
+            .send(&ctx)
+            .await;
+    }
+    if let Some(ref character) = character {
+        if !check_transition(&ctx, character, CharacterStatus::Approved).await? {
+            return Ok(());
+        }
+    }
+    // threads created through registration are owned by the bot, so the
+    // character's owner takes precedence over the thread's
+    let owner = match character {
+        Some(ref character) => character.owner.parse::<UserId>().unwrap(),
+        None => thread.owner_id.unwrap(),
+    };
+    ctx.http()
+        .add_member_role(
+            ctx.data().primary_guild_id,
+            owner,
+            APPROVED_CHARACTER_ROLE,
+            Some("Character approved."),
+        )
+        .await?;
+    let message = format!(
+        "Congratulations, {}! Your character has been approved. You can select your faction by \
+         using the `/role` command.",
+        Mention::from(owner)
+    );
+    match character {
+        Some(character) => {
+            let member = get_member(&ctx.data().db, owner).await?;
+            // a first character takes over the member's wallet, since balance
+            // commands only look at the active character from now on
+            let character = if member.active_character.is_none() {
+                let balance = member.balance;
+                let mut member = member.into_active_model();
+                member.active_character = Set(Some(character.id));
+                member.balance = Set(0);
+                member.save(&ctx.data().db).await?;
+                let total = character.balance + balance;
+                let mut character = character.into_active_model();
+                character.balance = Set(total);
+                character.update(&ctx.data().db).await?
+            } else {
+                character
+            };
+            let reviewer = character.reviewer.clone();
+            if !transition(
+                &ctx,
+                &mut thread,
+                character,
+                CharacterStatus::Approved,
+                reviewer,
+                message,
+            )
+            .await?
+            {
+                return Ok(());
+            }
+        },
+        None => {
+            let mut tags = thread.applied_tags.clone();
+            tags.push(APPROVED_TAG);
+            thread
+                .edit_thread(
+                    ctx.http(),
+                    EditThread::new().applied_tags(tags).locked(true),
+                )
+                .await?;
+            Audit::new(&ctx)
+                .target(owner)
+                .before("Pending character")
+                .after("Approved character")
+                .log()
+                .await?;
+            thread
+                .send_message(ctx.http(), CreateMessage::new().content(message))
+                .await?;
+        },
+    }
+    Embed::success(&ctx)
+        .description(format!("Character approved by {}.", ctx.author()))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn claim(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    ctx.defer_ephemeral().await?;
+    let Some((mut thread, character)) = registered_character_thread(&ctx).await? else {
+        return Ok(());
+    };
+    if let (CharacterStatus::InReview, Some(reviewer)) = (character.status, &character.reviewer) {
+        return Embed::error(&ctx)
+            .description(format!(
+                "This character has already been claimed by {}.",
+                Mention::from(reviewer.parse::<UserId>().unwrap())
+            ))
+            .send(&ctx)
+            .await;
+    }
+    if character.status != CharacterStatus::Submitted {
+        return Embed::error(&ctx)
+            .description(format!(
+                "Only submitted characters can be claimed, this one is {}.",
+                status_name(character.status).to_lowercase()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    if !transition(
+        &ctx,
+        &mut thread,
+        character,
+        CharacterStatus::InReview,
+        Some(ctx.author().id.to_string()),
+        format!("{} is now reviewing this character.", ctx.author()),
+    )
+    .await?
+    {
+        return Ok(());
+    }
+    Embed::success(&ctx)
+        .description("You have claimed this character for review.")
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn unclaim(
+    ctx: Ctx<'_>,
+    #[description = "Release another reviewer's claim"] force: Option<bool>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    ctx.defer_ephemeral().await?;
+    let Some((mut thread, character)) = registered_character_thread(&ctx).await? else {
+        return Ok(());
+    };
+    if character.status != CharacterStatus::InReview {
+        return Embed::error(&ctx)
+            .description("This character is not being reviewed.")
+            .send(&ctx)
+            .await;
+    }
+    // someone else's claim is only released on purpose, and that's recorded
+    let claimer = character
+        .reviewer
+        .as_ref()
+        .map(|reviewer| reviewer.parse::<UserId>().unwrap())
+        .filter(|reviewer| *reviewer != ctx.author().id);
+    if let Some(claimer) = claimer {
+        if !force.unwrap_or(false) {
+            return Embed::error(&ctx)
+                .description(format!(
+                    "This character was claimed by {}, set `force` to release their claim anyway.",
+                    Mention::from(claimer)
+                ))
+                .send(&ctx)
+                .await;
+        }
+    }
+    let name = character.name.clone();
+    if !transition(
+        &ctx,
+        &mut thread,
+        character,
+        CharacterStatus::Submitted,
+        None,
+        "This character is waiting for a reviewer again.".to_string(),
+    )
+    .await?
+    {
+        return Ok(());
+    }
+    if let Some(claimer) = claimer {
+        Audit::new(&ctx)
+            .target(claimer)
+            .before(format!("Claim on {}", name))
+            .after("Claim released by force")
+            .log()
+            .await?;
+    }
+    Embed::success(&ctx)
+        .description("You have released this character back to the review queue.")
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, rename = "request-changes")]
+pub async fn request_changes(ctx: Ctx<'_>, changes: String) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    ctx.defer_ephemeral().await?;
+    let Some((mut thread, character)) = registered_character_thread(&ctx).await? else {
+        return Ok(());
+    };
+    if !matches!(
+        character.status,
+        CharacterStatus::Submitted | CharacterStatus::InReview
+    ) {
+        return Embed::error(&ctx)
+            .description(format!(
+                "Changes can only be requested on characters awaiting review, this one is {}.",
+                status_name(character.status).to_lowercase()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let owner = Mention::from(character.owner.parse::<UserId>().unwrap());
+    if !transition(
+        &ctx,
+        &mut thread,
+        character,
+        CharacterStatus::ChangesRequested,
+        Some(ctx.author().id.to_string()),
+        format!(
+            "{}, {} has requested changes to your character:\n\n{}\n\nUse `/resubmit` in this \
+             thread once you've made them.",
+            owner,
+            ctx.author(),
+            changes
+        ),
+    )
+    .await?
+    {
+        return Ok(());
+    }
+    Embed::success(&ctx)
+        .description("Changes requested.")
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn reject(ctx: Ctx<'_>, reason: String) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    ctx.defer_ephemeral().await?;
+    let Some((mut thread, character)) = registered_character_thread(&ctx).await? else {
+        return Ok(());
+    };
+    let owner = Mention::from(character.owner.parse::<UserId>().unwrap());
+    if !transition(
+        &ctx,
+        &mut thread,
+        character,
+        CharacterStatus::Rejected,
+        Some(ctx.author().id.to_string()),
+        format!(
+            "{}, your character has been rejected by {}:\n\n{}",
+            owner,
+            ctx.author(),
+            reason
+        ),
+    )
+    .await?
+    {
+        return Ok(());
+    }
+    Embed::success(&ctx)
+        .description(format!("Character rejected by {}.", ctx.author()))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn resubmit(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    ctx.defer_ephemeral().await?;
+    let Some((mut thread, character)) = registered_character_thread(&ctx).await? else {
+        return Ok(());
+    };
+    if character.owner != ctx.author().id.to_string() {
+        return Embed::error(&ctx)
+            .description("Only the owner of this character can resubmit it.")
+            .send(&ctx)
+            .await;
+    }
+    if character.status != CharacterStatus::ChangesRequested {
+        return Embed::error(&ctx)
+            .description("No changes have been requested on this character.")
+            .send(&ctx)
+            .await;
+    }
+    // go straight back to whoever requested the changes
+    let (status, message) = match character.reviewer {
+        Some(ref reviewer) => {
+            (
+                CharacterStatus::InReview,
+                format!(
+                    "{}, {} has made the requested changes.",
+                    Mention::from(reviewer.parse::<UserId>().unwrap()),
+                    ctx.author()
+                ),
+            )
+        },
+        None => {
+            (
+                CharacterStatus::Submitted,
+                format!("{} has resubmitted this character.", ctx.author()),
+            )
+        },
+    };
+    let reviewer = character.reviewer.clone();
+    if !transition(&ctx, &mut thread, character, status, reviewer, message).await? {
+        return Ok(());
+    }
+    Embed::success(&ctx)
+        .description("Your character has been resubmitted.")
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "review-queue",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn review_queue(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let characters = entity::character::Entity::find()
+        .filter(
+            entity::character::Column::Status
+                .is_in([CharacterStatus::Submitted, CharacterStatus::InReview]),
+        )
+        .order_by_asc(entity::character::Column::CreatedAt)
+        .all(&ctx.data().db)
+        .await?;
+    if characters.is_empty() {
+        return Embed::info(&ctx)
+            .description("No characters are waiting for review.")
+            .send(&ctx)
+            .await;
+    }
+    Embed::info(&ctx)
+        .title("Review Queue")
+        .description(
+            characters
+                .iter()
+                .map(|character| {
+                    let thread = character
+                        .thread_id
+                        .as_ref()
+                        .map(|t| Mention::from(t.parse::<ChannelId>().unwrap()).to_string())
+                        .unwrap_or_else(|| character.name.clone());
+                    let reviewer = match character.reviewer {
+                        Some(ref reviewer) if character.status == CharacterStatus::InReview => {
+                            format!(
+                                "claimed by {}",
+                                Mention::from(reviewer.parse::<UserId>().unwrap())
+                            )
+                        },
+                        _ => "unclaimed".to_string(),
+                    };
+                    format!(
+                        "{} - submitted <t:{}:R>, {}",
+                        thread,
+                        character.created_at.timestamp(),
+                        reviewer
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![
+        approve(),
+        claim(),
+        unclaim(),
+        request_changes(),
+        reject(),
+        resubmit(),
+        review_queue(),
+    ]
+}
@@ -0,0 +1,346 @@
+use entity::role_panel::RolePanelStyle;
+use poise::serenity_prelude::{
+    ButtonStyle,
+    ChannelId,
+    ComponentInteraction,
+    ComponentInteractionDataKind,
+    Context,
+    CreateActionRow,
+    CreateButton,
+    CreateInteractionResponseFollowup,
+    CreateMessage,
+    CreateSelectMenu,
+    CreateSelectMenuKind,
+    CreateSelectMenuOption,
+    EditMessage,
+    GuildChannel,
+    MessageId,
+    RoleId,
+};
+use sea_orm::{prelude::*, QueryOrder, Set};
+
+use crate::{
+    commands::auto_role::{drop_auto_role, take_auto_role, take_auto_role_replacing},
+    utils::{
+        audit::Audit,
+        embed::{not_admin, Embed, EmbedStyle},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// Custom id prefix of the buttons and select menus on role panels.
+pub const PANEL_PREFIX: &str = "role-panel";
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum PanelStyle {
+    Buttons,
+    #[name = "Select menu"]
+    Select,
+}
+
+/// Builds the buttons or select menu for the roles in a panel's group.
+async fn panel_components(
+    ctx: &Context,
+    data: &Data,
+    group: &str,
+    style: RolePanelStyle,
+) -> Result<Vec<CreateActionRow>, crate::Error> {
+    let auto_roles = entity::auto_role::Entity::find()
+        .filter(entity::auto_role::Column::Group.eq(group))
+        .order_by_asc(entity::auto_role::Column::Id)
+        .all(&data.db)
+        .await?;
+    if auto_roles.is_empty() {
+        return Ok(vec![]);
+    }
+    let Some(group) = entity::auto_role_group::Entity::find_by_id(group)
+        .one(&data.db)
+        .await?
+    else {
+        return Ok(vec![]);
+    };
+    let roles = data.primary_guild_id.roles(ctx).await?;
+    let count = auto_roles.len().min(25) as u8;
+    let max_values = group
+        .max_roles
+        .map(|max| (max.clamp(1, 25) as u8).min(count))
+        .unwrap_or(count);
+    // discord allows at most 25 buttons or select options on a message
+    let labelled = auto_roles.iter().take(25).map(|auto_role| {
+        let id = auto_role.id.parse::<RoleId>().unwrap();
+        let name = roles
+            .get(&id)
+            .map(|r| r.name.clone())
+            .unwrap_or_else(|| auto_role.id.clone());
+        (id, name)
+    });
+    Ok(match style {
+        RolePanelStyle::Buttons => {
+            labelled
+                .map(|(id, name)| {
+                    CreateButton::new(format!("{}:{}", PANEL_PREFIX, id))
+                        .label(name)
+                        .style(ButtonStyle::Secondary)
+                })
+                .collect::<Vec<_>>()
+                .chunks(5)
+                .map(|row| CreateActionRow::Buttons(row.to_vec()))
+                .collect()
+        },
+        RolePanelStyle::Select => {
+            vec![CreateActionRow::SelectMenu(
+                CreateSelectMenu::new(
+                    format!("{}:select", PANEL_PREFIX),
+                    CreateSelectMenuKind::String {
+                        options: labelled
+                            .map(|(id, name)| CreateSelectMenuOption::new(name, id.to_string()))
+                            .collect(),
+                    },
+                )
+                .placeholder("Choose a role")
+                .min_values(if group.required { 1 } else { 0 })
+                .max_values(max_values),
+            )]
+        },
+    })
+}
+
+/// Rebuilds the components of every panel for a group after its roles change,
+/// forgetting panels whose message has been deleted.
+pub async fn refresh_panels(ctx: &Context, data: &Data, group: &str) -> Result<(), crate::Error> {
+    let panels = entity::role_panel::Entity::find()
+        .filter(entity::role_panel::Column::Group.eq(group))
+        .all(&data.db)
+        .await?;
+    for panel in panels {
+        let components = panel_components(ctx, data, group, panel.style).await?;
+        let channel = panel.channel.parse::<ChannelId>().unwrap();
+        let message = panel.id.parse::<MessageId>().unwrap();
+        match channel
+            .edit_message(ctx, message, EditMessage::new().components(components))
+            .await
+        {
+            Err(poise::serenity_prelude::Error::Http(e))
+                if e.status_code().map(|s| s.as_u16()) == Some(404) =>
+            {
+                panel.delete(&data.db).await?;
+            },
+            result => {
+                result?;
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Handles a click on a role panel button or a choice in its select menu,
+/// `args` being the custom id after the prefix.
+pub async fn panel_component(
+    ctx: &Context,
+    data: &Data,
+    interaction: &ComponentInteraction,
+    args: &str,
+) -> Result<(), crate::Error> {
+    interaction.defer_ephemeral(ctx).await?;
+    let held = |role: RoleId| {
+        interaction
+            .member
+            .as_ref()
+            .is_some_and(|m| m.roles.contains(&role))
+    };
+    let result = |result: Result<Embed, Embed>| {
+        let (Ok(embed) | Err(embed)) = result;
+        embed
+    };
+    let mut embeds = vec![];
+    match &interaction.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => {
+            let panel = entity::role_panel::Entity::find_by_id(interaction.message.id.to_string())
+                .one(&data.db)
+                .await?;
+            let Some(panel) = panel else {
+                return Ok(());
+            };
+            let auto_roles = entity::auto_role::Entity::find()
+                .filter(entity::auto_role::Column::Group.eq(&panel.group))
+                .all(&data.db)
+                .await?;
+            // take the new roles first, so a refused one leaves the old
+            // choice in place instead of nothing at all
+            let replacing = auto_roles
+                .iter()
+                .filter(|r| !values.contains(&r.id))
+                .map(|r| r.id.parse::<RoleId>().unwrap())
+                .filter(|r| held(*r))
+                .collect::<Vec<_>>();
+            let mut taken = true;
+            for auto_role in &auto_roles {
+                if held(auto_role.id.parse().unwrap()) || !values.contains(&auto_role.id) {
+                    continue;
+                }
+                let outcome =
+                    take_auto_role_replacing(ctx, data, &interaction.user, auto_role, &replacing)
+                        .await?;
+                taken &= outcome.is_ok();
+                embeds.push(result(outcome));
+            }
+            if taken {
+                for auto_role in &auto_roles {
+                    if replacing.contains(&auto_role.id.parse().unwrap()) {
+                        embeds.push(result(
+                            drop_auto_role(ctx, data, &interaction.user, auto_role).await?,
+                        ));
+                    }
+                }
+            }
+        },
+        _ => {
+            let auto_role = entity::auto_role::Entity::find_by_id(args)
+                .one(&data.db)
+                .await?;
+            embeds.push(match auto_role {
+                Some(auto_role) if held(auto_role.id.parse().unwrap()) => {
+                    result(drop_auto_role(ctx, data, &interaction.user, &auto_role).await?)
+                },
+                Some(auto_role) => {
+                    result(take_auto_role(ctx, data, &interaction.user, &auto_role).await?)
+                },
+                None => {
+                    Embed::plain(EmbedStyle::Error)
+                        .description("That role is no longer an auto role.")
+                },
+            });
+        },
+    };
+    if embeds.is_empty() {
+        embeds.push(Embed::plain(EmbedStyle::Info).description("Your roles are up to date."));
+    }
+    interaction
+        .create_followup(
+            ctx,
+            CreateInteractionResponseFollowup::new()
+                .embeds(embeds.into_iter().take(10).map(Embed::build).collect())
+                .ephemeral(true),
+        )
+        .await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    rename = "role-panel",
+    subcommands("create", "remove"),
+    subcommand_required,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn role_panel(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+#[poise::command(slash_command)]
+pub async fn create(
+    ctx: Ctx<'_>,
+    group: String,
+    style: PanelStyle,
+    channel: Option<GuildChannel>,
+    title: Option<String>,
+    description: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    if entity::auto_role_group::Entity::find_by_id(&group)
+        .one(&ctx.data().db)
+        .await?
+        .is_none()
+    {
+        return Embed::error(&ctx)
+            .description(format!("Auto role group {} does not exist.", group))
+            .send(&ctx)
+            .await;
+    }
+    let style = match style {
+        PanelStyle::Buttons => RolePanelStyle::Buttons,
+        PanelStyle::Select => RolePanelStyle::Select,
+    };
+    let channel = channel.map(|c| c.id).unwrap_or(ctx.channel_id());
+    let components = panel_components(ctx.serenity_context(), ctx.data(), &group, style).await?;
+    let message = channel
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(
+                    Embed::plain(EmbedStyle::Info)
+                        .title(title.unwrap_or_else(|| group.clone()))
+                        .description(description.unwrap_or_else(|| {
+                            match style {
+                                RolePanelStyle::Buttons => {
+                                    "Click a button to take or drop a role.".to_string()
+                                },
+                                RolePanelStyle::Select => "Choose a role below.".to_string(),
+                            }
+                        }))
+                        .build(),
+                )
+                .components(components),
+        )
+        .await?;
+    entity::role_panel::ActiveModel {
+        id:      Set(message.id.to_string()),
+        channel: Set(channel.to_string()),
+        group:   Set(group.clone()),
+        style:   Set(style),
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    Audit::new(&ctx)
+        .target(channel)
+        .after(format!("Role panel for {} ({})", group, message.id))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Posted a role panel for {} in {}.", group, channel))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command)]
+pub async fn remove(
+    ctx: Ctx<'_>,
+    #[description = "ID of the panel message"] message: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let panel = entity::role_panel::Entity::find_by_id(&message)
+        .one(&ctx.data().db)
+        .await?;
+    let Some(panel) = panel else {
+        return Embed::error(&ctx)
+            .description(format!("{} is not a role panel.", message))
+            .send(&ctx)
+            .await;
+    };
+    let channel = panel.channel.parse::<ChannelId>().unwrap();
+    // the message may already be gone, the panel is forgotten either way
+    let _ = channel
+        .delete_message(ctx, panel.id.parse::<MessageId>().unwrap())
+        .await;
+    let group = panel.group.clone();
+    panel.delete(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(channel)
+        .before(format!("Role panel for {} ({})", group, message))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Removed the role panel for {}.", group))
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![role_panel()]
+}
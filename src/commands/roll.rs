@@ -0,0 +1,110 @@
+use crate::{
+    commands::stats::{autocomplete_stat, stat_modifier},
+    utils::{
+        db::{get_active_character, get_member},
+        dice::{parse, roll as roll_dice, Expr, Outcome, Term},
+        embed::Embed,
+    },
+    Ctx,
+    Data,
+};
+
+/// Symbols used in the breakdown of narrative rolls.
+const NARRATIVE_LEGEND: &str = "S success · A advantage · ! triumph · F failure · T threat · X \
+                                despair · L light side · D dark side";
+
+/// The breakdown of a roll, cut short to fit in an embed field.
+pub fn capped_breakdown(outcome: &Outcome) -> String {
+    let breakdown = outcome.breakdown();
+    if breakdown.len() > 1024 {
+        return format!("{}…", breakdown.chars().take(1000).collect::<String>());
+    }
+    breakdown
+}
+
+/// Adds the result and breakdown of a roll to an embed.
+pub fn with_outcome(embed: Embed, outcome: &Outcome) -> Embed {
+    let breakdown = capped_breakdown(outcome);
+    let embed = embed.field("Result", format!("**{}**", outcome.summary()), true);
+    match outcome {
+        Outcome::Standard { .. } => embed.field("Breakdown", breakdown, false),
+        Outcome::Narrative { .. } => {
+            embed
+                .field("Breakdown", breakdown, false)
+                .footer(NARRATIVE_LEGEND, None::<String>)
+        },
+    }
+}
+
+/// Rolls dice, e.g. 2d20kh1+5, 4d6!, 4d6r1, 5d10>=8 or narrative pools like
+/// 2g1y2p.
+#[poise::command(slash_command)]
+pub async fn roll(
+    ctx: Ctx<'_>,
+    #[description = "Dice to roll, e.g. 2d20kh1+5, 4d6!, 5d10>=8 or 2g1y2p"] dice: String,
+    #[description = "Add one of your character's stats to the roll"]
+    #[autocomplete = "autocomplete_stat"]
+    stat: Option<String>,
+    #[description = "What the roll is for"] reason: Option<String>,
+    #[description = "Only show the roll to you"] private: Option<bool>,
+) -> Result<(), crate::Error> {
+    let mut expr = match parse(&dice) {
+        Ok(expr) => expr,
+        Err(e) => {
+            return Embed::error(&ctx)
+                .description(format!("`{}` is not a valid roll. {}", dice, e))
+                .send(&ctx)
+                .await;
+        },
+    };
+    let member = get_member(&ctx.data().db, ctx.author().id).await?;
+    let character = get_active_character(&ctx.data().db, &member).await?;
+    let mut rolling = dice.trim().to_string();
+    if let Some(stat) = stat {
+        let Some(ref character) = character else {
+            return Embed::error(&ctx)
+                .description("You need an active character to roll with a stat.")
+                .send(&ctx)
+                .await;
+        };
+        let Some((stat, modifier)) = stat_modifier(&ctx.data().db, character.id, &stat).await?
+        else {
+            return Embed::error(&ctx)
+                .description(format!(
+                    "{} doesn't have a stat named {}.",
+                    character.name, stat
+                ))
+                .send(&ctx)
+                .await;
+        };
+        let Expr::Standard(ref mut terms) = expr else {
+            return Embed::error(&ctx)
+                .description("Stats can't be added to narrative dice pools.")
+                .send(&ctx)
+                .await;
+        };
+        let sign = if modifier < 0 { -1 } else { 1 };
+        terms.push((sign, Term::Number(modifier.abs() as i64)));
+        rolling = format!("{} + {}", rolling, stat.name);
+    }
+    let outcome = roll_dice(&expr);
+    // rolls are made in character when the member is playing one
+    let title = match character {
+        Some(character) => format!("{} rolls {}", character.name, rolling),
+        None => format!("Rolling {}", rolling),
+    };
+    let mut embed = Embed::info(&ctx).title(title);
+    if let Some(reason) = reason {
+        embed = embed.description(reason);
+    }
+    let embed = with_outcome(embed, &outcome);
+    if private.unwrap_or(false) {
+        embed.send(&ctx).await
+    } else {
+        embed.send_pub(&ctx).await
+    }
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![roll()]
+}
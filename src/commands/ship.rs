@@ -0,0 +1,1118 @@
+use poise::serenity_prelude::{CreateMessage, User, UserId};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, QuerySelect, Set};
+
+use crate::{
+    commands::{
+        faction::{authority, membership},
+        galaxy::{autocomplete_planet, find_planet, journey, sync_location_role},
+    },
+    utils::{
+        audit::Audit,
+        db::{find_active_character, get_active_character, get_member},
+        embed::{not_admin, Embed},
+        inventory::{add_cargo, add_item, remove_cargo, remove_item},
+        num::{money, to_money, Ths},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// When a ship in hyperspace arrives, if it's still on its way.
+fn arrival(ship: &entity::ship::Model) -> Option<DateTimeUtc> {
+    ship.arrives_at.filter(|at| *at > chrono::Utc::now())
+}
+
+fn describe_class(class: &entity::ship_class::Model) -> String {
+    format!(
+        "{}: {}, {} cargo, {} crew, {} hull",
+        class.name,
+        money(class.price),
+        class.cargo_capacity.ths(),
+        class.crew_capacity,
+        class.hull
+    )
+}
+
+async fn active_character(ctx: &Ctx<'_>) -> Result<Option<entity::character::Model>, crate::Error> {
+    let member = get_member(&ctx.data().db, ctx.author().id).await?;
+    let character = get_active_character(&ctx.data().db, &member).await?;
+    if character.is_none() {
+        Embed::error(ctx)
+            .description("You need an active character to do that.")
+            .send(ctx)
+            .await?;
+    }
+    Ok(character)
+}
+
+async fn find_ship(ctx: &Ctx<'_>, name: &str) -> Result<Option<entity::ship::Model>, crate::Error> {
+    let ship = entity::ship::Entity::find()
+        .filter(entity::ship::Column::Name.like(name.trim()))
+        .one(&ctx.data().db)
+        .await?;
+    if ship.is_none() {
+        Embed::error(ctx)
+            .description(format!("There is no ship named {}.", name))
+            .send(ctx)
+            .await?;
+    }
+    Ok(ship)
+}
+
+async fn find_class(
+    ctx: &Ctx<'_>,
+    name: &str,
+) -> Result<Option<entity::ship_class::Model>, crate::Error> {
+    let class = entity::ship_class::Entity::find()
+        .filter(entity::ship_class::Column::Name.like(name.trim()))
+        .one(&ctx.data().db)
+        .await?;
+    if class.is_none() {
+        Embed::error(ctx)
+            .description(format!("There is no ship class named {}.", name))
+            .send(ctx)
+            .await?;
+    }
+    Ok(class)
+}
+
+async fn autocomplete_ship(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    entity::ship::Entity::find()
+        .filter(entity::ship::Column::Name.contains(partial))
+        .order_by_asc(entity::ship::Column::Name)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|ships| ships.into_iter().map(|s| s.name).collect())
+        .unwrap_or_default()
+}
+
+async fn autocomplete_class(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    entity::ship_class::Entity::find()
+        .filter(entity::ship_class::Column::Name.contains(partial))
+        .order_by_asc(entity::ship_class::Column::Price)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|classes| classes.into_iter().map(|c| c.name).collect())
+        .unwrap_or_default()
+}
+
+/// The ship's crew with their characters, invited crew included.
+async fn crew(
+    db: &DatabaseConnection,
+    ship: i32,
+) -> Result<Vec<(entity::ship_crew::Model, entity::character::Model)>, sea_orm::DbErr> {
+    Ok(entity::ship_crew::Entity::find()
+        .filter(entity::ship_crew::Column::Ship.eq(ship))
+        .find_also_related(entity::character::Entity)
+        .order_by_asc(entity::ship_crew::Column::Id)
+        .all(db)
+        .await?
+        .into_iter()
+        .filter_map(|(crew, character)| character.map(|c| (crew, c)))
+        .collect())
+}
+
+/// Everyone who sails on a ship: the character who owns it and the crew who
+/// have joined.
+async fn aboard(
+    db: &DatabaseConnection,
+    ship: &entity::ship::Model,
+) -> Result<Vec<entity::character::Model>, sea_orm::DbErr> {
+    let mut characters = crew(db, ship.id)
+        .await?
+        .into_iter()
+        .filter(|(crew, _)| crew.joined_at.is_some())
+        .map(|(_, c)| c)
+        .collect::<Vec<_>>();
+    if let Some(owner) = ship.owner_character {
+        if let Some(owner) = entity::character::Entity::find_by_id(owner).one(db).await? {
+            characters.insert(0, owner);
+        }
+    }
+    Ok(characters)
+}
+
+/// Whether the author can give orders for a ship: the owner of the character
+/// who owns it, an officer of the faction that owns it, or an admin. Tells
+/// them off when they can't.
+async fn commands_ship(ctx: &Ctx<'_>, ship: &entity::ship::Model) -> Result<bool, crate::Error> {
+    let allowed = if admin(ctx).await? {
+        true
+    } else if let Some(ref faction) = ship.owner_faction {
+        authority(ctx, faction).await?.is_some()
+    } else if let Some(owner) = ship.owner_character {
+        entity::character::Entity::find_by_id(owner)
+            .one(&ctx.data().db)
+            .await?
+            .is_some_and(|c| c.owner == ctx.author().id.to_string())
+    } else {
+        false
+    };
+    if !allowed {
+        Embed::error(ctx)
+            .description(format!("You don't command the {}.", ship.name))
+            .send(ctx)
+            .await?;
+    }
+    Ok(allowed)
+}
+
+/// Checks the author's active character sails on a ship and is on the same
+/// planet as it, as loading cargo or flying it needs.
+async fn crew_member(
+    ctx: &Ctx<'_>,
+    ship: &entity::ship::Model,
+) -> Result<Option<entity::character::Model>, crate::Error> {
+    let Some(character) = active_character(ctx).await? else {
+        return Ok(None);
+    };
+    if !aboard(&ctx.data().db, ship)
+        .await?
+        .iter()
+        .any(|c| c.id == character.id)
+    {
+        Embed::error(ctx)
+            .description(format!(
+                "{} isn't part of the crew of the {}.",
+                character.name, ship.name
+            ))
+            .send(ctx)
+            .await?;
+        return Ok(None);
+    }
+    // landed characters have arrives_at cleared by the scheduler but ships
+    // keep theirs, so only compare journeys that are still under way
+    let travelling = character.arrives_at.filter(|at| *at > chrono::Utc::now());
+    if character.location != ship.location || travelling != arrival(ship) {
+        Embed::error(ctx)
+            .description(format!(
+                "{} isn't where the {} is.",
+                character.name, ship.name
+            ))
+            .send(ctx)
+            .await?;
+        return Ok(None);
+    }
+    Ok(Some(character))
+}
+
+async fn owner_name(
+    db: &DatabaseConnection,
+    ship: &entity::ship::Model,
+) -> Result<String, sea_orm::DbErr> {
+    if let Some(ref faction) = ship.owner_faction {
+        return Ok(faction.clone());
+    }
+    Ok(match ship.owner_character {
+        Some(owner) => {
+            entity::character::Entity::find_by_id(owner)
+                .one(db)
+                .await?
+                .map(|c| c.name)
+                .unwrap_or_default()
+        },
+        None => "Nobody".to_string(),
+    })
+}
+
+#[poise::command(
+    slash_command,
+    subcommands(
+        "buy", "view", "list", "invite", "join", "leave", "dismiss", "travel", "load", "unload",
+        "hull", "transfer", "scrap"
+    ),
+    subcommand_required
+)]
+pub async fn ship(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Buys a ship from the shipyard with your active character's money.
+#[poise::command(slash_command)]
+pub async fn buy(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_class"] class: String,
+    #[max_length = 50] name: String,
+    #[description = "Buy the ship for your faction, which officers can do"] for_faction: Option<
+        bool,
+    >,
+) -> Result<(), crate::Error> {
+    let Some(character) = active_character(&ctx).await? else {
+        return Ok(());
+    };
+    let Some(class) = find_class(&ctx, &class).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let name = name.trim().to_string();
+    if entity::ship::Entity::find()
+        .filter(entity::ship::Column::Name.like(&name))
+        .one(db)
+        .await?
+        .is_some()
+    {
+        return Embed::error(&ctx)
+            .description(format!("There is already a ship named {}.", name))
+            .send(&ctx)
+            .await;
+    }
+    let faction = if for_faction.unwrap_or(false) {
+        match membership(db, character.id).await? {
+            Some((member, rank)) if rank.officer => Some(member.faction),
+            _ => {
+                return Embed::error(&ctx)
+                    .description(format!("{} isn't an officer of a faction.", character.name))
+                    .send(&ctx)
+                    .await;
+            },
+        }
+    } else {
+        None
+    };
+    if character.balance < class.price {
+        return Embed::error(&ctx)
+            .description(format!(
+                "A {} costs {} but {} only has {}.",
+                class.name,
+                money(class.price),
+                character.name,
+                money(character.balance)
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let ship = entity::ship::ActiveModel {
+        name: Set(name),
+        class: Set(class.name.clone()),
+        owner_character: Set(faction.is_none().then_some(character.id)),
+        owner_faction: Set(faction),
+        cargo_capacity: Set(class.cargo_capacity),
+        crew_capacity: Set(class.crew_capacity),
+        hull: Set(class.hull),
+        max_hull: Set(class.hull),
+        // a ship is delivered wherever its buyer is
+        location: Set(character.location.clone()),
+        arrives_at: Set(character.arrives_at),
+        created_at: Set(chrono::Utc::now()),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    let mut buyer = character.clone().into_active_model();
+    buyer.balance = Set(character.balance - class.price);
+    buyer.update(db).await?;
+    Audit::new(&ctx)
+        .target(ctx.author().id)
+        .before(money(character.balance))
+        .after(format!(
+            "{}, bought the {} ({})",
+            money(character.balance - class.price),
+            ship.name,
+            class.name
+        ))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} bought the {}, a {}, for {}.",
+            character.name,
+            ship.name,
+            class.name,
+            money(class.price)
+        ))
+        .send_pub(&ctx)
+        .await
+}
+
+/// Shows a ship's owner, location, hull, crew and cargo.
+#[poise::command(slash_command)]
+pub async fn view(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] name: String,
+) -> Result<(), crate::Error> {
+    let Some(ship) = find_ship(&ctx, &name).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let location = match (&ship.location, arrival(&ship)) {
+        (Some(planet), Some(at)) => {
+            format!("Bound for {}, arriving <t:{}:R>", planet, at.timestamp())
+        },
+        (Some(planet), None) => format!("Docked at {}", planet),
+        (None, _) => "Unknown".to_string(),
+    };
+    let crew = crew(db, ship.id).await?;
+    let cargo = entity::ship_cargo::Entity::find()
+        .filter(entity::ship_cargo::Column::Ship.eq(ship.id))
+        .order_by_asc(entity::ship_cargo::Column::Name)
+        .all(db)
+        .await?;
+    let used = cargo.iter().map(|c| c.quantity).sum::<i32>();
+    Embed::info(&ctx)
+        .title(ship.name.clone())
+        .field("Class", ship.class.clone(), true)
+        .field("Owner", owner_name(db, &ship).await?, true)
+        .field("Hull", format!("{}/{}", ship.hull, ship.max_hull), true)
+        .field("Location", location, false)
+        .field(
+            format!("Crew ({}/{})", crew.len(), ship.crew_capacity),
+            if crew.is_empty() {
+                "None.".to_string()
+            } else {
+                crew.iter()
+                    .map(|(crew, c)| {
+                        if crew.joined_at.is_some() {
+                            c.name.clone()
+                        } else {
+                            format!("{} (invited)", c.name)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            },
+            true,
+        )
+        .field(
+            format!("Cargo ({}/{})", used.ths(), ship.cargo_capacity.ths()),
+            if cargo.is_empty() {
+                "Empty.".to_string()
+            } else {
+                cargo
+                    .iter()
+                    .map(|item| format!("{} x{}", item.name, item.quantity.ths()))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            },
+            true,
+        )
+        .send(&ctx)
+        .await
+}
+
+/// Lists the ships your active character owns or crews.
+#[poise::command(slash_command)]
+pub async fn list(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let Some(character) = active_character(&ctx).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let mut ships = entity::ship::Entity::find()
+        .filter(entity::ship::Column::OwnerCharacter.eq(character.id))
+        .order_by_asc(entity::ship::Column::Name)
+        .all(db)
+        .await?
+        .into_iter()
+        .map(|s| format!("{} ({}), owner", s.name, s.class))
+        .collect::<Vec<_>>();
+    if let Some(ref faction) = character.faction {
+        ships.extend(
+            entity::ship::Entity::find()
+                .filter(entity::ship::Column::OwnerFaction.eq(faction))
+                .order_by_asc(entity::ship::Column::Name)
+                .all(db)
+                .await?
+                .into_iter()
+                .map(|s| format!("{} ({}), {}", s.name, s.class, faction)),
+        );
+    }
+    ships.extend(
+        entity::ship_crew::Entity::find()
+            .filter(entity::ship_crew::Column::Character.eq(character.id))
+            .find_also_related(entity::ship::Entity)
+            .all(db)
+            .await?
+            .into_iter()
+            .filter_map(|(crew, ship)| {
+                ship.map(|s| {
+                    format!(
+                        "{} ({}), {}",
+                        s.name,
+                        s.class,
+                        if crew.joined_at.is_some() {
+                            "crew"
+                        } else {
+                            "invited"
+                        }
+                    )
+                })
+            }),
+    );
+    Embed::info(&ctx)
+        .title(format!("{}'s Ships", character.name))
+        .description(if ships.is_empty() {
+            "None.".to_string()
+        } else {
+            ships.join("\n")
+        })
+        .send(&ctx)
+        .await
+}
+
+/// Invites a member's active character to crew a ship you command.
+#[poise::command(slash_command)]
+pub async fn invite(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+    user: User,
+) -> Result<(), crate::Error> {
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    if !commands_ship(&ctx, &ship).await? {
+        return Ok(());
+    }
+    let db = &ctx.data().db;
+    let Some(character) = find_active_character(db, user.id).await? else {
+        return Embed::error(&ctx)
+            .description(format!("{} doesn't have an active character.", user))
+            .send(&ctx)
+            .await;
+    };
+    let crew = crew(db, ship.id).await?;
+    if ship.owner_character == Some(character.id) || crew.iter().any(|(_, c)| c.id == character.id)
+    {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} is already aboard the {}.",
+                character.name, ship.name
+            ))
+            .send(&ctx)
+            .await;
+    }
+    if crew.len() as i32 >= ship.crew_capacity {
+        return Embed::error(&ctx)
+            .description(format!("The {} has no room for more crew.", ship.name))
+            .send(&ctx)
+            .await;
+    }
+    entity::ship_crew::ActiveModel {
+        ship: Set(ship.id),
+        character: Set(character.id),
+        joined_at: Set(None),
+        ..Default::default()
+    }
+    .insert(db)
+    .await?;
+    if let Ok(channel) = user.create_dm_channel(&ctx).await {
+        let _ = channel
+            .send_message(
+                &ctx,
+                CreateMessage::new().content(format!(
+                    "{} has been invited to crew the {}. Use `/ship join` to accept.",
+                    character.name, ship.name
+                )),
+            )
+            .await;
+    }
+    Embed::success(&ctx)
+        .description(format!(
+            "Invited {} to crew the {}.",
+            character.name, ship.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Accepts an invite to crew a ship as your active character.
+#[poise::command(slash_command)]
+pub async fn join(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+) -> Result<(), crate::Error> {
+    let Some(character) = active_character(&ctx).await? else {
+        return Ok(());
+    };
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let invite = entity::ship_crew::Entity::find()
+        .filter(entity::ship_crew::Column::Ship.eq(ship.id))
+        .filter(entity::ship_crew::Column::Character.eq(character.id))
+        .filter(entity::ship_crew::Column::JoinedAt.is_null())
+        .one(db)
+        .await?;
+    let Some(invite) = invite else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} hasn't been invited to crew the {}.",
+                character.name, ship.name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    let mut invite = invite.into_active_model();
+    invite.joined_at = Set(Some(chrono::Utc::now()));
+    invite.update(db).await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} joined the crew of the {}.",
+            character.name, ship.name
+        ))
+        .send_pub(&ctx)
+        .await
+}
+
+/// Leaves a ship's crew, or turns down an invite to it.
+#[poise::command(slash_command)]
+pub async fn leave(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+) -> Result<(), crate::Error> {
+    let Some(character) = active_character(&ctx).await? else {
+        return Ok(());
+    };
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let removed = entity::ship_crew::Entity::delete_many()
+        .filter(entity::ship_crew::Column::Ship.eq(ship.id))
+        .filter(entity::ship_crew::Column::Character.eq(character.id))
+        .exec(&ctx.data().db)
+        .await?
+        .rows_affected;
+    if removed == 0 {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} isn't part of the crew of the {}.",
+                character.name, ship.name
+            ))
+            .send(&ctx)
+            .await;
+    }
+    Embed::success(&ctx)
+        .description(format!("{} left the {}.", character.name, ship.name))
+        .send(&ctx)
+        .await
+}
+
+/// Removes a character from the crew of a ship you command.
+#[poise::command(slash_command)]
+pub async fn dismiss(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+    #[description = "Name of the crew member"] character: String,
+) -> Result<(), crate::Error> {
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    if !commands_ship(&ctx, &ship).await? {
+        return Ok(());
+    }
+    let db = &ctx.data().db;
+    let Some((crew, member)) = crew(db, ship.id)
+        .await?
+        .into_iter()
+        .find(|(_, c)| c.name.eq_ignore_ascii_case(character.trim()))
+    else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} isn't part of the crew of the {}.",
+                character, ship.name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    crew.delete(db).await?;
+    Audit::new(&ctx)
+        .target(member.owner.parse::<UserId>().unwrap())
+        .before(format!("{} on the {}", member.name, ship.name))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Dismissed {} from the {}.", member.name, ship.name))
+        .send(&ctx)
+        .await
+}
+
+/// Flies a ship and everyone aboard to another planet, paying for the fuel.
+#[poise::command(slash_command)]
+pub async fn travel(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+    #[autocomplete = "autocomplete_planet"] planet: String,
+) -> Result<(), crate::Error> {
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let Some(pilot) = crew_member(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let Some(destination) = find_planet(&ctx, &planet).await? else {
+        return Ok(());
+    };
+    if let Some(at) = arrival(&ship) {
+        return Embed::error(&ctx)
+            .description(format!(
+                "The {} is still in hyperspace, arriving <t:{}:R>.",
+                ship.name,
+                at.timestamp()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    if ship.hull <= 0 {
+        return Embed::error(&ctx)
+            .description(format!("The {} is too damaged to fly.", ship.name))
+            .send(&ctx)
+            .await;
+    }
+    let Some(origin) = ship.location.clone() else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "The {} isn't on the map yet, ask staff to place it.",
+                ship.name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    if origin == destination.name {
+        return Embed::error(&ctx)
+            .description(format!("The {} is already at {}.", ship.name, origin))
+            .send(&ctx)
+            .await;
+    }
+    let db = &ctx.data().db;
+    let Some(journey) = journey(db, &origin, &destination.name).await? else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "There is no route from {} to {}.",
+                origin, destination.name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    if pilot.balance < journey.cost {
+        return Embed::error(&ctx)
+            .description(format!(
+                "Fuel costs {} but {} only has {}.",
+                money(journey.cost),
+                pilot.name,
+                money(pilot.balance)
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let arrives_at = (journey.duration > 0)
+        .then(|| chrono::Utc::now() + chrono::Duration::seconds(journey.duration));
+    // crew elsewhere in the galaxy are left behind
+    let passengers = aboard(db, &ship)
+        .await?
+        .into_iter()
+        .filter(|c| {
+            c.location == ship.location && c.arrives_at.is_none_or(|at| at <= chrono::Utc::now())
+        })
+        .collect::<Vec<_>>();
+    for passenger in &passengers {
+        let mut character = passenger.clone().into_active_model();
+        if passenger.id == pilot.id {
+            character.balance = Set(pilot.balance - journey.cost);
+        }
+        character.location = Set(Some(destination.name.clone()));
+        character.arrives_at = Set(arrives_at);
+        character.update(db).await?;
+        sync_location_role(
+            ctx.serenity_context(),
+            ctx.data(),
+            passenger.owner.parse().unwrap(),
+        )
+        .await?;
+    }
+    let name = ship.name.clone();
+    let mut ship = ship.into_active_model();
+    ship.location = Set(Some(destination.name.clone()));
+    ship.arrives_at = Set(arrives_at);
+    ship.update(db).await?;
+    Embed::info(&ctx)
+        .title(format!("The {} sets off for {}", name, destination.name))
+        .field("Route", journey.stops.join(" → "), false)
+        .field(
+            "Arrival",
+            format!(
+                "<t:{}:R>",
+                arrives_at.unwrap_or_else(chrono::Utc::now).timestamp()
+            ),
+            true,
+        )
+        .field("Fuel", money(journey.cost), true)
+        .field(
+            "Aboard",
+            passengers
+                .iter()
+                .map(|c| c.name.clone())
+                .collect::<Vec<_>>()
+                .join(", "),
+            false,
+        )
+        .send_pub(&ctx)
+        .await
+}
+
+/// Moves items from your active character's inventory into a ship's hold.
+#[poise::command(slash_command)]
+pub async fn load(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+    item: String,
+    #[min = 1] quantity: Option<i32>,
+) -> Result<(), crate::Error> {
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let Some(character) = crew_member(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let quantity = quantity.unwrap_or(1);
+    let Some(item) = entity::inventory_item::Entity::find()
+        .filter(entity::inventory_item::Column::Character.eq(character.id))
+        .filter(entity::inventory_item::Column::Name.like(item.trim()))
+        .one(db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("{} doesn't have any {}.", character.name, item))
+            .send(&ctx)
+            .await;
+    };
+    let used = entity::ship_cargo::Entity::find()
+        .filter(entity::ship_cargo::Column::Ship.eq(ship.id))
+        .all(db)
+        .await?
+        .iter()
+        .map(|c| c.quantity)
+        .sum::<i32>();
+    if used + quantity > ship.cargo_capacity {
+        return Embed::error(&ctx)
+            .description(format!(
+                "The {} only has room for {} more.",
+                ship.name,
+                (ship.cargo_capacity - used).max(0).ths()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    if !remove_item(db, character.id, &item.name, quantity).await? {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} only has {} x{}.",
+                character.name,
+                item.name,
+                item.quantity.ths()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    add_cargo(db, ship.id, &item.name, quantity).await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} loaded {} x{} onto the {}.",
+            character.name,
+            item.name,
+            quantity.ths(),
+            ship.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Moves items from a ship's hold into your active character's inventory.
+#[poise::command(slash_command)]
+pub async fn unload(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+    item: String,
+    #[min = 1] quantity: Option<i32>,
+) -> Result<(), crate::Error> {
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let Some(character) = crew_member(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let quantity = quantity.unwrap_or(1);
+    let Some(cargo) = entity::ship_cargo::Entity::find()
+        .filter(entity::ship_cargo::Column::Ship.eq(ship.id))
+        .filter(entity::ship_cargo::Column::Name.like(item.trim()))
+        .one(db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("There is no {} aboard the {}.", item, ship.name))
+            .send(&ctx)
+            .await;
+    };
+    if !remove_cargo(db, ship.id, &cargo.name, quantity).await? {
+        return Embed::error(&ctx)
+            .description(format!(
+                "The {} only has {} x{}.",
+                ship.name,
+                cargo.name,
+                cargo.quantity.ths()
+            ))
+            .send(&ctx)
+            .await;
+    }
+    add_item(db, character.id, &cargo.name, quantity).await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} unloaded {} x{} from the {}.",
+            character.name,
+            cargo.name,
+            quantity.ths(),
+            ship.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Damages or repairs a ship's hull.
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn hull(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+    #[description = "Negative to damage, positive to repair"] change: i32,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let before = format!("{}/{}", ship.hull, ship.max_hull);
+    let hull = ship.hull.saturating_add(change).clamp(0, ship.max_hull);
+    let mut ship = ship.into_active_model();
+    ship.hull = Set(hull);
+    let ship = ship.update(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .before(format!("{} hull {}", ship.name, before))
+        .after(format!(
+            "{} hull {}/{}",
+            ship.name, ship.hull, ship.max_hull
+        ))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "The {}'s hull is at {}/{}.",
+            ship.name, ship.hull, ship.max_hull
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Gives a ship to a member's active character or to a faction.
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn transfer(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+    user: Option<User>,
+    #[description = "Faction to own the ship instead of a character"] faction: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    let db = &ctx.data().db;
+    let (character, faction) = match (user, faction) {
+        (Some(user), None) => {
+            let Some(character) = find_active_character(db, user.id).await? else {
+                return Embed::error(&ctx)
+                    .description(format!("{} doesn't have an active character.", user))
+                    .send(&ctx)
+                    .await;
+            };
+            (Some(character.id), None)
+        },
+        (None, Some(faction)) => {
+            let Some(faction) = entity::faction::Entity::find()
+                .filter(entity::faction::Column::Name.like(faction.trim()))
+                .one(db)
+                .await?
+            else {
+                return Embed::error(&ctx)
+                    .description(format!("Faction {} does not exist.", faction))
+                    .send(&ctx)
+                    .await;
+            };
+            (None, Some(faction.name))
+        },
+        _ => {
+            return Embed::error(&ctx)
+                .description("Give the ship to either a member or a faction.")
+                .send(&ctx)
+                .await;
+        },
+    };
+    let before = owner_name(db, &ship).await?;
+    let mut ship = ship.into_active_model();
+    ship.owner_character = Set(character);
+    ship.owner_faction = Set(faction);
+    let ship = ship.update(db).await?;
+    // a new owner character can't also be crew
+    if let Some(character) = character {
+        entity::ship_crew::Entity::delete_many()
+            .filter(entity::ship_crew::Column::Ship.eq(ship.id))
+            .filter(entity::ship_crew::Column::Character.eq(character))
+            .exec(db)
+            .await?;
+    }
+    let after = owner_name(db, &ship).await?;
+    Audit::new(&ctx)
+        .before(format!("{} owned by {}", ship.name, before))
+        .after(format!("{} owned by {}", ship.name, after))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("The {} now belongs to {}.", ship.name, after))
+        .send(&ctx)
+        .await
+}
+
+/// Scraps a ship you command, along with its cargo.
+#[poise::command(slash_command)]
+pub async fn scrap(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_ship"] ship: String,
+) -> Result<(), crate::Error> {
+    let Some(ship) = find_ship(&ctx, &ship).await? else {
+        return Ok(());
+    };
+    if !commands_ship(&ctx, &ship).await? {
+        return Ok(());
+    }
+    let name = ship.name.clone();
+    let before = format!("{} ({})", ship.name, ship.class);
+    ship.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).before(before).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Scrapped the {}.", name))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("add_class", "remove_class", "list_classes"),
+    subcommand_required
+)]
+pub async fn shipyard(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Adds a ship class to the shipyard, or replaces its price and stats.
+#[poise::command(
+    slash_command,
+    rename = "add",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn add_class(
+    ctx: Ctx<'_>,
+    #[max_length = 50] name: String,
+    #[min = 0] price: f64,
+    #[description = "How many items the hold fits"]
+    #[min = 0]
+    cargo: i32,
+    #[description = "How many crew it takes besides its owner"]
+    #[min = 0]
+    crew: i32,
+    #[min = 1] hull: i32,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let db = &ctx.data().db;
+    let before = entity::ship_class::Entity::find()
+        .filter(entity::ship_class::Column::Name.like(name.trim()))
+        .one(db)
+        .await?;
+    let class = entity::ship_class::Model {
+        name: before
+            .as_ref()
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| name.trim().to_string()),
+        price: to_money(price),
+        cargo_capacity: cargo,
+        crew_capacity: crew,
+        hull,
+    };
+    match before {
+        Some(_) => {
+            class
+                .clone()
+                .into_active_model()
+                .reset_all()
+                .update(db)
+                .await?
+        },
+        None => class.clone().into_active_model().insert(db).await?,
+    };
+    let mut audit = Audit::new(&ctx).after(describe_class(&class));
+    if let Some(ref before) = before {
+        audit = audit.before(describe_class(before));
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(format!("Set ship class {}.", describe_class(&class)))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "remove",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn remove_class(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_class"] name: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(class) = find_class(&ctx, &name).await? else {
+        return Ok(());
+    };
+    let before = describe_class(&class);
+    class.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).before(&before).log().await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Removed ship class {}. Ships of the class keep flying.",
+            before
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Lists the ship classes for sale.
+#[poise::command(slash_command, rename = "list")]
+pub async fn list_classes(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let classes = entity::ship_class::Entity::find()
+        .order_by_asc(entity::ship_class::Column::Price)
+        .all(&ctx.data().db)
+        .await?;
+    Embed::info(&ctx)
+        .title("Shipyard")
+        .description(if classes.is_empty() {
+            "Nothing for sale.".to_string()
+        } else {
+            classes
+                .iter()
+                .map(describe_class)
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![ship(), shipyard()]
+}
@@ -0,0 +1,538 @@
+use entity::character_stat::StatKind;
+use poise::serenity_prelude::User;
+use sea_orm::{prelude::*, sea_query::OnConflict, IntoActiveModel, QueryOrder, QuerySelect, Set};
+
+use crate::{
+    commands::roll::with_outcome,
+    utils::{
+        audit::Audit,
+        db::{find_active_character, get_member},
+        dice::{parse, roll},
+        embed::{not_admin, Embed},
+        num::{money, to_money, Ths},
+        perms::{admin, is_admin},
+        xp::level_up,
+    },
+    Ctx,
+    Data,
+};
+
+/// The die every check is rolled with, before stats are added.
+const CHECK_DIE: &str = "1d20";
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum StatType {
+    Attribute,
+    Skill,
+}
+
+fn describe_stat(stat: &entity::character_stat::Model) -> String {
+    match stat.attribute {
+        Some(ref attribute) => format!("{} {:+} ({})", stat.name, stat.value, attribute),
+        None => format!("{} {:+}", stat.name, stat.value),
+    }
+}
+
+fn describe_difficulty(difficulty: &entity::difficulty::Model) -> String {
+    let mut rewards = vec![];
+    if difficulty.xp > 0 {
+        rewards.push(format!("{} XP", difficulty.xp.ths()));
+    }
+    if difficulty.credits > 0 {
+        rewards.push(money(difficulty.credits));
+    }
+    format!(
+        "{}: {}+{}",
+        difficulty.name,
+        difficulty.target,
+        if rewards.is_empty() {
+            String::new()
+        } else {
+            format!(", rewards {}", rewards.join(" and "))
+        }
+    )
+}
+
+async fn active_character(
+    ctx: &Ctx<'_>,
+    user: &User,
+) -> Result<Option<entity::character::Model>, crate::Error> {
+    let character = find_active_character(&ctx.data().db, user.id).await?;
+    if character.is_none() {
+        Embed::error(ctx)
+            .description(format!("{} doesn't have an active character.", user))
+            .send(ctx)
+            .await?;
+    }
+    Ok(character)
+}
+
+async fn find_stat(
+    db: &DatabaseConnection,
+    character: i32,
+    name: &str,
+) -> Result<Option<entity::character_stat::Model>, sea_orm::DbErr> {
+    entity::character_stat::Entity::find()
+        .filter(entity::character_stat::Column::Character.eq(character))
+        .filter(entity::character_stat::Column::Name.like(name))
+        .one(db)
+        .await
+}
+
+/// Finds a character's stat and the modifier it gives to rolls: its own value,
+/// plus the attribute it's based on for skills.
+pub async fn stat_modifier(
+    db: &DatabaseConnection,
+    character: i32,
+    name: &str,
+) -> Result<Option<(entity::character_stat::Model, i32)>, sea_orm::DbErr> {
+    let Some(stat) = find_stat(db, character, name).await? else {
+        return Ok(None);
+    };
+    let attribute = match stat.attribute {
+        Some(ref attribute) => {
+            find_stat(db, character, attribute)
+                .await?
+                .map(|a| a.value)
+                .unwrap_or_default()
+        },
+        None => 0,
+    };
+    let modifier = stat.value + attribute;
+    Ok(Some((stat, modifier)))
+}
+
+/// Suggests the stats of the author's active character.
+pub async fn autocomplete_stat(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    let Ok(member) = get_member(&ctx.data().db, ctx.author().id).await else {
+        return vec![];
+    };
+    let Some(character) = member.active_character else {
+        return vec![];
+    };
+    entity::character_stat::Entity::find()
+        .filter(entity::character_stat::Column::Character.eq(character))
+        .filter(entity::character_stat::Column::Name.contains(partial))
+        .order_by_asc(entity::character_stat::Column::Name)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|stats| stats.into_iter().map(|s| s.name).collect())
+        .unwrap_or_default()
+}
+
+async fn autocomplete_difficulty(ctx: Ctx<'_>, partial: &str) -> Vec<String> {
+    entity::difficulty::Entity::find()
+        .filter(entity::difficulty::Column::Name.contains(partial))
+        .order_by_asc(entity::difficulty::Column::Target)
+        .limit(25)
+        .all(&ctx.data().db)
+        .await
+        .map(|difficulties| difficulties.into_iter().map(|d| d.name).collect())
+        .unwrap_or_default()
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("view", "set", "remove"),
+    subcommand_required
+)]
+pub async fn stats(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Shows the attributes and skills of an active character.
+#[poise::command(slash_command)]
+pub async fn view(ctx: Ctx<'_>, user: Option<User>) -> Result<(), crate::Error> {
+    let mem = get_member(&ctx.data().db, ctx.author().id).await?;
+    let user = match user {
+        Some(ref user) if user.id != ctx.author().id => {
+            if !is_admin(&mem) {
+                return Embed::error(&ctx)
+                    .description("You do not have permission to view other users' stats.")
+                    .send(&ctx)
+                    .await;
+            }
+            user
+        },
+        _ => ctx.author(),
+    };
+    let Some(character) = active_character(&ctx, user).await? else {
+        return Ok(());
+    };
+    let stats = entity::character_stat::Entity::find()
+        .filter(entity::character_stat::Column::Character.eq(character.id))
+        .order_by_asc(entity::character_stat::Column::Name)
+        .all(&ctx.data().db)
+        .await?;
+    let list = |kind: StatKind| {
+        let stats = stats
+            .iter()
+            .filter(|s| s.kind == kind)
+            .map(describe_stat)
+            .collect::<Vec<_>>();
+        if stats.is_empty() {
+            "None.".to_string()
+        } else {
+            stats.join("\n")
+        }
+    };
+    Embed::info(&ctx)
+        .author(user)
+        .title(format!("{}'s Stats", character.name))
+        .field("Attributes", list(StatKind::Attribute), true)
+        .field("Skills", list(StatKind::Skill), true)
+        .send(&ctx)
+        .await
+}
+
+/// Sets an attribute or skill on a member's active character.
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn set(
+    ctx: Ctx<'_>,
+    user: User,
+    #[description = "Name of the attribute or skill"]
+    #[max_length = 50]
+    name: String,
+    kind: StatType,
+    #[min = -100]
+    #[max = 100]
+    value: i32,
+    #[description = "Attribute a skill is based on, added to its checks"] attribute: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(character) = active_character(&ctx, &user).await? else {
+        return Ok(());
+    };
+    let kind = match kind {
+        StatType::Attribute => StatKind::Attribute,
+        StatType::Skill => StatKind::Skill,
+    };
+    let name = name.trim().to_string();
+    let attribute = match (kind, attribute) {
+        (StatKind::Skill, Some(attribute)) => {
+            match find_stat(&ctx.data().db, character.id, attribute.trim()).await? {
+                Some(stat) if stat.kind == StatKind::Attribute => Some(stat.name),
+                _ => {
+                    return Embed::error(&ctx)
+                        .description(format!(
+                            "{} doesn't have an attribute named {}.",
+                            character.name, attribute
+                        ))
+                        .send(&ctx)
+                        .await;
+                },
+            }
+        },
+        (StatKind::Attribute, Some(_)) => {
+            return Embed::error(&ctx)
+                .description("Only skills can be based on an attribute.")
+                .send(&ctx)
+                .await;
+        },
+        (_, None) => None,
+    };
+    let before = find_stat(&ctx.data().db, character.id, &name).await?;
+    let stat = match before.clone() {
+        Some(stat) => {
+            let mut stat = stat.into_active_model();
+            stat.kind = Set(kind);
+            stat.value = Set(value);
+            stat.attribute = Set(attribute);
+            stat.update(&ctx.data().db).await?
+        },
+        None => {
+            entity::character_stat::ActiveModel {
+                character: Set(character.id),
+                kind: Set(kind),
+                name: Set(name),
+                value: Set(value),
+                attribute: Set(attribute),
+                ..Default::default()
+            }
+            .insert(&ctx.data().db)
+            .await?
+        },
+    };
+    let mut audit = Audit::new(&ctx).target(user.id).after(format!(
+        "{} on {}",
+        describe_stat(&stat),
+        character.name
+    ));
+    if let Some(ref before) = before {
+        audit = audit.before(format!("{} on {}", describe_stat(before), character.name));
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Set {} on {}.",
+            describe_stat(&stat),
+            character.name
+        ))
+        .send(&ctx)
+        .await
+}
+
+/// Removes an attribute or skill from a member's active character.
+#[poise::command(slash_command, default_member_permissions = "ADMINISTRATOR")]
+pub async fn remove(
+    ctx: Ctx<'_>,
+    user: User,
+    #[description = "Name of the attribute or skill"] name: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(character) = active_character(&ctx, &user).await? else {
+        return Ok(());
+    };
+    let Some(stat) = find_stat(&ctx.data().db, character.id, &name).await? else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} doesn't have a stat named {}.",
+                character.name, name
+            ))
+            .send(&ctx)
+            .await;
+    };
+    let before = format!("{} on {}", describe_stat(&stat), character.name);
+    if stat.kind == StatKind::Attribute {
+        // skills based on a removed attribute just stop getting its bonus
+        entity::character_stat::Entity::update_many()
+            .col_expr(
+                entity::character_stat::Column::Attribute,
+                Expr::value(None::<String>),
+            )
+            .filter(entity::character_stat::Column::Character.eq(character.id))
+            .filter(entity::character_stat::Column::Attribute.eq(&stat.name))
+            .exec(&ctx.data().db)
+            .await?;
+    }
+    stat.delete(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(&before)
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Removed {}.", before))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("set_difficulty", "remove_difficulty", "list_difficulties"),
+    subcommand_required,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn difficulty(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Sets the target roll for a difficulty, and what a success is worth.
+#[poise::command(slash_command, rename = "set")]
+pub async fn set_difficulty(
+    ctx: Ctx<'_>,
+    #[max_length = 50] name: String,
+    #[description = "Total needed to succeed"]
+    #[min = 1]
+    target: i32,
+    #[description = "XP awarded on success"]
+    #[min = 0]
+    xp: Option<i32>,
+    #[description = "Credits awarded on success"]
+    #[min = 0]
+    credits: Option<f64>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let before = entity::difficulty::Entity::find()
+        .filter(entity::difficulty::Column::Name.like(name.trim()))
+        .one(&ctx.data().db)
+        .await?;
+    let difficulty = entity::difficulty::Model {
+        // keep the existing spelling so renaming the case doesn't duplicate it
+        name: before
+            .as_ref()
+            .map(|d| d.name.clone())
+            .unwrap_or_else(|| name.trim().to_string()),
+        target,
+        xp: xp.unwrap_or_default(),
+        credits: credits.map(to_money).unwrap_or_default(),
+    };
+    entity::difficulty::Entity::insert(difficulty.clone().into_active_model())
+        .on_conflict(
+            OnConflict::column(entity::difficulty::Column::Name)
+                .update_columns([
+                    entity::difficulty::Column::Target,
+                    entity::difficulty::Column::Xp,
+                    entity::difficulty::Column::Credits,
+                ])
+                .to_owned(),
+        )
+        .exec(&ctx.data().db)
+        .await?;
+    let mut audit = Audit::new(&ctx).after(describe_difficulty(&difficulty));
+    if let Some(ref before) = before {
+        audit = audit.before(describe_difficulty(before));
+    }
+    audit.log().await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Set difficulty {}.",
+            describe_difficulty(&difficulty)
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, rename = "remove")]
+pub async fn remove_difficulty(
+    ctx: Ctx<'_>,
+    #[autocomplete = "autocomplete_difficulty"] name: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(difficulty) = entity::difficulty::Entity::find()
+        .filter(entity::difficulty::Column::Name.like(&name))
+        .one(&ctx.data().db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("There is no difficulty named {}.", name))
+            .send(&ctx)
+            .await;
+    };
+    let before = describe_difficulty(&difficulty);
+    difficulty.delete(&ctx.data().db).await?;
+    Audit::new(&ctx).before(&before).log().await?;
+    Embed::success(&ctx)
+        .description(format!("Removed difficulty {}.", before))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(slash_command, rename = "list")]
+pub async fn list_difficulties(ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    let difficulties = entity::difficulty::Entity::find()
+        .order_by_asc(entity::difficulty::Column::Target)
+        .all(&ctx.data().db)
+        .await?;
+    Embed::info(&ctx)
+        .title("Difficulties")
+        .description(if difficulties.is_empty() {
+            "No difficulties.".to_string()
+        } else {
+            difficulties
+                .iter()
+                .map(describe_difficulty)
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .send(&ctx)
+        .await
+}
+
+/// Rolls a skill or attribute check for your active character.
+#[poise::command(slash_command)]
+pub async fn check(
+    ctx: Ctx<'_>,
+    #[description = "Skill or attribute to check"]
+    #[autocomplete = "autocomplete_stat"]
+    stat: String,
+    #[autocomplete = "autocomplete_difficulty"] difficulty: String,
+    #[description = "Situational bonus or penalty"]
+    #[min = -100]
+    #[max = 100]
+    bonus: Option<i32>,
+) -> Result<(), crate::Error> {
+    let db = &ctx.data().db;
+    let Some(character) = active_character(&ctx, ctx.author()).await? else {
+        return Ok(());
+    };
+    let Some((stat, modifier)) = stat_modifier(db, character.id, &stat).await? else {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} doesn't have a stat named {}.",
+                character.name, stat
+            ))
+            .send(&ctx)
+            .await;
+    };
+    let Some(difficulty) = entity::difficulty::Entity::find()
+        .filter(entity::difficulty::Column::Name.like(&difficulty))
+        .one(db)
+        .await?
+    else {
+        return Embed::error(&ctx)
+            .description(format!("There is no difficulty named {}.", difficulty))
+            .send(&ctx)
+            .await;
+    };
+    let modifier = modifier + bonus.unwrap_or_default();
+    let expr = parse(&format!("{}{:+}", CHECK_DIE, modifier))?;
+    let outcome = roll(&expr);
+    let success = outcome.total() >= difficulty.target as i64;
+    let now = chrono::Utc::now();
+    let rewarding = success && (difficulty.xp > 0 || difficulty.credits > 0);
+    // rewards are paid at most once per cooldown, so checks can't be farmed
+    let cooled_down = character
+        .rewarded_at
+        .is_none_or(|at| at + ctx.data().check_cooldown <= now);
+    let mut rewards = vec![];
+    if rewarding && cooled_down {
+        if difficulty.xp > 0 {
+            let mut member = get_member(db, ctx.author().id).await?.into_active_model();
+            member.xp = Set(member.xp.unwrap().saturating_add(difficulty.xp));
+            level_up(ctx.http(), ctx.data(), &mut member).await?;
+            member.save(db).await?;
+            rewards.push(format!("{} XP", difficulty.xp.ths()));
+        }
+        let mut wallet = character.clone().into_active_model();
+        wallet.xp = Set(character.xp.saturating_add(difficulty.xp));
+        if difficulty.credits > 0 {
+            wallet.balance = Set(character.balance.saturating_add(difficulty.credits));
+            rewards.push(money(difficulty.credits));
+        }
+        wallet.rewarded_at = Set(Some(now));
+        wallet.save(db).await?;
+    }
+    let rewards = if !rewards.is_empty() {
+        format!(" Earned {}.", rewards.join(" and "))
+    } else if rewarding {
+        let resumes = character.rewarded_at.unwrap_or(now) + ctx.data().check_cooldown;
+        format!(" No rewards until <t:{}:R>.", resumes.timestamp())
+    } else {
+        String::new()
+    };
+    let embed = if success {
+        Embed::success(&ctx)
+    } else {
+        Embed::error(&ctx)
+    }
+    .title(format!(
+        "{} checks {} ({})",
+        character.name, stat.name, difficulty.name
+    ))
+    .description(format!(
+        "{} against {}.{}",
+        if success {
+            "**Success**"
+        } else {
+            "**Failure**"
+        },
+        difficulty.target,
+        rewards
+    ));
+    // checks are always public so the scene has a record of them
+    with_outcome(embed, &outcome).send_pub(&ctx).await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![stats(), difficulty(), check()]
+}
@@ -0,0 +1,307 @@
+use poise::serenity_prelude::{Context, Mention, Role, RoleId, User, UserId};
+use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, Set};
+
+use crate::{
+    utils::{
+        audit::Audit,
+        duration::{format_duration, parse_duration},
+        embed::{not_admin, Embed},
+        perms::admin,
+    },
+    Ctx,
+    Data,
+};
+
+/// Removes temporary roles that have expired. Expiry is kept in the database,
+/// so roles that expired while the bot was offline are removed on the first
+/// run after it comes back.
+pub async fn expire_temp_roles(ctx: &Context, data: &Data) -> Result<(), crate::Error> {
+    let expired = entity::temp_role::Entity::find()
+        .filter(entity::temp_role::Column::ExpiresAt.lte(chrono::Utc::now()))
+        .all(&data.db)
+        .await?;
+    for temp_role in expired {
+        let removed = ctx
+            .http
+            .remove_member_role(
+                data.primary_guild_id,
+                temp_role.member.parse::<UserId>().unwrap(),
+                temp_role.role.parse::<RoleId>().unwrap(),
+                Some("Temporary role expired."),
+            )
+            .await;
+        match removed {
+            Ok(()) => {},
+            // the member left or the role is gone, either way it's over
+            Err(poise::serenity_prelude::Error::Http(e))
+                if e.status_code().map(|s| s.as_u16()) == Some(404) => {},
+            // kept so the next run tries again
+            Err(e) => {
+                tracing::error!(
+                    "Failed to remove temporary role {} from {}: {:?}",
+                    temp_role.role,
+                    temp_role.member,
+                    e
+                );
+                continue;
+            },
+        }
+        temp_role.delete(&data.db).await?;
+    }
+    Ok(())
+}
+
+async fn find_temp_role(
+    ctx: &Ctx<'_>,
+    user: &User,
+    role: &Role,
+) -> Result<Option<entity::temp_role::Model>, crate::Error> {
+    let temp_role = entity::temp_role::Entity::find()
+        .filter(entity::temp_role::Column::Member.eq(user.id.to_string()))
+        .filter(entity::temp_role::Column::Role.eq(role.id.to_string()))
+        .one(&ctx.data().db)
+        .await?;
+    if temp_role.is_none() {
+        Embed::error(ctx)
+            .description(format!("{} doesn't have {} temporarily.", user, role))
+            .send(ctx)
+            .await?;
+    }
+    Ok(temp_role)
+}
+
+async fn invalid_duration(ctx: &Ctx<'_>, duration: &str) -> Result<(), crate::Error> {
+    Embed::error(ctx)
+        .description(format!(
+            "{} is not a valid duration, use something like `3d`, `12h` or `1d12h`.",
+            duration
+        ))
+        .send(ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "temprole",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn temprole(
+    ctx: Ctx<'_>,
+    user: User,
+    role: Role,
+    #[description = "How long to keep the role, e.g. 3d or 12h"] duration: String,
+    reason: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let now = chrono::Utc::now();
+    let Some((length, expires_at)) = parse_duration(&duration)
+        .and_then(|length| Some((length, now.checked_add_signed(length)?)))
+    else {
+        return invalid_duration(&ctx, &duration).await;
+    };
+    let existing = entity::temp_role::Entity::find()
+        .filter(entity::temp_role::Column::Member.eq(user.id.to_string()))
+        .filter(entity::temp_role::Column::Role.eq(role.id.to_string()))
+        .one(&ctx.data().db)
+        .await?;
+    if existing.is_some() {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} already has {} temporarily, use `/extend-temprole` instead.",
+                user, role
+            ))
+            .send(&ctx)
+            .await;
+    }
+    let member = ctx.data().primary_guild_id.member(ctx, user.id).await?;
+    if member.roles.contains(&role.id) {
+        return Embed::error(&ctx)
+            .description(format!(
+                "{} already has {}, it would be taken away when the temporary role expires.",
+                user, role
+            ))
+            .send(&ctx)
+            .await;
+    }
+    ctx.http()
+        .add_member_role(
+            ctx.data().primary_guild_id,
+            user.id,
+            role.id,
+            Some("Temporary role."),
+        )
+        .await?;
+    entity::temp_role::ActiveModel {
+        member: Set(user.id.to_string()),
+        role: Set(role.id.to_string()),
+        granted_by: Set(ctx.author().id.to_string()),
+        reason: Set(reason),
+        expires_at: Set(expires_at),
+        created_at: Set(now),
+        ..Default::default()
+    }
+    .insert(&ctx.data().db)
+    .await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .after(format!("{} for {}", role, format_duration(length)))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "Gave {} to {} until <t:{}:f>.",
+            role,
+            user,
+            expires_at.timestamp()
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "temproles",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn temproles(ctx: Ctx<'_>, user: Option<User>) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let mut query = entity::temp_role::Entity::find();
+    if let Some(ref user) = user {
+        query = query.filter(entity::temp_role::Column::Member.eq(user.id.to_string()));
+    }
+    let temp_roles = query
+        .order_by_asc(entity::temp_role::Column::ExpiresAt)
+        .all(&ctx.data().db)
+        .await?;
+    if temp_roles.is_empty() {
+        return Embed::info(&ctx)
+            .description("No temporary roles.")
+            .send(&ctx)
+            .await;
+    }
+    Embed::info(&ctx)
+        .title("Temporary Roles")
+        .description(
+            temp_roles
+                .iter()
+                .take(25)
+                .map(|t| {
+                    format!(
+                        "{} {} - expires <t:{}:R>{}",
+                        Mention::from(t.member.parse::<UserId>().unwrap()),
+                        Mention::from(t.role.parse::<RoleId>().unwrap()),
+                        t.expires_at.timestamp(),
+                        t.reason
+                            .as_ref()
+                            .map(|r| format!(" ({})", r))
+                            .unwrap_or_default()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "extend-temprole",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn extend_temprole(
+    ctx: Ctx<'_>,
+    user: User,
+    role: Role,
+    #[description = "How much longer to keep the role, e.g. 3d or 12h"] duration: String,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(length) = parse_duration(&duration) else {
+        return invalid_duration(&ctx, &duration).await;
+    };
+    let Some(temp_role) = find_temp_role(&ctx, &user, &role).await? else {
+        return Ok(());
+    };
+    let before = temp_role.expires_at;
+    let Some(expires_at) = before.checked_add_signed(length) else {
+        return invalid_duration(&ctx, &duration).await;
+    };
+    let mut temp_role = temp_role.into_active_model();
+    temp_role.expires_at = Set(expires_at);
+    let temp_role = temp_role.update(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(format!(
+            "{} until {}",
+            role,
+            before.format("%Y-%m-%d %H:%M")
+        ))
+        .after(format!(
+            "{} until {}",
+            role,
+            temp_role.expires_at.format("%Y-%m-%d %H:%M")
+        ))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!(
+            "{} now keeps {} until <t:{}:f>.",
+            user,
+            role,
+            temp_role.expires_at.timestamp()
+        ))
+        .send(&ctx)
+        .await
+}
+
+#[poise::command(
+    slash_command,
+    rename = "cancel-temprole",
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn cancel_temprole(ctx: Ctx<'_>, user: User, role: Role) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let Some(temp_role) = find_temp_role(&ctx, &user, &role).await? else {
+        return Ok(());
+    };
+    ctx.http()
+        .remove_member_role(
+            ctx.data().primary_guild_id,
+            user.id,
+            role.id,
+            Some("Temporary role cancelled."),
+        )
+        .await?;
+    let before = temp_role.expires_at;
+    temp_role.delete(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(format!(
+            "{} until {}",
+            role,
+            before.format("%Y-%m-%d %H:%M")
+        ))
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Removed {} from {}.", role, user))
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![
+        temprole(),
+        temproles(),
+        extend_temprole(),
+        cancel_temprole(),
+    ]
+}
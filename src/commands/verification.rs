@@ -0,0 +1,451 @@
+use poise::{
+    serenity_prelude::{
+        ButtonStyle,
+        ComponentInteraction,
+        Context,
+        CreateActionRow,
+        CreateButton,
+        CreateEmbed,
+        CreateInteractionResponse,
+        CreateInteractionResponseMessage,
+        CreateMessage,
+        GuildChannel,
+        Member,
+        ModalInteraction,
+        User,
+        UserId,
+    },
+    Modal,
+};
+use sea_orm::{prelude::*, IntoActiveModel, Set};
+
+use crate::{
+    utils::{
+        audit::Audit,
+        db::get_member,
+        embed::{not_admin, Embed, EmbedStyle},
+        perms::{admin, is_admin},
+    },
+    Ctx,
+    Data,
+};
+
+/// Custom id prefix of the verification buttons and questionnaire.
+pub const VERIFY_PREFIX: &str = "verify";
+
+#[derive(Debug, poise::Modal)]
+#[name = "Verification"]
+struct VerificationModal {
+    #[name = "How did you find the server?"]
+    #[max_length = 500]
+    #[paragraph]
+    found:    String,
+    #[name = "What would you like to roleplay?"]
+    #[max_length = 1000]
+    #[paragraph]
+    roleplay: String,
+    #[name = "Anything else staff should know?"]
+    #[max_length = 1000]
+    #[paragraph]
+    other:    Option<String>,
+}
+
+fn reply(embed: Embed) -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .embed(embed.build())
+            .ephemeral(true),
+    )
+}
+
+/// Puts a member who just joined behind the verification gate, if one is
+/// configured.
+pub async fn member_joined(
+    ctx: &Context,
+    data: &Data,
+    member: &Member,
+) -> Result<(), crate::Error> {
+    let Some(role) = data.unverified_role else {
+        return Ok(());
+    };
+    if let Some(existing) = entity::verification::Entity::find_by_id(member.user.id.to_string())
+        .one(&data.db)
+        .await?
+    {
+        existing.delete(&data.db).await?;
+    }
+    entity::verification::ActiveModel {
+        member:       Set(member.user.id.to_string()),
+        joined_at:    Set(chrono::Utc::now()),
+        answers:      Set(None),
+        submitted_at: Set(None),
+    }
+    .insert(&data.db)
+    .await?;
+    ctx.http
+        .add_member_role(
+            data.primary_guild_id,
+            member.user.id,
+            role,
+            Some("Awaiting verification."),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Lets a member through the gate, swapping the unverified role for the
+/// member role.
+async fn verify(ctx: &Context, data: &Data, user: UserId) -> Result<(), crate::Error> {
+    if let Some(role) = data.unverified_role {
+        ctx.http
+            .remove_member_role(data.primary_guild_id, user, role, Some("Verified."))
+            .await?;
+    }
+    if let Some(role) = data.member_role {
+        ctx.http
+            .add_member_role(data.primary_guild_id, user, role, Some("Verified."))
+            .await?;
+    }
+    entity::verification::Entity::delete_by_id(user.to_string())
+        .exec(&data.db)
+        .await?;
+    Ok(())
+}
+
+/// Kicks members who haven't started verification within the configured
+/// time. Members whose answers are waiting on staff are left alone.
+pub async fn kick_unverified(ctx: &Context, data: &Data) -> Result<(), crate::Error> {
+    let Some(timeout) = data.verification_timeout else {
+        return Ok(());
+    };
+    let overdue = entity::verification::Entity::find()
+        .filter(entity::verification::Column::SubmittedAt.is_null())
+        .filter(entity::verification::Column::JoinedAt.lte(chrono::Utc::now() - timeout))
+        .all(&data.db)
+        .await?;
+    for verification in overdue {
+        let kicked = ctx
+            .http
+            .kick_member(
+                data.primary_guild_id,
+                verification.member.parse().unwrap(),
+                Some("Did not verify in time."),
+            )
+            .await;
+        match kicked {
+            Ok(()) => {},
+            // the member left already, either way they're no longer waiting
+            Err(poise::serenity_prelude::Error::Http(e))
+                if e.status_code().map(|s| s.as_u16()) == Some(404) => {},
+            // kept so the next run tries again
+            Err(e) => {
+                tracing::error!(
+                    "Failed to kick unverified member {}: {:?}",
+                    verification.member,
+                    e
+                );
+                continue;
+            },
+        }
+        verification.delete(&data.db).await?;
+    }
+    Ok(())
+}
+
+/// Handles the rules button and the staff review buttons, `args` being the
+/// custom id after the prefix.
+pub async fn verification_component(
+    ctx: &Context,
+    data: &Data,
+    interaction: &ComponentInteraction,
+    args: &str,
+) -> Result<(), crate::Error> {
+    if args == "accept" {
+        let verification =
+            entity::verification::Entity::find_by_id(interaction.user.id.to_string())
+                .one(&data.db)
+                .await?;
+        let Some(verification) = verification else {
+            return Ok(interaction
+                .create_response(
+                    ctx,
+                    reply(Embed::plain(EmbedStyle::Info).description("You are already verified.")),
+                )
+                .await?);
+        };
+        if data.verification_channel.is_none() {
+            verify(ctx, data, interaction.user.id).await?;
+            return Ok(interaction
+                .create_response(
+                    ctx,
+                    reply(
+                        Embed::plain(EmbedStyle::Success)
+                            .description("Thanks for accepting the rules, welcome!"),
+                    ),
+                )
+                .await?);
+        }
+        if verification.submitted_at.is_some() {
+            return Ok(interaction
+                .create_response(
+                    ctx,
+                    reply(
+                        Embed::plain(EmbedStyle::Info)
+                            .description("Your answers are waiting on staff review."),
+                    ),
+                )
+                .await?);
+        }
+        return Ok(interaction
+            .create_response(
+                ctx,
+                VerificationModal::create(None, format!("{}:answers", VERIFY_PREFIX)),
+            )
+            .await?);
+    }
+
+    let Some((action, user)) = args.split_once(':') else {
+        return Ok(());
+    };
+    if !is_admin(&get_member(&data.db, interaction.user.id).await?) {
+        return Ok(interaction
+            .create_response(
+                ctx,
+                reply(Embed::plain(EmbedStyle::Error).description("You are not an admin.")),
+            )
+            .await?);
+    }
+    let user = user.parse::<UserId>()?;
+    let verification = entity::verification::Entity::find_by_id(user.to_string())
+        .one(&data.db)
+        .await?;
+    let Some(verification) = verification.filter(|v| v.submitted_at.is_some()) else {
+        return Ok(interaction
+            .create_response(
+                ctx,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(
+                            Embed::plain(EmbedStyle::Warning)
+                                .title("Verification")
+                                .description("These answers have already been handled.")
+                                .build(),
+                        )
+                        .components(vec![]),
+                ),
+            )
+            .await?);
+    };
+    let approved = action == "approve";
+    if approved {
+        verify(ctx, data, user).await?;
+    } else {
+        // denied members can answer again, or be kicked once their time runs out
+        let mut verification = verification.into_active_model();
+        verification.answers = Set(None);
+        verification.submitted_at = Set(None);
+        verification.update(&data.db).await?;
+    }
+    let outcome = if approved { "approved" } else { "denied" };
+    Audit::component(
+        &ctx.http,
+        data,
+        interaction,
+        format!("verification {}", action),
+    )
+    .target(user)
+    .after(format!("Verification {}", outcome))
+    .log()
+    .await?;
+    // keep the answers on the message, recoloured to show the outcome
+    let embed = interaction
+        .message
+        .embeds
+        .first()
+        .cloned()
+        .map(CreateEmbed::from)
+        .unwrap_or_default()
+        .colour(if approved {
+            EmbedStyle::Success.colour()
+        } else {
+            EmbedStyle::Error.colour()
+        });
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!("Verification {} by {}.", outcome, interaction.user))
+                    .embed(embed)
+                    .components(vec![]),
+            ),
+        )
+        .await?;
+    if let Ok(channel) = user.create_dm_channel(ctx).await {
+        let _ = channel
+            .send_message(
+                ctx,
+                CreateMessage::new().content(if approved {
+                    "Your verification was approved, welcome!"
+                } else {
+                    "Your verification was not approved. You can answer the questions again from \
+                     the rules channel."
+                }),
+            )
+            .await;
+    }
+    Ok(())
+}
+
+/// Handles a submitted verification questionnaire by posting it for staff.
+pub async fn verification_modal(
+    ctx: &Context,
+    data: &Data,
+    interaction: &ModalInteraction,
+) -> Result<(), crate::Error> {
+    let answers = VerificationModal::parse(interaction.data.clone())?;
+    let verification = entity::verification::Entity::find_by_id(interaction.user.id.to_string())
+        .one(&data.db)
+        .await?;
+    let (Some(verification), Some(channel)) = (verification, data.verification_channel) else {
+        return Ok(interaction
+            .create_response(
+                ctx,
+                reply(Embed::plain(EmbedStyle::Info).description("You are already verified.")),
+            )
+            .await?);
+    };
+    let user = &interaction.user;
+    let mut embed = Embed::plain(EmbedStyle::Info)
+        .author(user)
+        .title("Verification")
+        .description(format!(
+            "{} joined <t:{}:R>.",
+            user,
+            verification.joined_at.timestamp()
+        ))
+        .field("How did you find the server?", &answers.found, false)
+        .field("What would you like to roleplay?", &answers.roleplay, false);
+    if let Some(ref other) = answers.other {
+        embed = embed.field("Anything else staff should know?", other, false);
+    }
+    channel
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(embed.build())
+                .components(vec![CreateActionRow::Buttons(vec![
+                    CreateButton::new(format!("{}:approve:{}", VERIFY_PREFIX, user.id))
+                        .label("Approve")
+                        .style(ButtonStyle::Success),
+                    CreateButton::new(format!("{}:deny:{}", VERIFY_PREFIX, user.id))
+                        .label("Deny")
+                        .style(ButtonStyle::Danger),
+                ])]),
+        )
+        .await?;
+    let mut verification = verification.into_active_model();
+    verification.answers = Set(Some(format!(
+        "{}\n\n{}\n\n{}",
+        answers.found,
+        answers.roleplay,
+        answers.other.unwrap_or_default()
+    )));
+    verification.submitted_at = Set(Some(chrono::Utc::now()));
+    verification.update(&data.db).await?;
+    interaction
+        .create_response(
+            ctx,
+            reply(
+                Embed::plain(EmbedStyle::Success)
+                    .description("Thanks! Staff will review your answers shortly."),
+            ),
+        )
+        .await?;
+    Ok(())
+}
+
+#[poise::command(
+    slash_command,
+    subcommands("panel", "approve"),
+    subcommand_required,
+    default_member_permissions = "ADMINISTRATOR"
+)]
+pub async fn verification(_ctx: Ctx<'_>) -> Result<(), crate::Error> {
+    Ok(())
+}
+
+/// Posts the rules message with the button that starts verification.
+#[poise::command(slash_command)]
+pub async fn panel(
+    ctx: Ctx<'_>,
+    channel: Option<GuildChannel>,
+    #[description = "The rules members must accept"] rules: Option<String>,
+) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    let channel = channel.map(|c| c.id).unwrap_or(ctx.channel_id());
+    channel
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(
+                    Embed::plain(EmbedStyle::Info)
+                        .title("Rules")
+                        .description(rules.unwrap_or_else(|| {
+                            "Read the rules above, then press the button below to get access to \
+                             the server."
+                                .to_string()
+                        }))
+                        .build(),
+                )
+                .components(vec![CreateActionRow::Buttons(vec![CreateButton::new(
+                    format!("{}:accept", VERIFY_PREFIX),
+                )
+                .label("I accept the rules")
+                .style(ButtonStyle::Success)])]),
+        )
+        .await?;
+    Audit::new(&ctx)
+        .target(channel)
+        .after("Verification panel")
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Posted the verification panel in {}.", channel))
+        .send(&ctx)
+        .await
+}
+
+/// Verifies a member by hand, skipping the rules and questionnaire.
+#[poise::command(slash_command)]
+pub async fn approve(ctx: Ctx<'_>, user: User) -> Result<(), crate::Error> {
+    if !admin(&ctx).await? {
+        return not_admin(&ctx).await;
+    }
+    if entity::verification::Entity::find_by_id(user.id.to_string())
+        .one(&ctx.data().db)
+        .await?
+        .is_none()
+    {
+        return Embed::error(&ctx)
+            .description(format!("{} is already verified.", user))
+            .send(&ctx)
+            .await;
+    }
+    verify(ctx.serenity_context(), ctx.data(), user.id).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .after("Verified")
+        .log()
+        .await?;
+    Embed::success(&ctx)
+        .description(format!("Verified {}.", user))
+        .send(&ctx)
+        .await
+}
+
+pub fn commands() -> Vec<poise::Command<Data, crate::Error>> {
+    vec![verification()]
+}
@@ -3,29 +3,54 @@ use sea_orm::{prelude::*, IntoActiveModel, QueryOrder, QuerySelect, Set};
 
 use crate::{
     utils::{
-        db::get_member,
+        audit::Audit,
+        db::{find_member, get_active_character, get_member},
         embed::{not_admin, Embed},
         num::Ths,
         perms::admin,
-        xp::{level_down, level_up, xp_at_level},
+        xp::{add_character_xp, level_down, level_up, xp_at_level},
     },
     Ctx,
     Data,
 };
 
+fn xp_summary(member: &entity::member::ActiveModel) -> String {
+    format!(
+        "{} XP (Level {})",
+        member.xp.clone().unwrap().ths(),
+        member.level.clone().unwrap().ths()
+    )
+}
+
 #[poise::command(slash_command)]
 pub async fn xp(ctx: Ctx<'_>, user: Option<User>) -> Result<(), crate::Error> {
     let user = user.as_ref().unwrap_or_else(|| ctx.author());
-    let member = get_member(&ctx.data().db, user.id).await?;
+    let member = if user.id == ctx.author().id {
+        get_member(&ctx.data().db, user.id).await?
+    } else {
+        let Some(member) = find_member(&ctx.data().db, user.id).await? else {
+            return Embed::error(&ctx)
+                .description(format!("{} hasn't earned any XP yet.", user))
+                .send(&ctx)
+                .await;
+        };
+        member
+    };
     let rank = entity::member::Entity::find()
         .filter(entity::member::Column::Xp.gt(member.xp))
+        .filter(entity::member::Column::DepartedAt.is_null())
         .count(&ctx.data().db)
         .await?
         + 1;
     let xp_to_current_level = xp_at_level(member.level);
     let xp_in_level = member.xp - xp_to_current_level;
     let xp_to_next_level = xp_at_level(member.level + 1) - xp_to_current_level;
-    Embed::info(&ctx)
+    let character = get_active_character(&ctx.data().db, &member).await?;
+    let mut embed = Embed::info(&ctx);
+    if let Some(character) = &character {
+        embed = embed.title(character.name.clone());
+    }
+    embed = embed
         .author(user)
         .description(format!("Leaderboard rank: #{}", rank.ths()))
         .field("XP", member.xp.ths(), true)
@@ -39,9 +64,11 @@ pub async fn xp(ctx: Ctx<'_>, user: Option<User>) -> Result<(), crate::Error> {
                 xp_to_next_level.ths(),
             ),
             true,
-        )
-        .send(&ctx)
-        .await
+        );
+    if let Some(character) = character {
+        embed = embed.field("Character XP", character.xp.ths(), true);
+    }
+    embed.send(&ctx).await
 }
 
 #[poise::command(
@@ -56,9 +83,18 @@ pub async fn add_xp(ctx: Ctx<'_>, user: User, #[min = 0] xp: i32) -> Result<(),
     let mut member = get_member(&ctx.data().db, user.id)
         .await?
         .into_active_model();
+    let before = xp_summary(&member);
     member.xp = Set(member.xp.unwrap() + xp);
+    add_character_xp(&ctx.data().db, &member, xp).await?;
     let leveled_up = level_up(ctx.http(), ctx.data(), &mut member).await?;
+    let after = xp_summary(&member);
     member.save(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(before)
+        .after(after)
+        .log()
+        .await?;
     Embed::success(&ctx)
         .description(format!(
             "Added {} XP to {}.{}",
@@ -88,9 +124,18 @@ pub async fn remove_xp(ctx: Ctx<'_>, user: User, #[min = 0] xp: i32) -> Result<(
             .send(&ctx)
             .await;
     }
+    let before = xp_summary(&member);
     member.xp = Set(member.xp.unwrap() - xp);
+    add_character_xp(&ctx.data().db, &member, -xp).await?;
     let leveled_down = level_down(ctx.http(), ctx.data(), &mut member).await?;
+    let after = xp_summary(&member);
     member.save(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(before)
+        .after(after)
+        .log()
+        .await?;
     Embed::success(&ctx)
         .description(format!(
             "Removed {} XP from {}.{}",
@@ -122,11 +167,19 @@ pub async fn add_level(
     let mut member = get_member(&ctx.data().db, user.id)
         .await?
         .into_active_model();
+    let before = xp_summary(&member);
     member.level = Set(member.level.unwrap() + level);
     if level > 0 {
         member.xp = Set(xp_at_level(member.level.clone().unwrap()));
     }
+    let after = xp_summary(&member);
     member.save(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(before)
+        .after(after)
+        .log()
+        .await?;
     Embed::success(&ctx)
         .description(format!(
             "Added {} level{} to {}.",
@@ -160,11 +213,19 @@ pub async fn remove_level(
             .send(&ctx)
             .await;
     }
+    let before = xp_summary(&member);
     member.level = Set(member.level.unwrap() - level);
     if level > 0 {
         member.xp = Set(xp_at_level(member.level.clone().unwrap()));
     }
+    let after = xp_summary(&member);
     member.save(&ctx.data().db).await?;
+    Audit::new(&ctx)
+        .target(user.id)
+        .before(before)
+        .after(after)
+        .log()
+        .await?;
     Embed::success(&ctx)
         .description(format!(
             "Removed {} level{} from {}.",
@@ -181,10 +242,12 @@ pub async fn leaderboard(ctx: Ctx<'_>, #[min = 1] page: Option<u64>) -> Result<(
     let member = get_member(&ctx.data().db, ctx.author().id).await?;
     let rank = entity::member::Entity::find()
         .filter(entity::member::Column::Xp.gt(member.xp))
+        .filter(entity::member::Column::DepartedAt.is_null())
         .count(&ctx.data().db)
         .await?
         + 1;
     let members = entity::member::Entity::find()
+        .filter(entity::member::Column::DepartedAt.is_null())
         .order_by_desc(entity::member::Column::Xp)
         .limit(10)
         .offset((page.unwrap_or(1) - 1) * 10)
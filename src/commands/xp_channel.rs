@@ -3,6 +3,7 @@ use sea_orm::{EntityTrait, IntoActiveModel, Set};
 
 use crate::{
     utils::{
+        audit::Audit,
         embed::{not_admin, Embed},
         perms::admin,
     },
@@ -34,6 +35,11 @@ pub async fn add_xp_channel(ctx: Ctx<'_>, channel: Channel) -> Result<(), crate:
         entity::xp_channel::Entity::insert(xp_channel)
             .exec(&ctx.data().db)
             .await?;
+        Audit::new(&ctx)
+            .target(channel.id())
+            .after("XP channel")
+            .log()
+            .await?;
         Embed::success(&ctx)
             .description(format!("Added {} as an XP channel.", channel))
             .send(&ctx)
@@ -57,6 +63,11 @@ pub async fn remove_xp_channel(ctx: Ctx<'_>, channel: Channel) -> Result<(), cra
         entity::xp_channel::Entity::delete(xp_channel.into_active_model())
             .exec(&ctx.data().db)
             .await?;
+        Audit::new(&ctx)
+            .target(channel.id())
+            .before("XP channel")
+            .log()
+            .await?;
         Embed::success(&ctx)
             .description(format!("Removed {} as an XP channel.", channel))
             .send(&ctx)
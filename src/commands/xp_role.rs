@@ -3,6 +3,7 @@ use sea_orm::{prelude::*, EntityTrait, IntoActiveModel, QueryFilter, QueryOrder,
 
 use crate::{
     utils::{
+        audit::Audit,
         embed::{not_admin, Embed},
         perms::admin,
     },
@@ -39,9 +40,15 @@ pub async fn add_xp_role(
         entity::xp_role::Entity::insert(xp_role)
             .exec(&ctx.data().db)
             .await?;
+        Audit::new(&ctx)
+            .target(role.id)
+            .after(format!("XP role at level {}", level))
+            .log()
+            .await?;
         ctx.defer_ephemeral().await?;
         let members = entity::member::Entity::find()
             .filter(entity::member::Column::Level.gte(level))
+            .filter(entity::member::Column::DepartedAt.is_null())
             .all(&ctx.data().db)
             .await?;
         for mem in members {
@@ -78,9 +85,15 @@ pub async fn remove_xp_role(ctx: Ctx<'_>, role: Role) -> Result<(), crate::Error
         entity::xp_role::Entity::delete(xp_role.clone().into_active_model())
             .exec(&ctx.data().db)
             .await?;
+        Audit::new(&ctx)
+            .target(role.id)
+            .before(format!("XP role at level {}", xp_role.level))
+            .log()
+            .await?;
         ctx.defer_ephemeral().await?;
         let members = entity::member::Entity::find()
             .filter(entity::member::Column::Level.gte(xp_role.level))
+            .filter(entity::member::Column::DepartedAt.is_null())
             .all(&ctx.data().db)
             .await?;
         for mem in members {
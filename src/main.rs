@@ -3,13 +3,23 @@ mod utils;
 
 use poise::{
     samples::{register_globally, register_in_guild},
-    serenity_prelude::{self as serenity, CacheHttp, FullEvent, GuildId},
+    serenity_prelude::{
+        self as serenity,
+        CacheHttp,
+        ChannelId,
+        FullEvent,
+        GuildId,
+        Interaction,
+        RoleId,
+    },
 };
-use sea_orm::{prelude::*, IntoActiveModel, IntoSimpleExpr, Set, UpdateResult};
+use sea_orm::{prelude::*, IntoActiveModel, IntoSimpleExpr, Set};
 use tracing::{debug, info};
 use utils::{
-    db::get_member,
-    xp::{can_earn_xp, level_up, xp_from_message},
+    db::{get_member, mark_departed, mark_returned, purge_departed},
+    duration::parse_duration,
+    proxy::proxy,
+    xp::{add_character_xp, can_earn_xp, level_up, xp_from_message},
 };
 
 async fn event_handler(
@@ -21,6 +31,17 @@ async fn event_handler(
     #[allow(clippy::single_match)]
     match event {
         FullEvent::Message { new_message: msg } => {
+            // proxied posts are credited to the author when the original is handled
+            if msg.webhook_id.is_some() {
+                return Ok(());
+            }
+            // flagged messages are deleted, so they're neither proxied nor earn xp
+            if commands::automod(ctx, data, msg).await? {
+                return Ok(());
+            }
+            let content = proxy(ctx, data, msg)
+                .await?
+                .unwrap_or_else(|| msg.content.clone());
             // check if the channel or thread is directly allowed to earn xp
             if !can_earn_xp(&data.db, msg.channel_id).await? {
                 if let Some(channel) = msg.channel(&ctx).await?.guild() {
@@ -50,11 +71,12 @@ async fn event_handler(
                     return Ok(());
                 }
             }
-            let xp = xp_from_message(msg.content.as_str());
+            let xp = xp_from_message(content.as_str());
             let mut member = get_member(&data.db, msg.author.id)
                 .await?
                 .into_active_model();
             member.xp = Set(member.xp.unwrap() + xp);
+            add_character_xp(&data.db, &member, xp).await?;
             let leveled_up = level_up(ctx.http(), data, &mut member).await?;
             let level = member.level.clone().unwrap();
             member.save(&data.db).await?;
@@ -73,6 +95,16 @@ async fn event_handler(
         FullEvent::GuildMemberAddition {
             new_member: member, ..
         } => {
+            mark_returned(&data.db, member.user.id).await?;
+            // the welcome and XP roles shouldn't depend on verification working
+            if let Err(e) = commands::member_joined(ctx, data, member).await {
+                tracing::error!(
+                    "Failed to start verification for {}: {:?}",
+                    member.user.id,
+                    e
+                );
+            }
+            commands::welcome(ctx, data, member).await?;
             let mem = entity::member::Entity::find_by_id(member.user.id.to_string())
                 .one(&data.db)
                 .await?;
@@ -93,6 +125,26 @@ async fn event_handler(
                 }
             }
         },
+        FullEvent::GuildMemberRemoval { user, .. } => {
+            mark_departed(&data.db, user.id).await?;
+            commands::farewell(ctx, data, user).await?;
+        },
+        FullEvent::ReactionAdd { add_reaction } => {
+            commands::reaction_add(ctx, data, add_reaction).await?;
+        },
+        FullEvent::ReactionRemove { removed_reaction } => {
+            commands::reaction_remove(ctx, data, removed_reaction).await?;
+        },
+        FullEvent::InteractionCreate {
+            interaction: Interaction::Component(interaction),
+        } => {
+            commands::component(ctx, data, interaction).await?;
+        },
+        FullEvent::InteractionCreate {
+            interaction: Interaction::Modal(interaction),
+        } => {
+            commands::modal(ctx, data, interaction).await?;
+        },
         _ => {},
     }
     Ok(())
@@ -100,7 +152,9 @@ async fn event_handler(
 
 #[tracing::instrument]
 #[allow(clippy::inconsistent_digit_grouping)]
-async fn income(db: &DatabaseConnection) -> Result<UpdateResult, sea_orm::DbErr> {
+async fn income(db: &DatabaseConnection) -> Result<(), sea_orm::DbErr> {
+    // members playing a character are paid into that character's balance
+    // instead, and members who left aren't paid at all
     entity::member::Entity::update_many()
         .col_expr(
             entity::member::Column::Balance,
@@ -108,14 +162,105 @@ async fn income(db: &DatabaseConnection) -> Result<UpdateResult, sea_orm::DbErr>
                 .into_simple_expr()
                 .add(200_000_000_00_i64),
         )
+        .filter(entity::member::Column::ActiveCharacter.is_null())
+        .filter(entity::member::Column::DepartedAt.is_null())
         .exec(db)
-        .await
+        .await?;
+    entity::character::Entity::update_many()
+        .col_expr(
+            entity::character::Column::Balance,
+            entity::character::Column::Balance
+                .into_simple_expr()
+                .add(200_000_000_00_i64),
+        )
+        .filter(
+            entity::character::Column::Id.in_subquery(
+                sea_orm::sea_query::Query::select()
+                    .column(entity::member::Column::ActiveCharacter)
+                    .from(entity::member::Entity)
+                    .and_where(entity::member::Column::ActiveCharacter.is_not_null())
+                    .and_where(entity::member::Column::DepartedAt.is_null())
+                    .to_owned(),
+            ),
+        )
+        .exec(db)
+        .await?;
+    Ok(())
 }
 
-#[derive(Debug)]
+/// Brings the departed flags in line with who is actually in the server, as
+/// members who joined or left while the bot was offline fired no events.
+async fn reconcile_members(ctx: &serenity::Context, data: &Data) -> Result<(), Error> {
+    let mut present = std::collections::HashSet::new();
+    let mut after = None;
+    loop {
+        let page = data
+            .primary_guild_id
+            .members(ctx, Some(1000), after)
+            .await?;
+        after = page.last().map(|member| member.user.id);
+        present.extend(page.iter().map(|member| member.user.id.to_string()));
+        if page.len() < 1000 {
+            break;
+        }
+    }
+    for member in entity::member::Entity::find().all(&data.db).await? {
+        match (present.contains(&member.id), member.departed_at) {
+            (true, Some(_)) => {
+                mark_returned(&data.db, &member.id).await?;
+            },
+            (false, None) => mark_departed(&data.db, &member.id).await?,
+            _ => {},
+        }
+    }
+    Ok(())
+}
+
+/// Runs the jobs that have to happen on time, such as expiring temporary
+/// roles. Jobs keep their state in the database, so anything that came due
+/// while the bot was offline is handled on the first tick.
+async fn scheduler(ctx: serenity::Context, data: Data) {
+    if let Err(e) = reconcile_members(&ctx, &data).await {
+        tracing::error!("Failed to reconcile members: {:?}", e);
+    }
+    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+    loop {
+        interval.tick().await;
+        if let Err(e) = commands::expire_temp_roles(&ctx, &data).await {
+            tracing::error!("Failed to expire temporary roles: {:?}", e);
+        }
+        if let Err(e) = commands::kick_unverified(&ctx, &data).await {
+            tracing::error!("Failed to kick unverified members: {:?}", e);
+        }
+        if let Err(e) = purge_departed(&data.db, data.departed_grace_period).await {
+            tracing::error!("Failed to purge departed members: {:?}", e);
+        }
+        if let Err(e) = commands::arrive_travellers(&ctx, &data).await {
+            tracing::error!("Failed to land arriving travellers: {:?}", e);
+        }
+        if let Err(e) = commands::announce_months(&ctx, &data).await {
+            tracing::error!("Failed to announce the new month: {:?}", e);
+        }
+        if let Err(e) = commands::remind_events(&ctx, &data).await {
+            tracing::error!("Failed to send event reminders: {:?}", e);
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct Data {
-    pub db:               sea_orm::DatabaseConnection,
+    pub db: sea_orm::DatabaseConnection,
     pub primary_guild_id: GuildId,
+    pub audit_log_channel: Option<ChannelId>,
+    pub mod_log_channel: Option<ChannelId>,
+    pub role_request_channel: Option<ChannelId>,
+    pub unverified_role: Option<RoleId>,
+    pub member_role: Option<RoleId>,
+    pub verification_channel: Option<ChannelId>,
+    pub verification_timeout: Option<chrono::Duration>,
+    pub departed_grace_period: chrono::Duration,
+    pub warning_expiry: chrono::Duration,
+    pub check_cooldown: chrono::Duration,
 }
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -137,6 +282,39 @@ async fn main() {
         .expect("Expected a primary guild ID in the environment")
         .parse()
         .expect("Failed to parse primary guild ID");
+    let audit_log_channel = std::env::var("AUDIT_LOG_CHANNEL_ID")
+        .ok()
+        .map(|s| s.parse().expect("Failed to parse audit log channel ID"));
+    let mod_log_channel = std::env::var("MOD_LOG_CHANNEL_ID")
+        .ok()
+        .map(|s| s.parse().expect("Failed to parse mod log channel ID"));
+    let role_request_channel = std::env::var("ROLE_REQUEST_CHANNEL_ID")
+        .ok()
+        .map(|s| s.parse().expect("Failed to parse role request channel ID"));
+    let unverified_role = std::env::var("UNVERIFIED_ROLE_ID")
+        .ok()
+        .map(|s| s.parse().expect("Failed to parse unverified role ID"));
+    let member_role = std::env::var("MEMBER_ROLE_ID")
+        .ok()
+        .map(|s| s.parse().expect("Failed to parse member role ID"));
+    let verification_channel = std::env::var("VERIFICATION_CHANNEL_ID")
+        .ok()
+        .map(|s| s.parse().expect("Failed to parse verification channel ID"));
+    let verification_timeout = std::env::var("VERIFICATION_TIMEOUT")
+        .ok()
+        .map(|s| parse_duration(&s).expect("Failed to parse verification timeout"));
+    let departed_grace_period = std::env::var("DEPARTED_GRACE_PERIOD")
+        .ok()
+        .map(|s| parse_duration(&s).expect("Failed to parse departed grace period"))
+        .unwrap_or_else(|| chrono::Duration::days(30));
+    let warning_expiry = std::env::var("WARNING_EXPIRY")
+        .ok()
+        .map(|s| parse_duration(&s).expect("Failed to parse warning expiry"))
+        .unwrap_or_else(|| chrono::Duration::days(30));
+    let check_cooldown = std::env::var("CHECK_COOLDOWN")
+        .ok()
+        .map(|s| parse_duration(&s).expect("Failed to parse check cooldown"))
+        .unwrap_or_else(|| chrono::Duration::hours(1));
 
     let mut opt = sea_orm::ConnectOptions::new(db_url);
     opt.sqlx_logging_level(tracing::log::LevelFilter::Trace);
@@ -168,10 +346,22 @@ async fn main() {
                 } else {
                     register_globally(ctx, &framework.options().commands).await?;
                 }
-                Ok(Data {
+                let data = Data {
                     db,
                     primary_guild_id,
-                })
+                    audit_log_channel,
+                    mod_log_channel,
+                    role_request_channel,
+                    unverified_role,
+                    member_role,
+                    verification_channel,
+                    verification_timeout,
+                    departed_grace_period,
+                    warning_expiry,
+                    check_cooldown,
+                };
+                tokio::spawn(scheduler(ctx.clone(), data.clone()));
+                Ok(data)
             })
         })
         .options(poise::FrameworkOptions {
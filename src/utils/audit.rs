@@ -0,0 +1,112 @@
+use poise::serenity_prelude::{ComponentInteraction, CreateMessage, Http, Mention, User};
+use sea_orm::{EntityTrait, Set};
+
+use super::embed::{Embed, EmbedStyle};
+use crate::{Ctx, Data};
+
+/// An entry in the audit log, recorded for every privileged command.
+///
+/// The actor, command and arguments are taken from the invocation context, so
+/// callers only need to supply the target and the values that changed.
+pub struct Audit<'a> {
+    http:      &'a Http,
+    data:      &'a Data,
+    actor:     &'a User,
+    command:   String,
+    arguments: String,
+    target:    Option<Mention>,
+    before:    Option<String>,
+    after:     Option<String>,
+}
+
+impl<'a> Audit<'a> {
+    #[inline]
+    pub fn new(ctx: &Ctx<'a>) -> Self {
+        Self {
+            http:      ctx.http(),
+            data:      ctx.data(),
+            actor:     ctx.author(),
+            command:   ctx.command().qualified_name.clone(),
+            arguments: ctx.invocation_string(),
+            target:    None,
+            before:    None,
+            after:     None,
+        }
+    }
+
+    /// An entry for a privileged action taken through a message component,
+    /// such as a staff member pressing an approval button.
+    #[inline]
+    pub fn component(
+        http: &'a Http,
+        data: &'a Data,
+        interaction: &'a ComponentInteraction,
+        action: impl ToString,
+    ) -> Self {
+        Self {
+            http,
+            data,
+            actor: &interaction.user,
+            command: action.to_string(),
+            arguments: interaction.data.custom_id.clone(),
+            target: None,
+            before: None,
+            after: None,
+        }
+    }
+
+    #[inline]
+    pub fn target(mut self, target: impl Into<Mention>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    #[inline]
+    pub fn before(mut self, before: impl ToString) -> Self {
+        self.before = Some(before.to_string());
+        self
+    }
+
+    #[inline]
+    pub fn after(mut self, after: impl ToString) -> Self {
+        self.after = Some(after.to_string());
+        self
+    }
+
+    pub async fn log(self) -> Result<(), crate::Error> {
+        let entry = entity::audit_log::ActiveModel {
+            actor: Set(self.actor.id.to_string()),
+            target: Set(self.target.map(|t| t.to_string())),
+            command: Set(self.command.clone()),
+            arguments: Set(self.arguments.clone()),
+            before: Set(self.before.clone()),
+            after: Set(self.after.clone()),
+            created_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        };
+        entity::audit_log::Entity::insert(entry)
+            .exec(&self.data.db)
+            .await?;
+        if let Some(channel) = self.data.audit_log_channel {
+            let mut embed = Embed::plain(EmbedStyle::Info)
+                .author(self.actor)
+                .title("Audit Log")
+                .field("Command", format!("`{}`", self.arguments), false)
+                .field("Actor", self.actor.to_string(), true);
+            if let Some(target) = self.target {
+                embed = embed.field("Target", target.to_string(), true);
+            }
+            if let Some(before) = self.before {
+                embed = embed.field("Before", before, true);
+            }
+            if let Some(after) = self.after {
+                embed = embed.field("After", after, true);
+            }
+            // the mirror is best effort, the entry is already in the database
+            let _ = channel
+                .send_message(self.http, CreateMessage::new().embed(embed.build()))
+                .await;
+        }
+        Ok(())
+    }
+}
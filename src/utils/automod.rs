@@ -0,0 +1,91 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+/// Messages with fewer letters than this are never flagged for caps, so short
+/// replies like "OK" or "LOL" are fine.
+const MIN_CAPS_LETTERS: usize = 10;
+
+static INVITE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(discord\.gg|discord(app)?\.com/invite)/[a-z0-9-]+").unwrap());
+static CUSTOM_EMOJI: Lazy<Regex> = Lazy::new(|| Regex::new(r"<a?:\w+:\d+>").unwrap());
+
+/// Compiles a pattern finding a word or phrase on its own, ignoring case, so
+/// banning "ass" doesn't flag "class". Words can start or end with
+/// punctuation, which `\b` wouldn't allow.
+pub fn word_regex(word: &str) -> Option<Regex> {
+    Regex::new(&format!(r"(?i)(?:^|\W){}(?:\W|$)", regex::escape(word))).ok()
+}
+
+pub fn contains_invite(content: &str) -> bool {
+    INVITE.is_match(content)
+}
+
+/// The percentage of letters in a message that are capitals, or `None` if it
+/// is too short to judge.
+pub fn caps_percent(content: &str) -> Option<u32> {
+    let letters = content.chars().filter(|c| c.is_alphabetic()).count();
+    if letters < MIN_CAPS_LETTERS {
+        return None;
+    }
+    let caps = content.chars().filter(|c| c.is_uppercase()).count();
+    Some((caps * 100 / letters) as u32)
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(c as u32, 0x1F000..=0x1FAFF | 0x2600..=0x27BF)
+}
+
+/// Counts custom and unicode emoji in a message.
+pub fn emoji_count(content: &str) -> usize {
+    CUSTOM_EMOJI.find_iter(content).count() + content.chars().filter(|c| is_emoji(*c)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains_word(content: &str, word: &str) -> bool {
+        word_regex(word).unwrap().is_match(content)
+    }
+
+    #[test]
+    fn test_word_regex() {
+        assert!(contains_word("what the heck", "heck"));
+        assert!(contains_word("HECK!", "heck"));
+        assert!(contains_word("oh my gosh darn it", "gosh darn"));
+        assert!(!contains_word("checking", "heck"));
+        assert!(contains_word("a (b) c", "(b"));
+        assert!(contains_word("written in c++", "c++"));
+        assert!(contains_word("c++ is fine", "c++"));
+        assert!(!contains_word("c++x", "c++"));
+        assert!(!contains_word("xc++", "c++"));
+        assert!(contains_word("a.b", "a.b"));
+        assert!(!contains_word("axb", "a.b"));
+    }
+
+    #[test]
+    fn test_contains_invite() {
+        assert!(contains_invite("join discord.gg/abc123"));
+        assert!(contains_invite("https://discord.com/invite/abc-123"));
+        assert!(contains_invite("https://DiscordApp.com/invite/abc"));
+        assert!(!contains_invite("discord.gg is a domain"));
+        assert!(!contains_invite("https://discord.com/channels/1/2"));
+    }
+
+    #[test]
+    fn test_caps_percent() {
+        assert_eq!(caps_percent("OK"), None);
+        assert_eq!(caps_percent("THIS IS ALL CAPS"), Some(100));
+        assert_eq!(caps_percent("this is all lower"), Some(0));
+        assert_eq!(caps_percent("HALF half HALF half"), Some(50));
+        assert_eq!(caps_percent("12345678901234567890"), None);
+    }
+
+    #[test]
+    fn test_emoji_count() {
+        assert_eq!(emoji_count("no emoji here"), 0);
+        assert_eq!(emoji_count("😀😀 hi ☀"), 3);
+        assert_eq!(emoji_count("<:pog:123> <a:dance:456>"), 2);
+        assert_eq!(emoji_count("<:pog:123> 😀"), 2);
+    }
+}
@@ -0,0 +1,136 @@
+use chrono::Duration;
+
+/// A month of the in-universe calendar.
+#[derive(Clone, Copy, Debug)]
+pub struct Month<'a> {
+    pub name: &'a str,
+    pub days: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year:  i32,
+    /// Index into the calendar's months.
+    pub month: usize,
+    /// Starts at 1.
+    pub day:   i32,
+}
+
+/// How many in-universe days pass in a stretch of real time, when `ratio`
+/// in-universe days pass each real day. `None` if there are too many to count.
+pub fn elapsed_days(real: Duration, ratio: f64) -> Option<i64> {
+    let days = (real.num_seconds() as f64 * ratio / 86400.0).floor();
+    (days.is_finite() && days >= i64::MIN as f64 && days < i64::MAX as f64).then_some(days as i64)
+}
+
+/// How many days into its year a date is, starting at 0.
+pub fn day_of_year(months: &[Month<'_>], month: usize, day: i32) -> i64 {
+    months[..month].iter().map(|m| m.days as i64).sum::<i64>() + day as i64 - 1
+}
+
+/// Finds the date `day` days into `year`, rolling into later (or, for negative
+/// days, earlier) years. `None` if the calendar has no days in it or the year
+/// is out of range.
+pub fn date(months: &[Month<'_>], year: i32, day: i64) -> Option<Date> {
+    let length = months.iter().map(|m| m.days as i64).sum::<i64>();
+    if length <= 0 {
+        return None;
+    }
+    let year = year.checked_add(i32::try_from(day.div_euclid(length)).ok()?)?;
+    let mut day = day.rem_euclid(length);
+    for (i, month) in months.iter().enumerate() {
+        if day < month.days as i64 {
+            return Some(Date {
+                year,
+                month: i,
+                day: day as i32 + 1,
+            });
+        }
+        day -= month.days as i64;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONTHS: [Month<'static>; 3] = [
+        Month {
+            name: "Elona",
+            days: 35,
+        },
+        Month {
+            name: "Kelona",
+            days: 35,
+        },
+        Month {
+            name: "Festival",
+            days: 5,
+        },
+    ];
+
+    #[test]
+    fn test_elapsed_days() {
+        assert_eq!(elapsed_days(Duration::days(2), 1.0), Some(2));
+        assert_eq!(elapsed_days(Duration::hours(12), 7.0), Some(3));
+        assert_eq!(elapsed_days(Duration::hours(23), 1.0), Some(0));
+        assert_eq!(elapsed_days(Duration::days(-1), 2.0), Some(-2));
+        assert_eq!(elapsed_days(Duration::days(2), f64::MAX), None);
+    }
+
+    #[test]
+    fn test_date() {
+        assert_eq!(
+            date(&MONTHS, 35, 0),
+            Some(Date {
+                year:  35,
+                month: 0,
+                day:   1,
+            })
+        );
+        assert_eq!(
+            date(&MONTHS, 35, 72),
+            Some(Date {
+                year:  35,
+                month: 2,
+                day:   3,
+            })
+        );
+        assert_eq!(
+            date(&MONTHS, 35, 75),
+            Some(Date {
+                year:  36,
+                month: 0,
+                day:   1,
+            })
+        );
+        // years can count backwards past zero, as BBY dates do
+        assert_eq!(
+            date(&MONTHS, 0, -1),
+            Some(Date {
+                year:  -1,
+                month: 2,
+                day:   5,
+            })
+        );
+        assert_eq!(date(&[], 35, 10), None);
+        assert_eq!(date(&MONTHS, i32::MAX, 75), None);
+        assert_eq!(date(&MONTHS, 0, i64::MAX), None);
+    }
+
+    #[test]
+    fn test_day_of_year() {
+        assert_eq!(day_of_year(&MONTHS, 0, 1), 0);
+        assert_eq!(day_of_year(&MONTHS, 2, 3), 72);
+        let day = day_of_year(&MONTHS, 1, 20);
+        assert_eq!(
+            date(&MONTHS, 4, day),
+            Some(Date {
+                year:  4,
+                month: 1,
+                day:   20,
+            })
+        );
+    }
+}
@@ -1,4 +1,12 @@
-use sea_orm::{DatabaseConnection, EntityTrait};
+use sea_orm::{
+    ActiveModelTrait,
+    ActiveValue,
+    ColumnTrait,
+    DatabaseConnection,
+    EntityTrait,
+    IntoActiveModel,
+    QueryFilter,
+};
 
 #[inline]
 pub async fn get_member(
@@ -22,3 +30,168 @@ pub async fn get_member(
         },
     }
 }
+
+/// Looks a member up without creating them, for commands that look at other
+/// users who may never have talked or may have left.
+#[inline]
+pub async fn find_member(
+    db: &DatabaseConnection,
+    id: impl ToString,
+) -> Result<Option<entity::member::Model>, sea_orm::DbErr> {
+    entity::member::Entity::find_by_id(id.to_string())
+        .one(db)
+        .await
+}
+
+/// Flags a member who left the server as departed. Their data is kept for
+/// the grace period in case they come back, but they no longer earn income or
+/// show up on leaderboards.
+pub async fn mark_departed(
+    db: &DatabaseConnection,
+    id: impl ToString,
+) -> Result<(), sea_orm::DbErr> {
+    entity::member::Entity::update_many()
+        .col_expr(
+            entity::member::Column::DepartedAt,
+            chrono::Utc::now().into(),
+        )
+        .filter(entity::member::Column::Id.eq(id.to_string()))
+        .filter(entity::member::Column::DepartedAt.is_null())
+        .exec(db)
+        .await?;
+    Ok(())
+}
+
+/// Clears the departed flag of a member who rejoined, returning whether they
+/// had been flagged.
+pub async fn mark_returned(
+    db: &DatabaseConnection,
+    id: impl ToString,
+) -> Result<bool, sea_orm::DbErr> {
+    let result = entity::member::Entity::update_many()
+        .col_expr(
+            entity::member::Column::DepartedAt,
+            Option::<chrono::DateTime<chrono::Utc>>::None.into(),
+        )
+        .filter(entity::member::Column::Id.eq(id.to_string()))
+        .filter(entity::member::Column::DepartedAt.is_not_null())
+        .exec(db)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Deletes members who left longer than `grace` ago, along with their
+/// characters and anything else kept against their id. Returns how many
+/// members were purged.
+pub async fn purge_departed(
+    db: &DatabaseConnection,
+    grace: chrono::Duration,
+) -> Result<u64, sea_orm::DbErr> {
+    let departed = entity::member::Entity::find()
+        .filter(entity::member::Column::DepartedAt.lte(chrono::Utc::now() - grace))
+        .all(db)
+        .await?;
+    let ids = departed.into_iter().map(|m| m.id).collect::<Vec<_>>();
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    entity::temp_role::Entity::delete_many()
+        .filter(entity::temp_role::Column::Member.is_in(ids.clone()))
+        .exec(db)
+        .await?;
+    entity::auto_role_request::Entity::delete_many()
+        .filter(entity::auto_role_request::Column::Member.is_in(ids.clone()))
+        .exec(db)
+        .await?;
+    entity::verification::Entity::delete_many()
+        .filter(entity::verification::Column::Member.is_in(ids.clone()))
+        .exec(db)
+        .await?;
+    entity::event_rsvp::Entity::delete_many()
+        .filter(entity::event_rsvp::Column::Member.is_in(ids.clone()))
+        .exec(db)
+        .await?;
+    // characters and everything hanging off them cascade from the member,
+    // except ships, which are left without an owner for staff to hand on
+    Ok(entity::member::Entity::delete_many()
+        .filter(entity::member::Column::Id.is_in(ids))
+        .exec(db)
+        .await?
+        .rows_affected)
+}
+
+#[inline]
+pub async fn get_active_character(
+    db: &DatabaseConnection,
+    member: &entity::member::Model,
+) -> Result<Option<entity::character::Model>, sea_orm::DbErr> {
+    match member.active_character {
+        Some(id) => entity::character::Entity::find_by_id(id).one(db).await,
+        None => Ok(None),
+    }
+}
+
+/// The active character of a member, if they are known and have one.
+pub async fn find_active_character(
+    db: &DatabaseConnection,
+    id: impl ToString,
+) -> Result<Option<entity::character::Model>, sea_orm::DbErr> {
+    match find_member(db, id).await? {
+        Some(member) => get_active_character(db, &member).await,
+        None => Ok(None),
+    }
+}
+
+/// Where a member's money is kept: their active character if they have one,
+/// otherwise the member account itself.
+pub enum Wallet {
+    Member(entity::member::ActiveModel),
+    Character(Box<entity::character::ActiveModel>),
+}
+
+impl Wallet {
+    #[inline]
+    pub fn balance(&self) -> i64 {
+        match self {
+            Self::Member(m) => m.balance.clone().unwrap(),
+            Self::Character(c) => c.balance.clone().unwrap(),
+        }
+    }
+
+    #[inline]
+    pub fn set_balance(&mut self, balance: i64) {
+        match self {
+            Self::Member(m) => m.balance = ActiveValue::Set(balance),
+            Self::Character(c) => c.balance = ActiveValue::Set(balance),
+        }
+    }
+
+    /// The name of the character holding the wallet, if any.
+    #[inline]
+    pub fn character_name(&self) -> Option<String> {
+        match self {
+            Self::Member(_) => None,
+            Self::Character(c) => Some(c.name.clone().unwrap()),
+        }
+    }
+
+    #[inline]
+    pub async fn save(self, db: &DatabaseConnection) -> Result<(), sea_orm::DbErr> {
+        match self {
+            Self::Member(m) => m.save(db).await.map(|_| ()),
+            Self::Character(c) => c.save(db).await.map(|_| ()),
+        }
+    }
+}
+
+#[inline]
+pub async fn get_wallet(
+    db: &DatabaseConnection,
+    id: impl ToString,
+) -> Result<Wallet, sea_orm::DbErr> {
+    let member = get_member(db, id).await?;
+    Ok(match get_active_character(db, &member).await? {
+        Some(character) => Wallet::Character(Box::new(character.into_active_model())),
+        None => Wallet::Member(member.into_active_model()),
+    })
+}
@@ -0,0 +1,913 @@
+//! Dice notation, e.g. `2d20kh1+5`, `4d6!`, `5d10>=8` or Star Wars narrative
+//! pools like `2g1y2p`.
+//!
+//! Standard terms are `NdS` followed by any of `kh`/`kl`/`dh`/`dl` with an
+//! optional count, `!` to explode (on the highest face unless given a
+//! condition), `r` or `ro` with a condition to reroll always or once, and a
+//! bare condition such as `>=8` to count successes instead of summing. Sides
+//! can be `%` for a d100 or `F` for fudge dice. Narrative pools are counts of
+//! `b`oost, blac`k` setback, `g`reen ability, `p`urple difficulty, `y`ellow
+//! proficiency, `r`ed challenge and `w`hite force dice.
+
+use std::fmt::Write;
+
+use rand::Rng;
+
+const MAX_DICE: u32 = 100;
+const MAX_SIDES: u32 = 1000;
+const MAX_TERMS: usize = 20;
+/// Explosions and rerolls stop after this many extra rolls per term.
+const MAX_EXTRA_ROLLS: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compare {
+    Eq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A comparison against a target value, such as `>=8`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Condition {
+    pub compare: Compare,
+    pub target:  i64,
+}
+
+impl Condition {
+    pub fn test(self, value: i64) -> bool {
+        match self.compare {
+            Compare::Eq => value == self.target,
+            Compare::Lt => value < self.target,
+            Compare::Le => value <= self.target,
+            Compare::Gt => value > self.target,
+            Compare::Ge => value >= self.target,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sides {
+    Number(u32),
+    Fudge,
+}
+
+impl Sides {
+    fn faces(self) -> (i64, i64) {
+        match self {
+            Self::Number(n) => (1, n as i64),
+            Self::Fudge => (-1, 1),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Keep {
+    Highest(u32),
+    Lowest(u32),
+    DropHighest(u32),
+    DropLowest(u32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dice {
+    pub count:   u32,
+    pub sides:   Sides,
+    pub keep:    Option<Keep>,
+    pub explode: Option<Condition>,
+    /// The condition to reroll on, and whether to reroll only once.
+    pub reroll:  Option<(Condition, bool)>,
+    pub success: Option<Condition>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Term {
+    Dice(Dice),
+    Number(i64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NarrativeDie {
+    Boost,
+    Setback,
+    Ability,
+    Difficulty,
+    Proficiency,
+    Challenge,
+    Force,
+}
+
+impl NarrativeDie {
+    /// The faces of the die, `S` success, `A` advantage, `!` triumph, `F`
+    /// failure, `T` threat, `X` despair, `L` light side and `D` dark side.
+    fn faces(self) -> &'static [&'static str] {
+        match self {
+            Self::Boost => &["", "", "S", "SA", "AA", "A"],
+            Self::Setback => &["", "", "F", "F", "T", "T"],
+            Self::Ability => &["", "S", "S", "SS", "A", "A", "SA", "AA"],
+            Self::Difficulty => &["", "F", "FF", "T", "T", "T", "TT", "FT"],
+            Self::Proficiency => {
+                &[
+                    "", "S", "S", "SS", "SS", "A", "SA", "SA", "SA", "AA", "AA", "!",
+                ]
+            },
+            Self::Challenge => {
+                &[
+                    "", "F", "F", "FF", "FF", "T", "T", "FT", "FT", "TT", "TT", "X",
+                ]
+            },
+            Self::Force => {
+                &[
+                    "D", "D", "D", "D", "D", "D", "DD", "L", "L", "LL", "LL", "LL",
+                ]
+            },
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Boost => "Boost",
+            Self::Setback => "Setback",
+            Self::Ability => "Ability",
+            Self::Difficulty => "Difficulty",
+            Self::Proficiency => "Proficiency",
+            Self::Challenge => "Challenge",
+            Self::Force => "Force",
+        }
+    }
+
+    fn from_letter(c: char) -> Option<Self> {
+        Some(match c {
+            'b' => Self::Boost,
+            'k' => Self::Setback,
+            'g' => Self::Ability,
+            'p' => Self::Difficulty,
+            'y' => Self::Proficiency,
+            'r' => Self::Challenge,
+            'w' => Self::Force,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    /// Terms with their sign, `1` or `-1`.
+    Standard(Vec<(i64, Term)>),
+    Narrative(Vec<(u32, NarrativeDie)>),
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos:   usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        let matches = s
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.chars.get(self.pos + i) == Some(&c));
+        if matches {
+            self.pos += s.chars().count();
+        }
+        matches
+    }
+
+    fn number(&mut self) -> Result<Option<u32>, String> {
+        let start = self.pos;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return Ok(None);
+        }
+        self.chars[start..self.pos]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .map(Some)
+            .map_err(|_| "That number is too big.".to_string())
+    }
+
+    fn condition(&mut self) -> Result<Option<Condition>, String> {
+        let compare = if self.eat(">=") {
+            Compare::Ge
+        } else if self.eat("<=") {
+            Compare::Le
+        } else if self.eat(">") {
+            Compare::Gt
+        } else if self.eat("<") {
+            Compare::Lt
+        } else if self.eat("=") {
+            Compare::Eq
+        } else {
+            // a bare number means an exact match, e.g. `r1`
+            return Ok(self.number()?.map(|n| {
+                Condition {
+                    compare: Compare::Eq,
+                    target:  n as i64,
+                }
+            }));
+        };
+        let negative = self.eat("-");
+        match self.number()? {
+            Some(n) => {
+                Ok(Some(Condition {
+                    compare,
+                    target: if negative { -(n as i64) } else { n as i64 },
+                }))
+            },
+            None => Err("Expected a number to compare against.".to_string()),
+        }
+    }
+
+    fn dice(&mut self, count: u32) -> Result<Dice, String> {
+        let sides = if self.eat("%") {
+            Sides::Number(100)
+        } else if self.eat("f") {
+            Sides::Fudge
+        } else {
+            match self.number()? {
+                Some(n) => Sides::Number(n),
+                None => return Err("Expected the number of sides after `d`.".to_string()),
+            }
+        };
+        if count == 0 || count > MAX_DICE {
+            return Err(format!("You can roll between 1 and {} dice.", MAX_DICE));
+        }
+        if let Sides::Number(n) = sides {
+            if n == 0 || n > MAX_SIDES {
+                return Err(format!("Dice can have between 1 and {} sides.", MAX_SIDES));
+            }
+        }
+        let mut dice = Dice {
+            count,
+            sides,
+            keep: None,
+            explode: None,
+            reroll: None,
+            success: None,
+        };
+        loop {
+            // the longer forms have to be tried first, `k` alone keeps highest
+            // and `d` alone drops lowest
+            let keep: Option<fn(u32) -> Keep> = if self.eat("kh") {
+                Some(Keep::Highest)
+            } else if self.eat("kl") {
+                Some(Keep::Lowest)
+            } else if self.eat("k") {
+                Some(Keep::Highest)
+            } else if self.eat("dh") {
+                Some(Keep::DropHighest)
+            } else if self.eat("dl") || self.eat("d") {
+                Some(Keep::DropLowest)
+            } else {
+                None
+            };
+            if let Some(keep) = keep {
+                if dice.keep.is_some() {
+                    return Err("Dice can only keep or drop once.".to_string());
+                }
+                let n = self.number()?.unwrap_or(1);
+                if n == 0 {
+                    return Err("Keep or drop at least one die.".to_string());
+                }
+                dice.keep = Some(keep(n));
+            } else if self.eat("!") {
+                let (_, max) = sides.faces();
+                dice.explode = Some(self.condition()?.unwrap_or(Condition {
+                    compare: Compare::Ge,
+                    target:  max,
+                }));
+            } else if self.eat("ro") || self.eat("r") {
+                let once = self.chars[self.pos - 1] == 'o';
+                match self.condition()? {
+                    Some(condition) => dice.reroll = Some((condition, once)),
+                    None => return Err("Expected what to reroll, e.g. `r1`.".to_string()),
+                }
+            } else if matches!(self.peek(), Some('>' | '<' | '=')) {
+                dice.success = self.condition()?;
+            } else {
+                break;
+            }
+        }
+        let (min, max) = sides.faces();
+        let always = |condition: Condition| (min..=max).all(|face| condition.test(face));
+        if dice.explode.is_some_and(always) {
+            return Err("Those dice would explode forever.".to_string());
+        }
+        if dice.reroll.is_some_and(|(condition, _)| always(condition)) {
+            return Err("Those dice would be rerolled forever.".to_string());
+        }
+        Ok(dice)
+    }
+}
+
+/// Parses dice notation. Whitespace is ignored and letters can be in any case.
+pub fn parse(input: &str) -> Result<Expr, String> {
+    let mut parser = Parser {
+        chars: input
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect(),
+        pos:   0,
+    };
+    if parser.chars.is_empty() {
+        return Err("Nothing to roll.".to_string());
+    }
+    let mut standard = vec![];
+    let mut narrative = vec![];
+    let mut sign = 1;
+    if parser.eat("-") {
+        sign = -1;
+    } else {
+        parser.eat("+");
+    }
+    loop {
+        let count = parser.number()?;
+        if parser.eat("d") {
+            standard.push((sign, Term::Dice(parser.dice(count.unwrap_or(1))?)));
+        } else if let Some(die) = parser.peek().and_then(NarrativeDie::from_letter) {
+            parser.pos += 1;
+            let count = count.unwrap_or(1);
+            if count == 0 || count > MAX_DICE {
+                return Err(format!("You can roll between 1 and {} dice.", MAX_DICE));
+            }
+            if sign < 0 {
+                return Err("Narrative dice can't be subtracted.".to_string());
+            }
+            narrative.push((count, die));
+        } else if let Some(n) = count {
+            standard.push((sign, Term::Number(n as i64)));
+        } else {
+            return Err(match parser.peek() {
+                Some(c) => format!("Unexpected `{}` in the roll.", c),
+                None => "The roll ends too early.".to_string(),
+            });
+        }
+        if !standard.is_empty() && !narrative.is_empty() {
+            return Err("Narrative dice can't be mixed with other dice.".to_string());
+        }
+        if standard.len() + narrative.len() > MAX_TERMS {
+            return Err(format!("Rolls can have at most {} parts.", MAX_TERMS));
+        }
+        match parser.peek() {
+            None => break,
+            Some('+') => {
+                parser.pos += 1;
+                sign = 1;
+            },
+            Some('-') => {
+                parser.pos += 1;
+                sign = -1;
+            },
+            // narrative pools are often written without separators, e.g. `2g1p`
+            Some(_) if !narrative.is_empty() => sign = 1,
+            Some(c) => return Err(format!("Unexpected `{}` in the roll.", c)),
+        }
+    }
+    Ok(if narrative.is_empty() {
+        Expr::Standard(standard)
+    } else {
+        Expr::Narrative(narrative)
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DieRoll {
+    pub value:    i64,
+    /// Earlier values that were rerolled.
+    pub rerolled: Vec<i64>,
+    pub kept:     bool,
+    /// Whether the die exploded into the one after it.
+    pub exploded: bool,
+    pub success:  Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TermOutcome {
+    Dice {
+        dice:  Dice,
+        rolls: Vec<DieRoll>,
+        total: i64,
+    },
+    Number(i64),
+}
+
+impl TermOutcome {
+    pub fn total(&self) -> i64 {
+        match self {
+            Self::Dice { total, .. } => *total,
+            Self::Number(n) => *n,
+        }
+    }
+}
+
+/// Counts of narrative symbols.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Symbols {
+    pub success:   i64,
+    pub advantage: i64,
+    pub triumph:   i64,
+    pub failure:   i64,
+    pub threat:    i64,
+    pub despair:   i64,
+    pub light:     i64,
+    pub dark:      i64,
+}
+
+impl Symbols {
+    fn add_face(&mut self, face: &str) {
+        for symbol in face.chars() {
+            match symbol {
+                'S' => self.success += 1,
+                'A' => self.advantage += 1,
+                '!' => self.triumph += 1,
+                'F' => self.failure += 1,
+                'T' => self.threat += 1,
+                'X' => self.despair += 1,
+                'L' => self.light += 1,
+                'D' => self.dark += 1,
+                _ => {},
+            }
+        }
+    }
+
+    /// Successes less failures, with triumphs and despairs counting as both.
+    pub fn net_success(&self) -> i64 {
+        self.success + self.triumph - self.failure - self.despair
+    }
+
+    pub fn net_advantage(&self) -> i64 {
+        self.advantage - self.threat
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Standard {
+        terms: Vec<(i64, TermOutcome)>,
+        total: i64,
+    },
+    Narrative {
+        dice:    Vec<(NarrativeDie, Vec<&'static str>)>,
+        symbols: Symbols,
+    },
+}
+
+fn roll_dice(dice: Dice, roll: &mut impl FnMut(u32) -> u32) -> (Vec<DieRoll>, i64) {
+    let mut die = || {
+        match dice.sides {
+            Sides::Number(n) => roll(n) as i64,
+            Sides::Fudge => roll(3) as i64 - 2,
+        }
+    };
+    let mut rolls: Vec<DieRoll> = vec![];
+    let mut extra = 0;
+    for _ in 0..dice.count {
+        let mut value = die();
+        let mut rerolled = vec![];
+        if let Some((condition, once)) = dice.reroll {
+            while condition.test(value) && extra < MAX_EXTRA_ROLLS {
+                rerolled.push(value);
+                value = die();
+                extra += 1;
+                if once {
+                    break;
+                }
+            }
+        }
+        rolls.push(DieRoll {
+            value,
+            rerolled,
+            kept: true,
+            exploded: false,
+            success: None,
+        });
+        while let Some(condition) = dice.explode {
+            let last = rolls.last_mut().unwrap();
+            if !condition.test(last.value) || extra >= MAX_EXTRA_ROLLS {
+                break;
+            }
+            last.exploded = true;
+            extra += 1;
+            rolls.push(DieRoll {
+                value:    die(),
+                rerolled: vec![],
+                kept:     true,
+                exploded: false,
+                success:  None,
+            });
+        }
+    }
+    if let Some(keep) = dice.keep {
+        let mut order = (0..rolls.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&i| rolls[i].value);
+        let len = rolls.len();
+        let dropped = match keep {
+            Keep::Highest(n) => order[..len.saturating_sub(n as usize)].to_vec(),
+            Keep::Lowest(n) => order[(n as usize).min(len)..].to_vec(),
+            Keep::DropHighest(n) => order[len.saturating_sub(n as usize)..].to_vec(),
+            Keep::DropLowest(n) => order[..(n as usize).min(len)].to_vec(),
+        };
+        for i in dropped {
+            rolls[i].kept = false;
+        }
+    }
+    let total = match dice.success {
+        Some(condition) => {
+            let mut successes = 0;
+            for r in rolls.iter_mut().filter(|r| r.kept) {
+                let success = condition.test(r.value);
+                r.success = Some(success);
+                successes += success as i64;
+            }
+            successes
+        },
+        None => rolls.iter().filter(|r| r.kept).map(|r| r.value).sum(),
+    };
+    (rolls, total)
+}
+
+/// Rolls a parsed expression. `roll` is given a number of sides and returns a
+/// result from 1 to that number.
+pub fn evaluate(expr: &Expr, roll: &mut impl FnMut(u32) -> u32) -> Outcome {
+    match expr {
+        Expr::Standard(terms) => {
+            let terms = terms
+                .iter()
+                .map(|&(sign, term)| {
+                    (sign, match term {
+                        Term::Dice(dice) => {
+                            let (rolls, total) = roll_dice(dice, roll);
+                            TermOutcome::Dice { dice, rolls, total }
+                        },
+                        Term::Number(n) => TermOutcome::Number(n),
+                    })
+                })
+                .collect::<Vec<_>>();
+            let total = terms.iter().map(|(sign, t)| sign * t.total()).sum();
+            Outcome::Standard { terms, total }
+        },
+        Expr::Narrative(pool) => {
+            let mut symbols = Symbols::default();
+            let dice = pool
+                .iter()
+                .map(|&(count, die)| {
+                    let faces = die.faces();
+                    let rolled = (0..count)
+                        .map(|_| {
+                            let face = faces[(roll(faces.len() as u32) as usize - 1) % faces.len()];
+                            symbols.add_face(face);
+                            face
+                        })
+                        .collect();
+                    (die, rolled)
+                })
+                .collect();
+            Outcome::Narrative { dice, symbols }
+        },
+    }
+}
+
+/// Rolls a parsed expression with real dice.
+pub fn roll(expr: &Expr) -> Outcome {
+    let mut rng = rand::thread_rng();
+    evaluate(expr, &mut |sides| rng.gen_range(1..=sides))
+}
+
+fn plural(n: i64, word: &str) -> String {
+    format!("{} {}{}", n, word, if n == 1 { "" } else { "s" })
+}
+
+impl Outcome {
+    /// Shows every die rolled. Dropped dice and rerolled values are struck
+    /// through, successes are bold and exploding dice are marked with `!`.
+    pub fn breakdown(&self) -> String {
+        match self {
+            Self::Standard { terms, .. } => {
+                let mut out = String::new();
+                for (i, (sign, term)) in terms.iter().enumerate() {
+                    if i > 0 || *sign < 0 {
+                        out.push_str(if *sign < 0 { " - " } else { " + " });
+                    }
+                    match term {
+                        TermOutcome::Number(n) => write!(out, "{}", n).unwrap(),
+                        TermOutcome::Dice { rolls, .. } => {
+                            let dice = rolls
+                                .iter()
+                                .map(|r| {
+                                    let mut s = String::new();
+                                    for old in &r.rerolled {
+                                        write!(s, "~~{}~~ ", old).unwrap();
+                                    }
+                                    let value =
+                                        format!("{}{}", r.value, if r.exploded { "!" } else { "" });
+                                    if !r.kept {
+                                        write!(s, "~~{}~~", value).unwrap();
+                                    } else if r.success == Some(true) {
+                                        write!(s, "**{}**", value).unwrap();
+                                    } else {
+                                        s.push_str(&value);
+                                    }
+                                    s
+                                })
+                                .collect::<Vec<_>>()
+                                .join(", ");
+                            write!(out, "[{}]", dice).unwrap();
+                        },
+                    }
+                }
+                out.trim_start().to_string()
+            },
+            Self::Narrative { dice, .. } => {
+                dice.iter()
+                    .map(|(die, faces)| {
+                        format!(
+                            "{}: {}",
+                            die.name(),
+                            faces
+                                .iter()
+                                .map(|f| if f.is_empty() { "-" } else { f })
+                                .collect::<Vec<_>>()
+                                .join(" ")
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            },
+        }
+    }
+
+    /// The result in words, e.g. `17` or `Success with 2 advantage`.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Standard { terms, .. } => {
+                let total = self.total();
+                let counts_successes = terms.iter().any(
+                    |(_, t)| matches!(t, TermOutcome::Dice { dice, .. } if dice.success.is_some()),
+                );
+                if counts_successes {
+                    format!("{} success{}", total, if total == 1 { "" } else { "es" })
+                } else {
+                    total.to_string()
+                }
+            },
+            Self::Narrative { dice, symbols } => {
+                let mut parts = vec![];
+                let rolled_check = dice.iter().any(|(die, _)| *die != NarrativeDie::Force);
+                if rolled_check {
+                    let net = symbols.net_success();
+                    parts.push(if net > 0 {
+                        format!("Success ({} net)", net)
+                    } else {
+                        "Failure".to_string()
+                    });
+                    let advantage = symbols.net_advantage();
+                    if advantage > 0 {
+                        parts.push(format!("{} advantage", advantage));
+                    } else if advantage < 0 {
+                        parts.push(format!("{} threat", -advantage));
+                    }
+                    if symbols.triumph > 0 {
+                        parts.push(plural(symbols.triumph, "triumph"));
+                    }
+                    if symbols.despair > 0 {
+                        parts.push(format!("{} despair", symbols.despair));
+                    }
+                }
+                if symbols.light > 0 || symbols.dark > 0 {
+                    parts.push(format!(
+                        "{} light side, {} dark side",
+                        symbols.light, symbols.dark
+                    ));
+                }
+                parts.join(", ")
+            },
+        }
+    }
+
+    /// The numeric result, or net successes for narrative pools.
+    pub fn total(&self) -> i64 {
+        match self {
+            Self::Standard { total, .. } => *total,
+            Self::Narrative { symbols, .. } => symbols.net_success(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A roller that returns the given values in order.
+    fn scripted(values: &[u32]) -> impl FnMut(u32) -> u32 + '_ {
+        let mut values = values.iter();
+        move |_| *values.next().expect("ran out of scripted rolls")
+    }
+
+    fn roll(notation: &str, values: &[u32]) -> Outcome {
+        evaluate(&parse(notation).unwrap(), &mut scripted(values))
+    }
+
+    fn dice(notation: &str) -> Dice {
+        match parse(notation).unwrap() {
+            Expr::Standard(terms) => {
+                match terms[0].1 {
+                    Term::Dice(dice) => dice,
+                    _ => panic!("not dice"),
+                }
+            },
+            _ => panic!("not standard"),
+        }
+    }
+
+    #[test]
+    fn test_parse_standard() {
+        let d = dice("2d20kh1");
+        assert_eq!(d.count, 2);
+        assert_eq!(d.sides, Sides::Number(20));
+        assert_eq!(d.keep, Some(Keep::Highest(1)));
+        assert_eq!(dice("d6").count, 1);
+        assert_eq!(dice("d%").sides, Sides::Number(100));
+        assert_eq!(dice("4dF").sides, Sides::Fudge);
+        assert_eq!(dice("4d6dl").keep, Some(Keep::DropLowest(1)));
+        assert_eq!(dice("4d6k3").keep, Some(Keep::Highest(3)));
+        assert_eq!(dice("2d20kl1").keep, Some(Keep::Lowest(1)));
+        assert_eq!(dice("4d6dh").keep, Some(Keep::DropHighest(1)));
+        assert_eq!(
+            parse("2d20kh1 + 5").unwrap(),
+            Expr::Standard(vec![(1, Term::Dice(dice("2d20kh1"))), (1, Term::Number(5))])
+        );
+        assert_eq!(
+            parse("-1+d4").unwrap(),
+            Expr::Standard(vec![(-1, Term::Number(1)), (1, Term::Dice(dice("d4")))])
+        );
+    }
+
+    #[test]
+    fn test_parse_modifiers() {
+        assert_eq!(
+            dice("3d6!").explode,
+            Some(Condition {
+                compare: Compare::Ge,
+                target:  6,
+            })
+        );
+        assert_eq!(
+            dice("3d6!>=5").explode,
+            Some(Condition {
+                compare: Compare::Ge,
+                target:  5,
+            })
+        );
+        assert_eq!(
+            dice("4d6r1").reroll,
+            Some((
+                Condition {
+                    compare: Compare::Eq,
+                    target:  1,
+                },
+                false
+            ))
+        );
+        assert_eq!(
+            dice("4d6ro<3").reroll,
+            Some((
+                Condition {
+                    compare: Compare::Lt,
+                    target:  3,
+                },
+                true
+            ))
+        );
+        assert_eq!(
+            dice("5d10>=8").success,
+            Some(Condition {
+                compare: Compare::Ge,
+                target:  8,
+            })
+        );
+        let d = dice("6d10!r1>7");
+        assert!(d.explode.is_some() && d.reroll.is_some() && d.success.is_some());
+    }
+
+    #[test]
+    fn test_parse_narrative() {
+        assert_eq!(
+            parse("2g1Y 2p").unwrap(),
+            Expr::Narrative(vec![
+                (2, NarrativeDie::Ability),
+                (1, NarrativeDie::Proficiency),
+                (2, NarrativeDie::Difficulty),
+            ])
+        );
+        assert_eq!(
+            parse("w+b+k+r").unwrap(),
+            Expr::Narrative(vec![
+                (1, NarrativeDie::Force),
+                (1, NarrativeDie::Boost),
+                (1, NarrativeDie::Setback),
+                (1, NarrativeDie::Challenge),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("").is_err());
+        assert!(parse("2d").is_err());
+        assert!(parse("d0").is_err());
+        assert!(parse("101d6").is_err());
+        assert!(parse("1d1001").is_err());
+        assert!(parse("2d6+3g").is_err());
+        assert!(parse("2g-1p").is_err());
+        assert!(parse("1d1!").is_err());
+        assert!(parse("1d6r<7").is_err());
+        assert!(parse("2d6kh0").is_err());
+        assert!(parse("2d6kh1kl1").is_err());
+        assert!(parse("2d6r").is_err());
+        assert!(parse("2d6>").is_err());
+        assert!(parse("abc").is_err());
+        assert!(parse(&format!("{}1", "1+".repeat(20))).is_err());
+    }
+
+    #[test]
+    fn test_keep_and_drop() {
+        assert_eq!(roll("2d20kh1+5", &[4, 17]).total(), 22);
+        assert_eq!(roll("2d20kl1+5", &[4, 17]).total(), 9);
+        assert_eq!(roll("4d6dl1", &[3, 5, 1, 6]).total(), 14);
+        assert_eq!(roll("4d6dh2", &[3, 5, 1, 6]).total(), 4);
+        assert_eq!(roll("2d6kh5", &[3, 5]).total(), 8);
+        assert_eq!(roll("1d8-1d4-2", &[8, 3]).total(), 3);
+    }
+
+    #[test]
+    fn test_explode() {
+        // the first die explodes into the second
+        assert_eq!(roll("3d6!", &[6, 2, 3, 1]).total(), 12);
+        // explosions can chain
+        assert_eq!(roll("1d6!", &[6, 6, 1]).total(), 13);
+        assert_eq!(roll("2d10!>=9", &[9, 1, 4]).total(), 14);
+    }
+
+    #[test]
+    fn test_reroll() {
+        assert_eq!(roll("2d6r1", &[1, 1, 4, 5]).total(), 9);
+        assert_eq!(roll("2d6ro1", &[1, 1, 5]).total(), 6);
+        assert_eq!(roll("1d6r<3", &[2, 1, 3]).total(), 3);
+    }
+
+    #[test]
+    fn test_successes() {
+        let outcome = roll("5d10>=8", &[8, 3, 10, 7, 9]);
+        assert_eq!(outcome.total(), 3);
+        assert_eq!(outcome.summary(), "3 successes");
+        assert_eq!(roll("1d10>=8", &[9]).summary(), "1 success");
+        assert_eq!(roll("3d6>4+1", &[5, 4, 6]).total(), 3);
+    }
+
+    #[test]
+    fn test_fudge() {
+        assert_eq!(roll("4dF", &[1, 2, 3, 3]).total(), 1);
+    }
+
+    #[test]
+    fn test_breakdown() {
+        assert_eq!(roll("2d20kh1+5", &[4, 17]).breakdown(), "[~~4~~, 17] + 5");
+        assert_eq!(roll("2d6r1", &[1, 4, 5]).breakdown(), "[~~1~~ 4, 5]");
+        assert_eq!(roll("1d6!", &[6, 2]).breakdown(), "[6!, 2]");
+        assert_eq!(roll("3d10>=8", &[8, 3, 9]).breakdown(), "[**8**, 3, **9**]");
+        assert_eq!(roll("-1d4", &[2]).breakdown(), "- [2]");
+        assert_eq!(roll("17", &[]).summary(), "17");
+    }
+
+    #[test]
+    fn test_narrative() {
+        // ability faces 4 and 8 are SS and AA, difficulty face 2 is F
+        let outcome = roll("2g1p", &[4, 8, 2]);
+        match outcome {
+            Outcome::Narrative { symbols, .. } => {
+                assert_eq!(symbols.net_success(), 1);
+                assert_eq!(symbols.net_advantage(), 2);
+            },
+            _ => panic!("not narrative"),
+        }
+        assert_eq!(outcome.summary(), "Success (1 net), 2 advantage");
+        assert_eq!(outcome.breakdown(), "Ability: SS AA\nDifficulty: F");
+        // proficiency face 12 is a triumph, challenge face 12 a despair
+        let outcome = roll("1y1r", &[12, 12]);
+        assert_eq!(outcome.summary(), "Failure, 1 triumph, 1 despair");
+        let outcome = roll("1k1b", &[5, 1]);
+        assert_eq!(outcome.summary(), "Failure, 1 threat");
+        assert_eq!(outcome.breakdown(), "Setback: T\nBoost: -");
+        assert_eq!(roll("2w", &[7, 12]).summary(), "2 light side, 2 dark side");
+    }
+}
@@ -0,0 +1,124 @@
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+
+/// Longest duration [`parse_duration`] accepts, so adding one to a date can't
+/// run past what dates can hold.
+pub const MAX_DURATION: Duration = Duration::days(3650);
+
+/// Parses a duration such as `3d`, `1d12h` or `90m`. Supported units are
+/// weeks, days, hours, minutes and seconds, up to [`MAX_DURATION`] in total.
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    if s.is_empty() {
+        return None;
+    }
+    let mut total = Duration::zero();
+    let mut digits = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        if c.is_whitespace() && digits.is_empty() {
+            continue;
+        }
+        let n = digits.parse::<i64>().ok()?;
+        digits.clear();
+        total = total.checked_add(&match c.to_ascii_lowercase() {
+            'w' => Duration::try_weeks(n)?,
+            'd' => Duration::try_days(n)?,
+            'h' => Duration::try_hours(n)?,
+            'm' => Duration::try_minutes(n)?,
+            's' => Duration::try_seconds(n)?,
+            _ => return None,
+        })?;
+    }
+    // a trailing number without a unit is ambiguous
+    if !digits.is_empty() || total <= Duration::zero() || total > MAX_DURATION {
+        return None;
+    }
+    Some(total)
+}
+
+/// Parses when something happens, either a UTC date and time such as
+/// `2026-10-24 19:30` or a duration from `now` such as `2d`.
+pub fn parse_time(s: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    match NaiveDateTime::parse_from_str(s.trim(), "%Y-%m-%d %H:%M") {
+        Ok(time) => Some(time.and_utc()),
+        Err(_) => parse_duration(s).and_then(|d| now.checked_add_signed(d)),
+    }
+}
+
+/// Formats a duration as its largest units, e.g. `1d 12h`.
+pub fn format_duration(duration: Duration) -> String {
+    let mut seconds = duration.num_seconds().max(0);
+    let mut parts = vec![];
+    for (unit, size) in [("d", 86400), ("h", 3600), ("m", 60), ("s", 1)] {
+        if seconds >= size {
+            parts.push(format!("{}{}", seconds / size, unit));
+            seconds %= size;
+        }
+    }
+    if parts.is_empty() {
+        "0s".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("3d"), Some(Duration::days(3)));
+        assert_eq!(
+            parse_duration("1d12h"),
+            Some(Duration::days(1) + Duration::hours(12))
+        );
+        assert_eq!(
+            parse_duration("1h 30m"),
+            Some(Duration::hours(1) + Duration::minutes(30))
+        );
+        assert_eq!(parse_duration("2w"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration("45s"), Some(Duration::seconds(45)));
+        assert_eq!(parse_duration("10M"), Some(Duration::minutes(10)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("3"), None);
+        assert_eq!(parse_duration("3y"), None);
+        assert_eq!(parse_duration("d"), None);
+        assert_eq!(parse_duration("0d"), None);
+        assert_eq!(parse_duration("3650d"), Some(MAX_DURATION));
+        assert_eq!(parse_duration("3651d"), None);
+        assert_eq!(parse_duration("9999999999999w"), None);
+        assert_eq!(parse_duration("9223372036854775807s1s"), None);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        let now = DateTime::from_timestamp(1_800_000_000, 0).unwrap();
+        assert_eq!(
+            parse_time("2026-10-24 19:30", now),
+            Some(
+                NaiveDateTime::parse_from_str("2026-10-24 19:30", "%Y-%m-%d %H:%M")
+                    .unwrap()
+                    .and_utc()
+            )
+        );
+        assert_eq!(parse_time("2h", now), Some(now + Duration::hours(2)));
+        assert_eq!(parse_time("2026-10-24", now), None);
+        assert_eq!(parse_time("tomorrow", now), None);
+        assert_eq!(parse_time("99999999w", now), None);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::days(3)), "3d");
+        assert_eq!(
+            format_duration(Duration::days(1) + Duration::hours(12)),
+            "1d 12h"
+        );
+        assert_eq!(format_duration(Duration::minutes(90)), "1h 30m");
+        assert_eq!(format_duration(Duration::zero()), "0s");
+    }
+}
@@ -39,9 +39,15 @@ pub struct Embed {
 impl Embed {
     #[inline]
     pub fn new(ctx: &Ctx<'_>, style: EmbedStyle) -> Self {
+        Self::plain(style).author(ctx.author())
+    }
+
+    /// An embed without an author, for messages sent outside of a command.
+    #[inline]
+    pub fn plain(style: EmbedStyle) -> Self {
         Self {
             embed: CreateEmbed::new(),
-        }.colour(style.colour()).footer("Star Wars Roleplay", Some("https://cdn.discordapp.com/icons/504665700024057886/a_eac97a46a66b93b25e36723221f297c7.webp?size=160")).timestamp(Timestamp::now()).author(ctx.author())
+        }.colour(style.colour()).footer("Star Wars Roleplay", Some("https://cdn.discordapp.com/icons/504665700024057886/a_eac97a46a66b93b25e36723221f297c7.webp?size=160")).timestamp(Timestamp::now())
     }
 
     #[inline]
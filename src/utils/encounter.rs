@@ -0,0 +1,81 @@
+use std::cmp::Reverse;
+
+/// A combatant's place in the turn order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Turn {
+    pub id:         i32,
+    pub initiative: i32,
+    /// Defeated combatants stay in the order but are skipped.
+    pub defeated:   bool,
+}
+
+/// Sorts combatants into turn order: highest initiative first, ties going to
+/// whoever joined the encounter first.
+pub fn sort_turns(turns: &mut [Turn]) {
+    turns.sort_by_key(|t| (Reverse(t.initiative), t.id));
+}
+
+/// Finds who acts after `current` in a sorted turn order, skipping the
+/// defeated, and whether doing so starts a new round. With no current turn
+/// the first combatant standing goes. `None` means nobody is left standing.
+pub fn next_turn(turns: &[Turn], current: Option<i32>) -> Option<(i32, bool)> {
+    let start = match current.and_then(|id| turns.iter().position(|t| t.id == id)) {
+        Some(i) => i + 1,
+        None => 0,
+    };
+    (start..start + turns.len()).find_map(|i| {
+        let turn = turns[i % turns.len()];
+        (!turn.defeated).then_some((turn.id, current.is_some() && i >= turns.len()))
+    })
+}
+
+/// Applies damage (negative) or healing (positive) to hit points, never going
+/// below zero or above the maximum.
+pub fn change_hp(hp: i32, max_hp: i32, change: i32) -> i32 {
+    hp.saturating_add(change).clamp(0, max_hp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(id: i32, initiative: i32, defeated: bool) -> Turn {
+        Turn {
+            id,
+            initiative,
+            defeated,
+        }
+    }
+
+    #[test]
+    fn test_sort_turns() {
+        let mut turns = vec![turn(1, 5, false), turn(2, 18, false), turn(3, 5, false)];
+        sort_turns(&mut turns);
+        assert_eq!(turns.iter().map(|t| t.id).collect::<Vec<_>>(), vec![
+            2, 1, 3
+        ]);
+    }
+
+    #[test]
+    fn test_next_turn() {
+        let turns = vec![turn(2, 18, false), turn(1, 5, true), turn(3, 5, false)];
+        assert_eq!(next_turn(&turns, None), Some((2, false)));
+        assert_eq!(next_turn(&turns, Some(2)), Some((3, false)));
+        assert_eq!(next_turn(&turns, Some(3)), Some((2, true)));
+        // a combatant that has left the encounter starts the order over
+        assert_eq!(next_turn(&turns, Some(9)), Some((2, false)));
+        let turns = vec![turn(2, 18, true), turn(1, 5, false)];
+        assert_eq!(next_turn(&turns, None), Some((1, false)));
+        assert_eq!(next_turn(&turns, Some(1)), Some((1, true)));
+        assert_eq!(next_turn(&[turn(1, 5, true)], Some(1)), None);
+        assert_eq!(next_turn(&[], None), None);
+    }
+
+    #[test]
+    fn test_change_hp() {
+        assert_eq!(change_hp(10, 10, -3), 7);
+        assert_eq!(change_hp(2, 10, -5), 0);
+        assert_eq!(change_hp(8, 10, 5), 10);
+        assert_eq!(change_hp(0, 10, 0), 0);
+    }
+}
@@ -0,0 +1,126 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+/// A hyperspace route between two planets, usable in both directions.
+#[derive(Clone, Copy, Debug)]
+pub struct Route<'a> {
+    pub from:     &'a str,
+    pub to:       &'a str,
+    /// Travel time in seconds.
+    pub duration: i64,
+    pub cost:     i64,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Journey {
+    /// Every planet along the way, starting with the origin.
+    pub stops:    Vec<String>,
+    pub duration: i64,
+    pub cost:     i64,
+}
+
+/// Plans the quickest journey between two planets, taking the cheaper one
+/// when two are as quick. `None` if there's no way there.
+pub fn plan_journey(routes: &[Route<'_>], from: &str, to: &str) -> Option<Journey> {
+    let mut best: HashMap<&str, (i64, i64, Option<&str>)> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    best.insert(from, (0, 0, None));
+    queue.push(Reverse((0, 0, from)));
+    while let Some(Reverse((duration, cost, planet))) = queue.pop() {
+        if planet == to {
+            let mut stops = vec![to.to_string()];
+            let mut at = to;
+            while let Some((_, _, Some(previous))) = best.get(at) {
+                stops.push(previous.to_string());
+                at = previous;
+            }
+            stops.reverse();
+            return Some(Journey {
+                stops,
+                duration,
+                cost,
+            });
+        }
+        if best
+            .get(planet)
+            .is_some_and(|&(d, c, _)| (d, c) < (duration, cost))
+        {
+            continue;
+        }
+        let neighbours = routes.iter().filter_map(|r| {
+            if r.from == planet {
+                Some((r.to, r))
+            } else if r.to == planet {
+                Some((r.from, r))
+            } else {
+                None
+            }
+        });
+        for (next, route) in neighbours {
+            let arrival = (duration + route.duration, cost + route.cost);
+            if best.get(next).is_none_or(|&(d, c, _)| arrival < (d, c)) {
+                best.insert(next, (arrival.0, arrival.1, Some(planet)));
+                queue.push(Reverse((arrival.0, arrival.1, next)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn route<'a>(from: &'a str, to: &'a str, duration: i64, cost: i64) -> Route<'a> {
+        Route {
+            from,
+            to,
+            duration,
+            cost,
+        }
+    }
+
+    #[test]
+    fn test_plan_journey() {
+        let routes = vec![
+            route("Coruscant", "Corellia", 60, 100),
+            route("Corellia", "Kessel", 60, 100),
+            route("Coruscant", "Kessel", 180, 50),
+            route("Tatooine", "Geonosis", 30, 10),
+        ];
+        assert_eq!(
+            plan_journey(&routes, "Coruscant", "Kessel"),
+            Some(Journey {
+                stops:    vec![
+                    "Coruscant".to_string(),
+                    "Corellia".to_string(),
+                    "Kessel".to_string()
+                ],
+                duration: 120,
+                cost:     200,
+            })
+        );
+        // routes work both ways
+        assert_eq!(
+            plan_journey(&routes, "Kessel", "Corellia").map(|j| j.stops),
+            Some(vec!["Kessel".to_string(), "Corellia".to_string()])
+        );
+        assert_eq!(plan_journey(&routes, "Coruscant", "Tatooine"), None);
+        assert_eq!(plan_journey(&routes, "Coruscant", "Hoth"), None);
+    }
+
+    #[test]
+    fn test_plan_journey_prefers_cheaper_ties() {
+        let routes = vec![
+            route("A", "B", 60, 100),
+            route("B", "C", 60, 100),
+            route("A", "C", 120, 50),
+        ];
+        assert_eq!(
+            plan_journey(&routes, "A", "C").map(|j| (j.duration, j.cost)),
+            Some((120, 50))
+        );
+    }
+}
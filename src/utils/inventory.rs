@@ -0,0 +1,123 @@
+use sea_orm::{prelude::*, IntoActiveModel, Set};
+
+/// Adds `quantity` of an item to a character's inventory, stacking it with an
+/// existing item of the same name.
+pub async fn add_item(
+    db: &DatabaseConnection,
+    character: i32,
+    name: &str,
+    quantity: i32,
+) -> Result<(), sea_orm::DbErr> {
+    let item = entity::inventory_item::Entity::find()
+        .filter(entity::inventory_item::Column::Character.eq(character))
+        .filter(entity::inventory_item::Column::Name.like(name))
+        .one(db)
+        .await?;
+    match item {
+        Some(item) => {
+            let mut item = item.into_active_model();
+            item.quantity = Set(item.quantity.unwrap() + quantity);
+            item.save(db).await?;
+        },
+        None => {
+            entity::inventory_item::ActiveModel {
+                character: Set(character),
+                name: Set(name.to_string()),
+                quantity: Set(quantity),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        },
+    }
+    Ok(())
+}
+
+/// Removes `quantity` of an item from a character's inventory, returning
+/// `false` if the character doesn't have enough of it.
+pub async fn remove_item(
+    db: &DatabaseConnection,
+    character: i32,
+    name: &str,
+    quantity: i32,
+) -> Result<bool, sea_orm::DbErr> {
+    let item = entity::inventory_item::Entity::find()
+        .filter(entity::inventory_item::Column::Character.eq(character))
+        .filter(entity::inventory_item::Column::Name.like(name))
+        .one(db)
+        .await?;
+    match item {
+        Some(item) if item.quantity > quantity => {
+            let mut item = item.into_active_model();
+            item.quantity = Set(item.quantity.unwrap() - quantity);
+            item.save(db).await?;
+            Ok(true)
+        },
+        Some(item) if item.quantity == quantity => {
+            item.delete(db).await?;
+            Ok(true)
+        },
+        _ => Ok(false),
+    }
+}
+
+/// Adds `quantity` of an item to a ship's cargo hold, stacking it with an
+/// existing item of the same name.
+pub async fn add_cargo(
+    db: &DatabaseConnection,
+    ship: i32,
+    name: &str,
+    quantity: i32,
+) -> Result<(), sea_orm::DbErr> {
+    let item = entity::ship_cargo::Entity::find()
+        .filter(entity::ship_cargo::Column::Ship.eq(ship))
+        .filter(entity::ship_cargo::Column::Name.like(name))
+        .one(db)
+        .await?;
+    match item {
+        Some(item) => {
+            let mut item = item.into_active_model();
+            item.quantity = Set(item.quantity.unwrap() + quantity);
+            item.save(db).await?;
+        },
+        None => {
+            entity::ship_cargo::ActiveModel {
+                ship: Set(ship),
+                name: Set(name.to_string()),
+                quantity: Set(quantity),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        },
+    }
+    Ok(())
+}
+
+/// Removes `quantity` of an item from a ship's cargo hold, returning `false`
+/// if there isn't enough of it aboard.
+pub async fn remove_cargo(
+    db: &DatabaseConnection,
+    ship: i32,
+    name: &str,
+    quantity: i32,
+) -> Result<bool, sea_orm::DbErr> {
+    let item = entity::ship_cargo::Entity::find()
+        .filter(entity::ship_cargo::Column::Ship.eq(ship))
+        .filter(entity::ship_cargo::Column::Name.like(name))
+        .one(db)
+        .await?;
+    match item {
+        Some(item) if item.quantity > quantity => {
+            let mut item = item.into_active_model();
+            item.quantity = Set(item.quantity.unwrap() - quantity);
+            item.save(db).await?;
+            Ok(true)
+        },
+        Some(item) if item.quantity == quantity => {
+            item.delete(db).await?;
+            Ok(true)
+        },
+        _ => Ok(false),
+    }
+}
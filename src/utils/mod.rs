@@ -1,5 +1,15 @@
+pub mod audit;
+pub mod automod;
+pub mod calendar;
 pub mod db;
+pub mod dice;
+pub mod duration;
 pub mod embed;
+pub mod encounter;
+pub mod galaxy;
+pub mod inventory;
 pub mod num;
 pub mod perms;
+pub mod proxy;
+pub mod template;
 pub mod xp;
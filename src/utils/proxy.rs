@@ -0,0 +1,118 @@
+use poise::serenity_prelude::{
+    ChannelId,
+    ChannelType,
+    Context,
+    CreateAllowedMentions,
+    CreateAttachment,
+    CreateWebhook,
+    ExecuteWebhook,
+    Message,
+    Webhook,
+};
+use sea_orm::{prelude::*, QueryFilter};
+
+use crate::Data;
+
+const WEBHOOK_NAME: &str = "Gatekeeper";
+
+/// Splits a message of the form `[Trigger] text` into its trigger and text.
+pub fn parse_proxy(content: &str) -> Option<(&str, &str)> {
+    let (trigger, text) = content.strip_prefix('[')?.split_once(']')?;
+    let trigger = trigger.trim();
+    if trigger.is_empty() {
+        return None;
+    }
+    Some((trigger, text.trim()))
+}
+
+/// Finds the bot's webhook in a channel, creating it if it doesn't exist yet.
+async fn webhook(ctx: &Context, channel: ChannelId) -> Result<Webhook, crate::Error> {
+    let me = ctx.cache.current_user().id;
+    let existing = channel
+        .webhooks(&ctx.http)
+        .await?
+        .into_iter()
+        .find(|w| w.user.as_ref().map(|u| u.id) == Some(me) && w.token.is_some());
+    match existing {
+        Some(webhook) => Ok(webhook),
+        None => {
+            Ok(channel
+                .create_webhook(&ctx.http, CreateWebhook::new(WEBHOOK_NAME))
+                .await?)
+        },
+    }
+}
+
+/// Reposts a message as the author's character if it starts with one of their
+/// characters' triggers, returning the text that was posted.
+pub async fn proxy(
+    ctx: &Context,
+    data: &Data,
+    msg: &Message,
+) -> Result<Option<String>, crate::Error> {
+    let Some((trigger, text)) = parse_proxy(&msg.content) else {
+        return Ok(None);
+    };
+    if text.is_empty() && msg.attachments.is_empty() {
+        return Ok(None);
+    }
+    let character = entity::character::Entity::find()
+        .filter(entity::character::Column::Owner.eq(msg.author.id.to_string()))
+        .filter(entity::character::Column::Trigger.like(trigger))
+        .filter(entity::character::Column::Status.eq(entity::character::CharacterStatus::Approved))
+        .one(&data.db)
+        .await?;
+    let Some(character) = character else {
+        return Ok(None);
+    };
+    let Some(channel) = msg.channel(ctx).await?.guild() else {
+        return Ok(None);
+    };
+    // webhooks live on the parent channel, and post into threads from there
+    let (channel_id, thread_id) = match channel.kind {
+        ChannelType::PublicThread | ChannelType::PrivateThread => {
+            match channel.parent_id {
+                Some(parent) => (parent, Some(channel.id)),
+                None => return Ok(None),
+            }
+        },
+        _ => (channel.id, None),
+    };
+    let webhook = webhook(ctx, channel_id).await?;
+    let mut builder = ExecuteWebhook::new()
+        .username(character.name)
+        .content(text)
+        // the original message already pinged anyone it mentioned
+        .allowed_mentions(CreateAllowedMentions::new());
+    if let Some(avatar) = character.avatar {
+        builder = builder.avatar_url(avatar);
+    }
+    if let Some(thread_id) = thread_id {
+        builder = builder.in_thread(thread_id);
+    }
+    for attachment in &msg.attachments {
+        builder = builder.add_file(CreateAttachment::url(&ctx.http, &attachment.url).await?);
+    }
+    webhook.execute(&ctx.http, false, builder).await?;
+    msg.delete(ctx).await?;
+    Ok(Some(text.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proxy() {
+        assert_eq!(
+            parse_proxy("[Kira] hello there"),
+            Some(("Kira", "hello there"))
+        );
+        assert_eq!(parse_proxy("[ Kira ]hello"), Some(("Kira", "hello")));
+        assert_eq!(parse_proxy("[Kira]"), Some(("Kira", "")));
+        assert_eq!(parse_proxy("[] hello"), None);
+        assert_eq!(parse_proxy("[Kira hello"), None);
+        assert_eq!(parse_proxy("Kira] hello"), None);
+        assert_eq!(parse_proxy("hello [Kira]"), None);
+    }
+}
@@ -0,0 +1,70 @@
+/// Values substituted into greeting templates.
+pub struct Placeholders<'a> {
+    pub mention:      &'a str,
+    pub user:         &'a str,
+    pub server:       &'a str,
+    pub member_count: u64,
+    pub account_age:  i64,
+}
+
+/// Describes an age in days as the largest whole unit, e.g. `3 years`.
+pub fn describe_age(days: i64) -> String {
+    let (n, unit) = if days >= 365 {
+        (days / 365, "year")
+    } else if days >= 30 {
+        (days / 30, "month")
+    } else {
+        (days.max(0), "day")
+    };
+    format!("{} {}{}", n, unit, if n == 1 { "" } else { "s" })
+}
+
+/// Fills `{mention}`, `{user}`, `{server}`, `{member_count}` and
+/// `{account_age}` in a template. Unknown placeholders are left as they are.
+pub fn render(template: &str, placeholders: &Placeholders<'_>) -> String {
+    template
+        .replace("{mention}", placeholders.mention)
+        .replace("{user}", placeholders.user)
+        .replace("{server}", placeholders.server)
+        .replace("{member_count}", &placeholders.member_count.to_string())
+        .replace("{account_age}", &describe_age(placeholders.account_age))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_age() {
+        assert_eq!(describe_age(0), "0 days");
+        assert_eq!(describe_age(1), "1 day");
+        assert_eq!(describe_age(29), "29 days");
+        assert_eq!(describe_age(30), "1 month");
+        assert_eq!(describe_age(364), "12 months");
+        assert_eq!(describe_age(365), "1 year");
+        assert_eq!(describe_age(800), "2 years");
+    }
+
+    #[test]
+    fn test_render() {
+        let placeholders = Placeholders {
+            mention:      "<@1>",
+            user:         "kira",
+            server:       "Star Wars Roleplay",
+            member_count: 1234,
+            account_age:  2,
+        };
+        assert_eq!(
+            render(
+                "Welcome {mention} to {server}! You are member #{member_count}.",
+                &placeholders
+            ),
+            "Welcome <@1> to Star Wars Roleplay! You are member #1234."
+        );
+        assert_eq!(
+            render("{user}'s account is {account_age} old", &placeholders),
+            "kira's account is 2 days old"
+        );
+        assert_eq!(render("{unknown}", &placeholders), "{unknown}");
+    }
+}
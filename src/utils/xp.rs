@@ -1,5 +1,5 @@
 use poise::serenity_prelude::{ChannelId, Http};
-use sea_orm::{prelude::*, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use sea_orm::{prelude::*, DatabaseConnection, EntityTrait, IntoActiveModel, QueryFilter, Set};
 use tracing::debug;
 
 use crate::Data;
@@ -73,6 +73,27 @@ pub async fn level_up(
     Ok(false)
 }
 
+/// Adds XP to the member's active character, if they are playing one, so each
+/// character keeps its own tally alongside the account's level. Negative
+/// amounts take XP away, but never below zero.
+pub async fn add_character_xp(
+    db: &DatabaseConnection,
+    member: &entity::member::ActiveModel,
+    xp: i32,
+) -> Result<(), DbErr> {
+    let Some(id) = member.active_character.clone().unwrap() else {
+        return Ok(());
+    };
+    let Some(character) = entity::character::Entity::find_by_id(id).one(db).await? else {
+        return Ok(());
+    };
+    let total = character.xp.saturating_add(xp).max(0);
+    let mut character = character.into_active_model();
+    character.xp = Set(total);
+    character.update(db).await?;
+    Ok(())
+}
+
 pub async fn level_down(
     http: &Http,
     data: &Data,